@@ -2,7 +2,6 @@ use bytes::{BufMut, BytesMut};
 use futures::StreamExt;
 use livekit::prelude::*;
 use livekit::webrtc::audio_stream::native::NativeAudioStream;
-use livekit::webrtc::native::audio_resampler;
 use std::env;
 use tokio::fs::File;
 use tokio::io::{AsyncWriteExt, BufWriter};
@@ -89,7 +88,9 @@ async fn main() {
     let url = env::var("LIVEKIT_URL").expect("LIVEKIT_URL is not set");
     let token = env::var("LIVEKIT_TOKEN").expect("LIVEKIT_TOKEN is not set");
 
-    let (room, mut rx) = Room::connect(&url, &token).await.unwrap();
+    let (room, mut rx) = Room::connect(&url, &token, RoomOptions::default())
+        .await
+        .unwrap();
     println!("Connected to room: {} - {}", room.name(), room.sid());
 
     while let Some(msg) = rx.recv().await {
@@ -121,24 +122,16 @@ async fn record_track(audio_track: RemoteAudioTrack) -> Result<(), std::io::Erro
         num_channels: 2,
     };
 
-    let mut resampler = audio_resampler::AudioResampler::default();
     let mut wav_writer = WavWriter::create(FILE_PATH, header).await?;
-    let mut audio_stream = NativeAudioStream::new(rtc_track);
+    let mut audio_stream = NativeAudioStream::new(rtc_track)
+        .resample(header.sample_rate, header.num_channels)
+        .unwrap();
 
-    let max_record = 5 * header.sample_rate * header.num_channels as u32;
+    let max_record = 5 * header.sample_rate * header.num_channels;
     let mut sample_count = 0;
     'recv_loop: while let Some(frame) = audio_stream.next().await {
-        let data = resampler.remix_and_resample(
-            &frame.data,
-            frame.samples_per_channel,
-            frame.num_channels,
-            frame.sample_rate,
-            header.num_channels,
-            header.sample_rate,
-        );
-
-        for sample in data {
-            wav_writer.write_sample(*sample).await.unwrap();
+        for sample in frame.data {
+            wav_writer.write_sample(sample).await.unwrap();
             sample_count += 1;
 
             if sample_count >= max_record {
@@ -4,8 +4,8 @@ use livekit::options::{TrackPublishOptions, VideoCaptureOptions};
 use livekit::prelude::*;
 use livekit::webrtc::{
     native::yuv_helper,
-    video_frame::native::I420BufferExt,
-    video_frame::{I420Buffer, VideoFrame, VideoRotation},
+    video_frame::native::I420BufferPool,
+    video_frame::{VideoFrame, VideoRotation},
     video_source::native::NativeVideoSource,
 };
 use parking_lot::Mutex;
@@ -17,6 +17,7 @@ use tokio::task::JoinHandle;
 // The logo must not be bigger than the framebuffer
 const PIXEL_SIZE: usize = 4;
 const FRAME_RATE: u64 = 30;
+const BUFFER_POOL_SIZE: usize = 4;
 const MOVE_SPEED: i32 = 16;
 const FB_WIDTH: usize = 1280;
 const FB_HEIGHT: usize = 720;
@@ -25,7 +26,7 @@ const FB_HEIGHT: usize = 720;
 struct FrameData {
     image: Arc<RgbaImage>,
     framebuffer: Arc<Mutex<Vec<u8>>>,
-    video_frame: Arc<Mutex<VideoFrame<I420Buffer>>>,
+    buffer_pool: I420BufferPool,
     pos: (u32, u32),
     direction: (i32, i32),
 }
@@ -63,6 +64,7 @@ impl LogoTrack {
             "livekit_logo",
             VideoCaptureOptions::default(),
             self.rtc_source.clone(),
+            &self.room.runtime(),
         );
 
         let task = tokio::spawn(Self::track_task(close_rx, self.rtc_source.clone()));
@@ -115,11 +117,7 @@ impl LogoTrack {
         let mut data = FrameData {
             image: Arc::new(image),
             framebuffer: Arc::new(Mutex::new(vec![0u8; (FB_WIDTH * FB_HEIGHT * 4) as usize])),
-            video_frame: Arc::new(Mutex::new(VideoFrame {
-                rotation: VideoRotation::VideoRotation0,
-                buffer: I420Buffer::new(FB_WIDTH as u32, FB_HEIGHT as u32),
-                timestamp: 0,
-            })),
+            buffer_pool: I420BufferPool::new(BUFFER_POOL_SIZE),
             pos: (0, 0),
             direction: (1, 1),
         };
@@ -153,8 +151,11 @@ impl LogoTrack {
                 move || {
                     let image = data.image.as_raw();
                     let mut framebuffer = data.framebuffer.lock();
-                    let mut video_frame = data.video_frame.lock();
-                    let i420_buffer = &mut video_frame.buffer;
+                    let mut i420_buffer =
+                        match data.buffer_pool.get(FB_WIDTH as u32, FB_HEIGHT as u32) {
+                            Some(buffer) => buffer,
+                            None => return, // All the buffers are still used by the encoder
+                        };
 
                     let (stride_y, stride_u, stride_v) = i420_buffer.strides();
                     let (data_y, data_u, data_v) = i420_buffer.data_mut();
@@ -187,7 +188,14 @@ impl LogoTrack {
                     )
                     .unwrap();
 
-                    source.capture_frame(&*video_frame);
+                    source.capture_frame(&VideoFrame {
+                        rotation: VideoRotation::VideoRotation0,
+                        buffer: i420_buffer,
+                        timestamp: 0,
+                        rtp_timestamp: None,
+                        ntp_time_ms: None,
+                        receive_time_us: None,
+                    });
                 }
             })
             .await
@@ -1,11 +1,15 @@
 use livekit::options::{AudioCaptureOptions, TrackPublishOptions};
 use livekit::webrtc::audio_frame::AudioFrame;
+use livekit::webrtc::audio_source::native::DEFAULT_QUEUE_SIZE_MS;
+use livekit::webrtc::audio_source::AudioSourceOptions;
 use livekit::{prelude::*, webrtc::audio_source::native::NativeAudioSource};
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 
+const NUM_CHANNELS: u32 = 2;
+const SAMPLES_PER_FRAME: u32 = 1024; // Any size works, the source buffers the samples
+
 #[derive(Clone)]
 struct FrameData {
     pub sample_rate: u32,
@@ -40,7 +44,13 @@ pub struct SineTrack {
 impl SineTrack {
     pub fn new(room: Arc<Room>) -> Self {
         Self {
-            rtc_source: NativeAudioSource::default(),
+            rtc_source: NativeAudioSource::new(
+                AudioSourceOptions::default(),
+                FrameData::default().sample_rate,
+                NUM_CHANNELS,
+                DEFAULT_QUEUE_SIZE_MS,
+            )
+            .unwrap(),
             room,
             handle: None,
         }
@@ -60,6 +70,7 @@ impl SineTrack {
                 noise_suppression: false,
             },
             self.rtc_source.clone(),
+            &self.room.runtime(),
         );
 
         let task = tokio::spawn(Self::track_task(close_rx, self.rtc_source.clone()));
@@ -100,26 +111,10 @@ impl SineTrack {
 
     async fn track_task(mut close_rx: oneshot::Receiver<()>, rtc_source: NativeAudioSource) {
         let mut data = FrameData::default();
-        let mut interval = tokio::time::interval(Duration::from_millis(10));
-        let mut samples_10ms = Vec::<i16>::new();
+        let mut frame = AudioFrame::<f32>::new(data.sample_rate, NUM_CHANNELS, SAMPLES_PER_FRAME);
 
         loop {
-            tokio::select! {
-                _ = &mut close_rx => {
-                    break;
-                }
-                _ = interval.tick() => {}
-            }
-
-            const NUM_CHANNELS: usize = 2;
-
-            let samples_count_10ms = (data.sample_rate / 100) as usize * NUM_CHANNELS;
-
-            if samples_10ms.capacity() != samples_count_10ms {
-                samples_10ms.resize(samples_count_10ms, 0i16);
-            }
-
-            for i in (0..samples_count_10ms).step_by(NUM_CHANNELS) {
+            for samples in frame.data.chunks_exact_mut(NUM_CHANNELS as usize) {
                 let val = data.amplitude
                     * f64::sin(
                         std::f64::consts::PI
@@ -129,19 +124,21 @@ impl SineTrack {
                     );
 
                 data.phase += 1;
+                samples.fill(val as f32);
+            }
 
-                for c in 0..NUM_CHANNELS {
-                    // WebRTC uses 16-bit signed PCM
-                    samples_10ms[i + c] = (val * 32768.0) as i16;
+            // capture_frame waits when we're ahead of real time
+            tokio::select! {
+                _ = &mut close_rx => {
+                    break;
+                }
+                res = rtc_source.capture_frame(&frame) => {
+                    if let Err(err) = res {
+                        tracing::error!("failed to capture the sine frame: {:?}", err);
+                        break;
+                    }
                 }
             }
-
-            rtc_source.capture_frame(&AudioFrame {
-                data: samples_10ms.clone(),
-                sample_rate: data.sample_rate as u32,
-                num_channels: NUM_CHANNELS as u32,
-                samples_per_channel: samples_count_10ms as u32,
-            });
         }
     }
 }
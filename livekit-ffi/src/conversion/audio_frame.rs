@@ -10,6 +10,9 @@ impl proto::AudioFrameBufferInfo {
             samples_per_channel: buffer.samples_per_channel,
             sample_rate: buffer.sample_rate,
             num_channels: buffer.num_channels,
+            ntp_time_ms: buffer.ntp_time_ms,
+            receive_time_us: buffer.receive_time_us,
+            rtp_timestamp: buffer.rtp_timestamp,
         }
     }
 }
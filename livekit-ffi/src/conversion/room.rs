@@ -1,6 +1,10 @@
-use crate::{proto, FfiHandleId, INVALID_HANDLE};
+use crate::{proto, FfiError, FfiHandleId, INVALID_HANDLE};
 use livekit::options::{AudioEncoding, TrackPublishOptions, VideoEncoding};
 use livekit::prelude::*;
+use livekit::webrtc::prelude::{
+    IceTransportsType, NetworkType, PeerConnectionFactoryOptions, RtcConfiguration,
+};
+use livekit::LkRuntime;
 
 impl proto::RoomEvent {
     pub fn from(room_handle: FfiHandleId, event: RoomEvent) -> Option<Self> {
@@ -90,6 +94,8 @@ impl From<proto::TrackPublishOptions> for TrackPublishOptions {
             video_codec: proto::VideoCodec::from_i32(opts.video_codec)
                 .unwrap()
                 .into(),
+            scalability_mode: None,
+            backup_codec: None,
             dtx: opts.dtx,
             red: opts.red,
             simulcast: opts.simulcast,
@@ -99,6 +105,86 @@ impl From<proto::TrackPublishOptions> for TrackPublishOptions {
     }
 }
 
+impl From<proto::NetworkType> for NetworkType {
+    fn from(value: proto::NetworkType) -> Self {
+        match value {
+            proto::NetworkType::NetworkEthernet => Self::Ethernet,
+            proto::NetworkType::NetworkWifi => Self::Wifi,
+            proto::NetworkType::NetworkCellular => Self::Cellular,
+            proto::NetworkType::NetworkVpn => Self::Vpn,
+            proto::NetworkType::NetworkLoopback => Self::Loopback,
+        }
+    }
+}
+
+impl TryFrom<proto::RtcConfig> for RtcConfiguration {
+    type Error = FfiError;
+
+    fn try_from(config: proto::RtcConfig) -> Result<Self, Self::Error> {
+        let port_range = match (config.min_port, config.max_port) {
+            (Some(min_port), Some(max_port)) => {
+                let min_port = u16::try_from(min_port)
+                    .map_err(|_| FfiError::InvalidRequest("min_port is out of range"))?;
+                let max_port = u16::try_from(max_port)
+                    .map_err(|_| FfiError::InvalidRequest("max_port is out of range"))?;
+                if min_port > max_port {
+                    return Err(FfiError::InvalidRequest(
+                        "min_port is greater than max_port",
+                    ));
+                }
+                Some(min_port..=max_port)
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            ice_transport_type: if config.relay_only {
+                IceTransportsType::Relay
+            } else {
+                IceTransportsType::All
+            },
+            port_range,
+            disable_ipv6: config.disable_ipv6,
+            disable_link_local_networks: config.disable_link_local_networks,
+            disable_tcp: config.disable_tcp,
+            ..Default::default()
+        })
+    }
+}
+
+impl TryFrom<proto::RoomOptions> for RoomOptions {
+    type Error = FfiError;
+
+    fn try_from(opts: proto::RoomOptions) -> Result<Self, Self::Error> {
+        let ignored_network_types: Vec<NetworkType> = opts
+            .rtc_config
+            .iter()
+            .flat_map(|config| config.ignored_network_types.iter())
+            .filter_map(|network_type| proto::NetworkType::from_i32(*network_type))
+            .map(Into::into)
+            .collect();
+
+        // The ignored networks are applied by the PeerConnectionFactory
+        let runtime = (!ignored_network_types.is_empty()).then(|| {
+            LkRuntime::new(PeerConnectionFactoryOptions {
+                ignored_network_types,
+                ..Default::default()
+            })
+        });
+
+        Ok(Self {
+            auto_subscribe: opts.auto_subscribe,
+            adaptive_stream: opts.adaptive_stream,
+            rtc_config: opts
+                .rtc_config
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
+            runtime,
+        })
+    }
+}
+
 impl From<proto::VideoEncoding> for VideoEncoding {
     fn from(opts: proto::VideoEncoding) -> Self {
         Self {
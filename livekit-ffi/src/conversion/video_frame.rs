@@ -74,6 +74,9 @@ impl proto::VideoFrameInfo {
         Self {
             timestamp: frame.timestamp,
             rotation: proto::VideoRotation::from(frame.rotation).into(),
+            rtp_timestamp: frame.rtp_timestamp,
+            ntp_time_ms: frame.ntp_time_ms,
+            receive_time_us: frame.receive_time_us,
         }
     }
 }
@@ -280,6 +283,7 @@ impl From<proto::VideoCodec> for VideoCodec {
             proto::VideoCodec::Vp8 => Self::VP8,
             proto::VideoCodec::H264 => Self::H264,
             proto::VideoCodec::Av1 => Self::AV1,
+            proto::VideoCodec::Vp9 => Self::VP9,
         }
     }
 }
@@ -2,7 +2,8 @@ use crate::{proto, server, FfiError, FfiHandleId, FfiResult};
 use futures_util::StreamExt;
 use livekit::prelude::*;
 use livekit::webrtc::audio_frame::AudioFrame;
-use livekit::webrtc::audio_source::native::NativeAudioSource;
+use livekit::webrtc::audio_source::native::{NativeAudioSource, DEFAULT_QUEUE_SIZE_MS};
+use livekit::webrtc::audio_source::AudioSourceOptions;
 use livekit::webrtc::audio_stream::native::NativeAudioStream;
 use livekit::webrtc::prelude::*;
 use log::warn;
@@ -47,10 +48,9 @@ impl FfiAudioSream {
             &track_sid,
             &new_stream.participant_sid.into(),
             room_handle,
-        )?
-        .rtc_track();
+        )?;
 
-        let MediaStreamTrack::Audio(track) = track else {
+        let RemoteTrack::Audio(track) = track else {
             return Err(FfiError::InvalidRequest("not an audio track"));
         };
 
@@ -65,7 +65,7 @@ impl FfiAudioSream {
                 server.async_runtime.spawn(Self::native_audio_stream_task(
                     server,
                     audio_stream.handle_id,
-                    NativeAudioStream::new(track),
+                    NativeAudioStream::with_receiver(track.rtc_track(), track.receiver()),
                     close_rx,
                 ));
                 Ok::<FfiAudioSream, FfiError>(audio_stream)
@@ -155,7 +155,16 @@ impl FfiAudioSource {
         let source_type = proto::AudioSourceType::from_i32(new_source.r#type).unwrap();
         let source_inner = match source_type {
             proto::AudioSourceType::AudioSourceNative => {
-                let audio_source = NativeAudioSource::default();
+                let sample_rate = new_source.sample_rate.unwrap_or(48000);
+                let num_channels = new_source.num_channels.unwrap_or(2);
+                let audio_source = NativeAudioSource::with_runtime(
+                    AudioSourceOptions::default(),
+                    sample_rate,
+                    num_channels,
+                    DEFAULT_QUEUE_SIZE_MS,
+                    server.async_runtime.handle().clone(),
+                )
+                .map_err(|_| FfiError::InvalidRequest("invalid audio format"))?;
                 Ok::<AudioSource, FfiError>(AudioSource::Native(audio_source))
             } //_ => return Err(FfiError::InvalidRequest("unsupported audio source type")),
         }?;
@@ -193,9 +202,13 @@ impl FfiAudioSource {
 
                 let frame = frame
                     .downcast_ref::<AudioFrame>()
-                    .ok_or(FfiError::InvalidRequest("handle is not an audio frame"))?;
+                    .ok_or(FfiError::InvalidRequest("handle is not an audio frame"))?
+                    .clone();
 
-                source.capture_frame(frame);
+                server
+                    .async_runtime
+                    .block_on(source.capture_frame(&frame))
+                    .map_err(|_| FfiError::InvalidRequest("invalid audio frame"))?;
             }
         }
 
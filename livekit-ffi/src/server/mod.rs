@@ -6,6 +6,7 @@ use livekit::prelude::*;
 use livekit::webrtc::native::{audio_resampler, yuv_helper};
 use livekit::webrtc::prelude::*;
 use livekit::webrtc::video_frame::{native::I420BufferExt, BoxVideoFrameBuffer, I420Buffer};
+use livekit::LkRuntime;
 use parking_lot::Mutex;
 use prost::Message;
 use std::collections::HashMap;
@@ -290,11 +291,13 @@ impl FfiServer {
             .ok_or(FfiError::InvalidRequest("handle is not a video source"))?;
 
         let source = source.inner_source().clone();
+        let runtime = self.track_runtime(create.room_handle.as_ref())?;
         let video_track = match source {
             video_frame::VideoSource::Native(native_source) => LocalVideoTrack::create_video_track(
                 &create.name,
                 create.options.unwrap_or_default().into(),
                 native_source,
+                &runtime,
             ),
         };
 
@@ -309,6 +312,24 @@ impl FfiServer {
         })
     }
 
+    /// Runtime of the room the track will be published to, the global one if there is no room
+    fn track_runtime(&self, room_handle: Option<&proto::FfiHandleId>) -> FfiResult<Arc<LkRuntime>> {
+        let Some(room_handle) = room_handle else {
+            return Ok(LkRuntime::instance());
+        };
+
+        let ffi_room = self
+            .ffi_handles
+            .get(&(room_handle.id as FfiHandleId))
+            .ok_or(FfiError::InvalidRequest("room not found"))?;
+
+        let ffi_room = ffi_room
+            .downcast_ref::<room::FfiRoom>()
+            .ok_or(FfiError::InvalidRequest("room is not a FfiRoom"))?;
+
+        Ok(ffi_room.room().runtime())
+    }
+
     fn on_create_audio_track(
         &'static self,
         create: proto::CreateAudioTrackRequest,
@@ -329,11 +350,13 @@ impl FfiServer {
             .ok_or(FfiError::InvalidRequest("handle is not an audio source"))?;
 
         let source = source.inner_source().clone();
+        let runtime = self.track_runtime(create.room_handle.as_ref())?;
         let audio_track = match source {
             audio_frame::AudioSource::Native(native_source) => LocalAudioTrack::create_audio_track(
                 &create.name,
                 create.options.unwrap_or_default().into(),
                 native_source,
+                &runtime,
             ),
         };
 
@@ -646,7 +669,7 @@ impl FfiServer {
             .ok_or(FfiError::InvalidRequest("handle is empty"))?
             .id as FfiHandleId;
 
-        let data = {
+        let (data, rtp_timestamp, ntp_time_ms, receive_time_us) = {
             let buffer = self
                 .ffi_handles
                 .get(&buffer_id)
@@ -657,7 +680,7 @@ impl FfiServer {
                 .ok_or(FfiError::InvalidRequest("handle is not a buffer"))?;
 
             let mut resampler = resampler.lock();
-            resampler
+            let data = resampler
                 .remix_and_resample(
                     &buffer.data,
                     buffer.samples_per_channel,
@@ -666,7 +689,15 @@ impl FfiServer {
                     remix.num_channels,
                     remix.sample_rate,
                 )
-                .to_owned()
+                .map_err(|_| FfiError::InvalidRequest("invalid remix request"))?
+                .to_owned();
+
+            (
+                data,
+                buffer.rtp_timestamp,
+                buffer.ntp_time_ms,
+                buffer.receive_time_us,
+            )
         };
 
         let samples_per_channel = data.len() / remix.num_channels as usize;
@@ -675,6 +706,9 @@ impl FfiServer {
             num_channels: remix.num_channels,
             samples_per_channel: samples_per_channel as u32,
             sample_rate: remix.sample_rate,
+            rtp_timestamp,
+            ntp_time_ms,
+            receive_time_us,
         };
 
         let handle_id = self.next_id() as FfiHandleId;
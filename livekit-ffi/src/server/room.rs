@@ -16,7 +16,12 @@ impl FfiRoom {
         server: &'static FfiServer,
         connect: proto::ConnectRequest,
     ) -> FfiResult<proto::RoomInfo> {
-        let (room, events) = Room::connect(&connect.url, &connect.token).await?;
+        let options = connect
+            .options
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default();
+        let (room, events) = Room::connect(&connect.url, &connect.token, options).await?;
         let room = Arc::new(room);
         let (close_tx, close_rx) = oneshot::channel();
         let next_id = server.next_id() as FfiHandleId;
@@ -263,7 +263,7 @@ fn publish_video_track() {
 
             let publish_options = proto::TrackPublishOptions {
                 name: "video_test".to_string(),
-                video_codec: proto::VideoCodec::H264 as i32,
+                video_codec: proto::VideoCodec::Vp8 as i32,
                 source: proto::TrackSource::SourceCamera as i32,
                 ..Default::default()
             };
@@ -322,6 +322,7 @@ fn publish_video_track() {
                             frame: Some(proto::VideoFrameInfo {
                                 timestamp: 0, // TODO
                                 rotation: proto::VideoRotation::VideoRotation0 as i32,
+                                ..Default::default()
                             }),
                         },
                     )),
@@ -331,3 +332,19 @@ fn publish_video_track() {
             }
         })
 }
+
+#[test]
+fn rtc_config_port_range() {
+    use livekit::webrtc::prelude::RtcConfiguration;
+
+    let config = |min_port, max_port| proto::RtcConfig {
+        min_port: Some(min_port),
+        max_port: Some(max_port),
+        ..Default::default()
+    };
+
+    let rtc_config = RtcConfiguration::try_from(config(50000, 60000)).unwrap();
+    assert_eq!(rtc_config.port_range, Some(50000..=60000));
+    assert!(RtcConfiguration::try_from(config(50000, 70000)).is_err());
+    assert!(RtcConfiguration::try_from(config(60000, 50000)).is_err());
+}
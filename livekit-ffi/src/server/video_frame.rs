@@ -207,6 +207,9 @@ impl FfiVideoSource {
                 let frame = VideoFrame {
                     rotation: rotation.into(),
                     timestamp: frame_info.timestamp,
+                    rtp_timestamp: frame_info.rtp_timestamp,
+                    ntp_time_ms: frame_info.ntp_time_ms,
+                    receive_time_us: frame_info.receive_time_us,
                     buffer,
                 };
 
@@ -1,18 +1,437 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AudioFrameError {
+    #[error("frames have different formats: {0}")]
+    FormatMismatch(String),
+    #[error("invalid number of channels: {0}")]
+    InvalidChannels(u32),
+}
+
+/// Sample type of an AudioFrame.
+/// i16 samples use the full range, f32 samples are normalized to [-1.0, 1.0]
+pub trait Sample: Copy + Default + Debug + PartialEq + Send + Sync + 'static {
+    fn to_f32(self) -> f32;
+    fn from_f32(value: f32) -> Self;
+
+    fn to_i16(self) -> i16 {
+        i16::from_f32(self.to_f32())
+    }
+
+    /// libwebrtc only works with i16 samples, avoid the copy when possible
+    fn to_i16_slice(data: &[Self]) -> Cow<[i16]> {
+        Cow::Owned(data.iter().map(|s| s.to_i16()).collect())
+    }
+}
+
+impl Sample for i16 {
+    fn to_f32(self) -> f32 {
+        self as f32 / 32768.0
+    }
+
+    fn from_f32(value: f32) -> Self {
+        (value * 32768.0).clamp(i16::MIN as f32, i16::MAX as f32) as i16
+    }
+
+    fn to_i16(self) -> i16 {
+        self
+    }
+
+    fn to_i16_slice(data: &[Self]) -> Cow<[i16]> {
+        Cow::Borrowed(data)
+    }
+}
+
+impl Sample for f32 {
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+}
+
+/// Interleaved audio frame
 #[derive(Debug, Clone)]
-pub struct AudioFrame {
-    pub data: Vec<i16>,
+pub struct AudioFrame<S = i16> {
+    pub data: Vec<S>,
     pub sample_rate: u32,
     pub num_channels: u32,
     pub samples_per_channel: u32,
+    /// RTP timestamp of the first sample. Only set on frames received from a
+    /// `NativeAudioStream::with_receiver`, where it is the one of the last played packet
+    pub rtp_timestamp: Option<u32>,
+    /// Capture time in the sender's NTP clock, in milliseconds. Only available when the
+    /// sender uses the absolute capture time RTP header extension
+    pub ntp_time_ms: Option<i64>,
+    /// When the last played packet was received, in microseconds. Set like `rtp_timestamp`
+    pub receive_time_us: Option<i64>,
+}
+
+/// Audio frame with one buffer per channel
+#[derive(Debug, Clone)]
+pub struct PlanarAudioFrame<S = f32> {
+    pub planes: Vec<Vec<S>>,
+    pub sample_rate: u32,
+    pub samples_per_channel: u32,
+    pub rtp_timestamp: Option<u32>,
+    pub ntp_time_ms: Option<i64>,
+    pub receive_time_us: Option<i64>,
 }
 
-impl AudioFrame {
+impl<S: Sample> AudioFrame<S> {
     pub fn new(sample_rate: u32, num_channels: u32, samples_per_channel: u32) -> Self {
         Self {
-            data: vec![0; (num_channels * samples_per_channel) as usize],
+            data: vec![S::default(); (num_channels * samples_per_channel) as usize],
             sample_rate,
             num_channels,
             samples_per_channel,
+            rtp_timestamp: None,
+            ntp_time_ms: None,
+            receive_time_us: None,
         }
     }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.samples_per_channel as f64 / self.sample_rate as f64)
+    }
+
+    /// Convert the samples to another sample type (e.g i16 to f32)
+    pub fn convert<T: Sample>(&self) -> AudioFrame<T> {
+        AudioFrame {
+            data: self.data.iter().map(|s| T::from_f32(s.to_f32())).collect(),
+            sample_rate: self.sample_rate,
+            num_channels: self.num_channels,
+            samples_per_channel: self.samples_per_channel,
+            rtp_timestamp: self.rtp_timestamp,
+            ntp_time_ms: self.ntp_time_ms,
+            receive_time_us: self.receive_time_us,
+        }
+    }
+
+    pub fn to_planar(&self) -> PlanarAudioFrame<S> {
+        let num_channels = self.num_channels as usize;
+        let planes = (0..num_channels)
+            .map(|c| {
+                self.data
+                    .iter()
+                    .skip(c)
+                    .step_by(num_channels)
+                    .copied()
+                    .collect()
+            })
+            .collect();
+
+        PlanarAudioFrame {
+            planes,
+            sample_rate: self.sample_rate,
+            samples_per_channel: self.samples_per_channel,
+            rtp_timestamp: self.rtp_timestamp,
+            ntp_time_ms: self.ntp_time_ms,
+            receive_time_us: self.receive_time_us,
+        }
+    }
+
+    /// Up-mix or down-mix the channels (e.g stereo to mono).
+    /// Mono, stereo, quad and 5.1 are remixed using their speaker layouts (same channel
+    /// order and coefficients as WebAudio), other layouts are mapped channel by channel
+    pub fn remix(&self, num_channels: u32) -> Result<AudioFrame<S>, AudioFrameError> {
+        if self.num_channels == 0 {
+            return Err(AudioFrameError::InvalidChannels(self.num_channels));
+        }
+        if num_channels == 0 {
+            return Err(AudioFrameError::InvalidChannels(num_channels));
+        }
+
+        Ok(AudioFrame {
+            data: remix_interleaved(
+                &self.data,
+                self.num_channels as usize,
+                num_channels as usize,
+            ),
+            sample_rate: self.sample_rate,
+            num_channels,
+            samples_per_channel: self.samples_per_channel,
+            rtp_timestamp: self.rtp_timestamp,
+            ntp_time_ms: self.ntp_time_ms,
+            receive_time_us: self.receive_time_us,
+        })
+    }
+
+    /// Multiply the samples by the gain, i16 samples are clipped
+    pub fn apply_gain(&mut self, gain: f32) {
+        for sample in &mut self.data {
+            *sample = S::from_f32(sample.to_f32() * gain);
+        }
+    }
+
+    /// Add the samples of the other frame to this frame, i16 samples are clipped
+    pub fn mix(&mut self, other: &AudioFrame<S>) -> Result<(), AudioFrameError> {
+        if self.sample_rate != other.sample_rate
+            || self.num_channels != other.num_channels
+            || self.samples_per_channel != other.samples_per_channel
+        {
+            return Err(AudioFrameError::FormatMismatch(format!(
+                "{}Hz {}ch {} samples != {}Hz {}ch {} samples",
+                self.sample_rate,
+                self.num_channels,
+                self.samples_per_channel,
+                other.sample_rate,
+                other.num_channels,
+                other.samples_per_channel
+            )));
+        }
+
+        for (sample, other) in self.data.iter_mut().zip(&other.data) {
+            *sample = S::from_f32(sample.to_f32() + other.to_f32());
+        }
+
+        Ok(())
+    }
+
+    /// Split the frame into 10ms frames, the last one may be shorter.
+    /// A frame without channels has no chunks
+    pub fn chunks_10ms(&self) -> impl Iterator<Item = AudioFrame<S>> + '_ {
+        let samples_10ms = (self.sample_rate / 100).max(1) as usize;
+        let data = if self.num_channels == 0 {
+            &[]
+        } else {
+            self.data.as_slice()
+        };
+
+        data.chunks(samples_10ms * self.num_channels.max(1) as usize)
+            .enumerate()
+            .map(move |(i, data)| AudioFrame {
+                data: data.to_vec(),
+                sample_rate: self.sample_rate,
+                num_channels: self.num_channels,
+                samples_per_channel: (data.len() / self.num_channels as usize) as u32,
+                // Assumes the RTP clock rate is the sample rate (e.g Opus at 48kHz)
+                rtp_timestamp: self
+                    .rtp_timestamp
+                    .map(|t| t.wrapping_add((i * samples_10ms) as u32)),
+                ntp_time_ms: self.ntp_time_ms.map(|t| t + i as i64 * 10),
+                receive_time_us: self.receive_time_us.map(|t| t + i as i64 * 10_000),
+            })
+    }
+}
+
+impl<S: Sample> PlanarAudioFrame<S> {
+    pub fn new(sample_rate: u32, num_channels: u32, samples_per_channel: u32) -> Self {
+        Self {
+            planes: vec![vec![S::default(); samples_per_channel as usize]; num_channels as usize],
+            sample_rate,
+            samples_per_channel,
+            rtp_timestamp: None,
+            ntp_time_ms: None,
+            receive_time_us: None,
+        }
+    }
+
+    pub fn num_channels(&self) -> u32 {
+        self.planes.len() as u32
+    }
+
+    pub fn convert<T: Sample>(&self) -> PlanarAudioFrame<T> {
+        PlanarAudioFrame {
+            planes: self
+                .planes
+                .iter()
+                .map(|plane| plane.iter().map(|s| T::from_f32(s.to_f32())).collect())
+                .collect(),
+            sample_rate: self.sample_rate,
+            samples_per_channel: self.samples_per_channel,
+            rtp_timestamp: self.rtp_timestamp,
+            ntp_time_ms: self.ntp_time_ms,
+            receive_time_us: self.receive_time_us,
+        }
+    }
+
+    pub fn to_interleaved(&self) -> AudioFrame<S> {
+        let mut data = Vec::with_capacity(self.planes.len() * self.samples_per_channel as usize);
+        for i in 0..self.samples_per_channel as usize {
+            data.extend(self.planes.iter().map(|plane| plane[i]));
+        }
+
+        AudioFrame {
+            data,
+            sample_rate: self.sample_rate,
+            num_channels: self.num_channels(),
+            samples_per_channel: self.samples_per_channel,
+            rtp_timestamp: self.rtp_timestamp,
+            ntp_time_ms: self.ntp_time_ms,
+            receive_time_us: self.receive_time_us,
+        }
+    }
+}
+
+/// Remix interleaved samples, see AudioFrame::remix. The channel counts must be > 0
+pub(crate) fn remix_interleaved<S: Sample>(data: &[S], src: usize, dst: usize) -> Vec<S> {
+    let matrix = remix_matrix(src, dst);
+    let mut remixed = vec![S::default(); data.len() / src * dst];
+    for (src_frame, dst_frame) in data.chunks_exact(src).zip(remixed.chunks_exact_mut(dst)) {
+        for (sample, gains) in dst_frame.iter_mut().zip(&matrix) {
+            let mixed = src_frame
+                .iter()
+                .zip(gains)
+                .map(|(s, gain)| s.to_f32() * gain)
+                .sum::<f32>();
+            *sample = S::from_f32(mixed);
+        }
+    }
+    remixed
+}
+
+/// Gain of each source channel, for each destination channel
+fn remix_matrix(src: usize, dst: usize) -> Vec<Vec<f32>> {
+    const SQRT_HALF: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+    // Speaker layouts: mono (M), stereo (L, R), quad (L, R, SL, SR)
+    // and 5.1 (L, R, C, LFE, SL, SR)
+    let layout: &[&[f32]] = match (src, dst) {
+        (1, 2) => &[&[1.0], &[1.0]],
+        (1, 4) => &[&[1.0], &[1.0], &[0.0], &[0.0]],
+        (1, 6) => &[&[0.0], &[0.0], &[1.0], &[0.0], &[0.0], &[0.0]],
+        (2, 1) => &[&[0.5, 0.5]],
+        (4, 1) => &[&[0.25, 0.25, 0.25, 0.25]],
+        (4, 2) => &[&[0.5, 0.0, 0.5, 0.0], &[0.0, 0.5, 0.0, 0.5]],
+        (6, 1) => &[&[SQRT_HALF, SQRT_HALF, 1.0, 0.0, 0.5, 0.5]],
+        (6, 2) => &[
+            &[1.0, 0.0, SQRT_HALF, 0.0, SQRT_HALF, 0.0],
+            &[0.0, 1.0, SQRT_HALF, 0.0, 0.0, SQRT_HALF],
+        ],
+        (6, 4) => &[
+            &[1.0, 0.0, SQRT_HALF, 0.0, 0.0, 0.0],
+            &[0.0, 1.0, SQRT_HALF, 0.0, 0.0, 0.0],
+            &[0.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            &[0.0, 0.0, 0.0, 0.0, 0.0, 1.0],
+        ],
+        (4, 6) => &[
+            &[1.0, 0.0, 0.0, 0.0],
+            &[0.0, 1.0, 0.0, 0.0],
+            &[0.0, 0.0, 0.0, 0.0],
+            &[0.0, 0.0, 0.0, 0.0],
+            &[0.0, 0.0, 1.0, 0.0],
+            &[0.0, 0.0, 0.0, 1.0],
+        ],
+        _ => &[],
+    };
+
+    if !layout.is_empty() {
+        return layout.iter().map(|gains| gains.to_vec()).collect();
+    }
+
+    (0..dst)
+        .map(|d| {
+            (0..src)
+                .map(|s| {
+                    if dst == 1 {
+                        1.0 / src as f32 // Average everything into mono
+                    } else if src == 1 || s == d {
+                        1.0
+                    } else {
+                        0.0 // The extra channels are dropped or silent
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl<S: Sample> From<PlanarAudioFrame<S>> for AudioFrame<S> {
+    fn from(frame: PlanarAudioFrame<S>) -> Self {
+        frame.to_interleaved()
+    }
+}
+
+impl<S: Sample> From<AudioFrame<S>> for PlanarAudioFrame<S> {
+    fn from(frame: AudioFrame<S>) -> Self {
+        frame.to_planar()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stereo_frame() -> AudioFrame {
+        AudioFrame {
+            data: vec![1000, -1000, 2000, -2000, 3000, -3000, 4000, -4000],
+            sample_rate: 100, // 1 sample per 10ms
+            num_channels: 2,
+            samples_per_channel: 4,
+            rtp_timestamp: None,
+            ntp_time_ms: Some(100),
+            receive_time_us: None,
+        }
+    }
+
+    #[test]
+    fn planar_roundtrip() {
+        let frame = stereo_frame();
+        let planar = frame.to_planar();
+        assert_eq!(planar.planes[0], vec![1000, 2000, 3000, 4000]);
+        assert_eq!(planar.planes[1], vec![-1000, -2000, -3000, -4000]);
+        assert_eq!(planar.to_interleaved().data, frame.data);
+    }
+
+    #[test]
+    fn sample_conversion() {
+        let frame = stereo_frame();
+        let f32_frame = frame.convert::<f32>();
+        assert_eq!(f32_frame.data[0], 1000.0 / 32768.0);
+        assert_eq!(f32_frame.convert::<i16>().data, frame.data);
+        assert_eq!(i16::from_f32(2.0), i16::MAX);
+        assert_eq!(i16::from_f32(-2.0), i16::MIN);
+    }
+
+    #[test]
+    fn remix_channels() {
+        let mono = stereo_frame().remix(1).unwrap();
+        assert_eq!(mono.data, vec![0, 0, 0, 0]);
+
+        let stereo = mono.remix(2).unwrap();
+        assert_eq!(stereo.num_channels, 2);
+        assert_eq!(stereo.data.len(), 8);
+
+        // Quad (L, R, SL, SR) to stereo keeps the sides
+        let mut quad = AudioFrame::<i16>::new(48000, 4, 1);
+        quad.data = vec![1000, 2000, 3000, 4000];
+        assert_eq!(quad.remix(2).unwrap().data, vec![2000, 3000]);
+
+        // Stereo to 5.1 doesn't copy the channels to the center and LFE
+        let surround = stereo_frame().remix(6).unwrap();
+        assert_eq!(&surround.data[..6], &[1000, -1000, 0, 0, 0, 0]);
+
+        assert!(stereo_frame().remix(0).is_err());
+        assert!(AudioFrame::<i16>::new(48000, 0, 0).remix(2).is_err());
+    }
+
+    #[test]
+    fn mix_and_gain() {
+        let mut frame = stereo_frame();
+        frame.mix(&stereo_frame()).unwrap();
+        assert_eq!(frame.data[0], 2000);
+
+        frame.apply_gain(0.5);
+        assert_eq!(frame.data, stereo_frame().data);
+
+        assert!(frame.mix(&stereo_frame().remix(1).unwrap()).is_err());
+    }
+
+    #[test]
+    fn split_10ms() {
+        let chunks: Vec<_> = stereo_frame().chunks_10ms().collect();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[1].data, vec![2000, -2000]);
+        assert_eq!(chunks[1].ntp_time_ms, Some(110));
+
+        let mut empty = AudioFrame::<i16>::new(48000, 0, 0);
+        empty.data = vec![0; 480];
+        assert_eq!(empty.chunks_10ms().count(), 0);
+    }
 }
@@ -1,11 +1,37 @@
 use crate::imp::audio_source as imp_as;
 
+/// Processing applied by WebRTC's AudioProcessing module to the captured audio
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioSourceOptions {
+    pub echo_cancellation: bool,
+    pub noise_suppression: bool,
+    pub auto_gain_control: bool,
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
-    use super::imp_as;
-    use crate::audio_frame::AudioFrame;
+    use super::{imp_as, AudioSourceOptions};
+    use crate::audio_frame::{AudioFrame, Sample};
     use std::fmt::{Debug, Formatter};
+    use std::time::Duration;
+    use thiserror::Error;
 
+    /// Default size of the queue of NativeAudioSource
+    pub const DEFAULT_QUEUE_SIZE_MS: u32 = 200;
+
+    #[derive(Debug, Error)]
+    pub enum AudioSourceError {
+        #[error("invalid sample rate {0}Hz, it must be a multiple of 100Hz")]
+        InvalidSampleRate(u32),
+        #[error("invalid number of channels: {0}")]
+        InvalidChannels(u32),
+        #[error("no tokio runtime to send the audio")]
+        NoRuntime,
+    }
+
+    /// Frames can be of any size, sample rate or number of channels: they are buffered and
+    /// resampled to the format of the source, then sent in real time.
+    /// Defaults to 48kHz stereo.
     #[derive(Default, Clone)]
     pub struct NativeAudioSource {
         pub(crate) handle: imp_as::NativeAudioSource,
@@ -13,13 +39,164 @@ pub mod native {
 
     impl Debug for NativeAudioSource {
         fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-            f.debug_struct("NativeAudioSource").finish()
+            f.debug_struct("NativeAudioSource")
+                .field("sample_rate", &self.sample_rate())
+                .field("num_channels", &self.num_channels())
+                .finish()
         }
     }
 
     impl NativeAudioSource {
-        pub fn capture_frame(&self, frame: &AudioFrame) {
-            self.handle.capture_frame(frame)
+        /// queue_size_ms is the amount of audio that can be written ahead of real time
+        /// before capture_frame waits.
+        /// The audio is sent by a task spawned on the runtime calling capture_frame.
+        /// Fails if sample_rate isn't a multiple of 100Hz or if num_channels is 0
+        pub fn new(
+            options: AudioSourceOptions,
+            sample_rate: u32,
+            num_channels: u32,
+            queue_size_ms: u32,
+        ) -> Result<Self, AudioSourceError> {
+            Ok(Self {
+                handle: imp_as::NativeAudioSource::new(
+                    options,
+                    sample_rate,
+                    num_channels,
+                    queue_size_ms,
+                    None,
+                )?,
+            })
+        }
+
+        /// Same as new, but the audio is sent by a task spawned on the given runtime, so
+        /// capture_frame can be polled outside of it
+        pub fn with_runtime(
+            options: AudioSourceOptions,
+            sample_rate: u32,
+            num_channels: u32,
+            queue_size_ms: u32,
+            runtime: tokio::runtime::Handle,
+        ) -> Result<Self, AudioSourceError> {
+            Ok(Self {
+                handle: imp_as::NativeAudioSource::new(
+                    options,
+                    sample_rate,
+                    num_channels,
+                    queue_size_ms,
+                    Some(runtime),
+                )?,
+            })
+        }
+
+        pub fn audio_options(&self) -> AudioSourceOptions {
+            self.handle.audio_options()
+        }
+
+        pub fn set_audio_options(&self, options: AudioSourceOptions) {
+            self.handle.set_audio_options(options)
+        }
+
+        pub fn sample_rate(&self) -> u32 {
+            self.handle.sample_rate()
+        }
+
+        pub fn num_channels(&self) -> u32 {
+            self.handle.num_channels()
+        }
+
+        /// Number of samples (per channel) waiting to be sent
+        pub fn queue_depth(&self) -> u32 {
+            self.handle.queue_depth()
+        }
+
+        /// Time before a newly captured frame is sent
+        pub fn latency(&self) -> Duration {
+            self.handle.latency()
+        }
+
+        /// The frame is processed using the enabled AudioSourceOptions before being sent.
+        /// f32 samples are converted to i16, planar frames must be interleaved first.
+        /// Waits when the queue is full, so producers can't go faster than real time.
+        /// The sample rate of the frame must be a multiple of 100Hz.
+        pub async fn capture_frame<S: Sample>(
+            &self,
+            frame: &AudioFrame<S>,
+        ) -> Result<(), AudioSourceError> {
+            self.handle.capture_frame(frame).await
+        }
+
+        /// Push the audio played out locally (e.g the remote participants), it is used as the
+        /// reference signal of the echo canceller. Ignored when echo_cancellation is disabled.
+        pub fn capture_reverse_frame<S: Sample>(
+            &self,
+            frame: &AudioFrame<S>,
+        ) -> Result<(), AudioSourceError> {
+            self.handle.capture_reverse_frame(frame)
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct EncodedAudioFrameInfo {
+        pub sample_rate: u32,
+        pub samples_per_channel: u32, // Duration of the packet, at least 10ms
+    }
+
+    /// Publish already encoded Opus packets, they are sent without being re-encoded.
+    /// Packets must be captured in real time, at the pace they should be sent.
+    #[derive(Default, Clone)]
+    pub struct EncodedAudioSource {
+        pub(crate) handle: imp_as::EncodedAudioSource,
+    }
+
+    impl Debug for EncodedAudioSource {
+        fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+            f.debug_struct("EncodedAudioSource").finish()
+        }
+    }
+
+    impl EncodedAudioSource {
+        /// Returns false if the packet was rejected (e.g shorter than 10ms)
+        pub fn capture_frame(&self, data: &[u8], info: &EncodedAudioFrameInfo) -> bool {
+            self.handle.capture_frame(data, info)
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub mod web {
+    use super::{imp_as, AudioSourceOptions};
+    use std::fmt::{Debug, Formatter};
+
+    /// Audio track captured by the browser (e.g getUserMedia), the AudioSourceOptions are
+    /// applied as constraints of the track
+    #[derive(Clone)]
+    pub struct WebAudioSource {
+        pub(crate) handle: imp_as::WebAudioSource,
+    }
+
+    impl Debug for WebAudioSource {
+        fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+            f.debug_struct("WebAudioSource").finish()
+        }
+    }
+
+    impl WebAudioSource {
+        pub fn new(track: web_sys::MediaStreamTrack) -> Self {
+            Self {
+                handle: imp_as::WebAudioSource::new(track),
+            }
+        }
+
+        pub fn track(&self) -> web_sys::MediaStreamTrack {
+            self.handle.sys_handle()
+        }
+
+        pub fn audio_options(&self) -> AudioSourceOptions {
+            self.handle.audio_options()
+        }
+
+        pub fn set_audio_options(&self, options: AudioSourceOptions) {
+            self.handle.set_audio_options(options)
         }
     }
 }
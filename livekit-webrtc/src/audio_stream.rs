@@ -1,11 +1,15 @@
-use crate::imp::audio_stream as stream_imp;
+// Browsers don't expose the decoded audio of a track, there is no web stream (use an
+// AudioContext on the MediaStreamTrack instead)
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
-    use super::stream_imp;
-    use crate::audio_frame::AudioFrame;
+    use crate::audio_frame::{AudioFrame, Sample};
     use crate::audio_track::RtcAudioTrack;
-    use futures::stream::Stream;
+    use crate::imp::audio_resampler::{ResampleError, StreamingResampler};
+    use crate::imp::audio_stream as stream_imp;
+    use crate::rtp_receiver::RtpReceiver;
+    use futures::future;
+    use futures::stream::{Stream, StreamExt};
     use std::fmt::{Debug, Formatter};
     use std::pin::Pin;
     use std::task::{Context, Poll};
@@ -25,7 +29,15 @@ pub mod native {
     impl NativeAudioStream {
         pub fn new(audio_track: RtcAudioTrack) -> Self {
             Self {
-                handle: stream_imp::NativeAudioStream::new(audio_track),
+                handle: stream_imp::NativeAudioStream::new(audio_track, None),
+            }
+        }
+
+        /// Like `new`, but the frames also carry the RTP timestamp and receive time of the
+        /// last packet played out by the receiver of the track
+        pub fn with_receiver(audio_track: RtcAudioTrack, receiver: RtpReceiver) -> Self {
+            Self {
+                handle: stream_imp::NativeAudioStream::new(audio_track, Some(receiver)),
             }
         }
 
@@ -36,6 +48,27 @@ pub mod native {
         pub fn close(&mut self) {
             self.handle.close()
         }
+
+        /// Receive the frames with another sample type (e.g f32)
+        pub fn convert<S: Sample>(self) -> impl Stream<Item = AudioFrame<S>> {
+            self.map(|frame| frame.convert())
+        }
+
+        /// Receive the frames resampled to the given sample rate and number of channels.
+        /// Fails if the format is not supported by the resampler
+        pub fn resample(
+            self,
+            sample_rate: u32,
+            num_channels: u32,
+        ) -> Result<impl Stream<Item = AudioFrame>, ResampleError> {
+            let mut resampler = StreamingResampler::new(sample_rate, num_channels)?;
+            Ok(self.filter_map(move |frame| {
+                future::ready(resampler.push(&frame).unwrap_or_else(|err| {
+                    log::warn!("failed to resample the received audio: {}", err);
+                    None
+                }))
+            }))
+        }
     }
 
     impl Stream for NativeAudioStream {
@@ -45,4 +78,50 @@ pub mod native {
             Pin::new(&mut self.get_mut().handle).poll_next(cx)
         }
     }
+
+    #[derive(Debug, Clone)]
+    pub struct EncodedAudioFrame {
+        pub data: Vec<u8>,
+        pub mime_type: String, // e.g "audio/opus"
+        pub rtp_timestamp: u32,
+        pub ssrc: u32,
+    }
+
+    /// Yield a copy of the depacketized frames of a receiver. The frames are still
+    /// decoded and played out.
+    pub struct NativeEncodedAudioStream {
+        pub(crate) handle: stream_imp::NativeEncodedAudioStream,
+    }
+
+    impl Debug for NativeEncodedAudioStream {
+        fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+            f.debug_struct("NativeEncodedAudioStream")
+                .field("receiver", &self.receiver())
+                .finish()
+        }
+    }
+
+    impl NativeEncodedAudioStream {
+        pub fn new(receiver: RtpReceiver) -> Self {
+            Self {
+                handle: stream_imp::NativeEncodedAudioStream::new(receiver),
+            }
+        }
+
+        pub fn receiver(&self) -> RtpReceiver {
+            self.handle.receiver()
+        }
+
+        pub fn close(&mut self) {
+            self.handle.close()
+        }
+    }
+
+    impl Stream for NativeEncodedAudioStream {
+        type Item = EncodedAudioFrame;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.get_mut().handle).poll_next(cx)
+        }
+    }
 }
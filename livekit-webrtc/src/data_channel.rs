@@ -33,6 +33,8 @@ pub enum DataChannelError {
     Send,
     #[error("only utf8 strings can be sent")]
     Utf8(#[from] Utf8Error),
+    #[error("data channel is closed")]
+    Closed,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -49,6 +51,22 @@ pub struct DataBuffer<'a> {
     pub binary: bool,
 }
 
+/// Owned copy of a received DataBuffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataMessage {
+    pub data: Vec<u8>,
+    pub binary: bool,
+}
+
+impl From<DataBuffer<'_>> for DataMessage {
+    fn from(buffer: DataBuffer<'_>) -> Self {
+        Self {
+            data: buffer.data.to_vec(),
+            binary: buffer.binary,
+        }
+    }
+}
+
 pub type OnStateChange = Box<dyn FnMut(DataState) + Send + Sync>;
 pub type OnMessage = Box<dyn FnMut(DataBuffer) + Send + Sync>;
 pub type OnBufferedAmountChange = Box<dyn FnMut(u64) + Send + Sync>;
@@ -71,6 +89,11 @@ impl DataChannel {
         self.handle.state()
     }
 
+    /// Number of bytes queued to be sent
+    pub fn buffered_amount(&self) -> u64 {
+        self.handle.buffered_amount()
+    }
+
     pub fn close(&self) {
         self.handle.close()
     }
@@ -96,3 +119,310 @@ impl Debug for DataChannel {
             .finish()
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod native {
+    use super::{DataChannel, DataChannelError, DataMessage, DataState};
+    use futures::sink::{self, Sink};
+    use futures::stream::Stream;
+    use parking_lot::Mutex;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use tokio::sync::{mpsc, Notify};
+
+    /// Above this amount of buffered data, sending waits for the buffer to drain
+    pub const DEFAULT_MAX_BUFFERED_AMOUNT: u64 = 1024 * 1024;
+
+    /// Async interface of a DataChannel, received messages are read with the Stream impl.
+    /// It replaces the callbacks of the DataChannel, so only one should exist per channel.
+    /// The stream ends once the DataChannel is closed
+    pub struct AsyncDataChannel {
+        sender: DataChannelSender,
+        message_rx: mpsc::UnboundedReceiver<DataMessage>,
+    }
+
+    /// Sending half of an AsyncDataChannel
+    #[derive(Clone)]
+    pub struct DataChannelSender {
+        data_channel: DataChannel,
+        changed: Arc<Notify>,
+        max_buffered_amount: u64,
+    }
+
+    impl AsyncDataChannel {
+        pub fn new(data_channel: DataChannel, max_buffered_amount: u64) -> Self {
+            let changed = Arc::new(Notify::new());
+            let (message_tx, message_rx) = mpsc::unbounded_channel();
+            let message_tx = Arc::new(Mutex::new(Some(message_tx)));
+
+            data_channel.on_message(Some(Box::new({
+                let message_tx = message_tx.clone();
+                move |buffer| {
+                    if let Some(tx) = message_tx.lock().as_ref() {
+                        let _ = tx.send(buffer.into());
+                    }
+                }
+            })));
+
+            data_channel.on_state_change(Some(Box::new({
+                let changed = changed.clone();
+                move |state| {
+                    if state == DataState::Closed {
+                        // End the stream
+                        message_tx.lock().take();
+                    }
+                    changed.notify_waiters();
+                }
+            })));
+
+            data_channel.on_buffered_amount_change(Some(Box::new({
+                let changed = changed.clone();
+                move |_| changed.notify_waiters()
+            })));
+
+            Self {
+                sender: DataChannelSender {
+                    data_channel,
+                    changed,
+                    max_buffered_amount,
+                },
+                message_rx,
+            }
+        }
+
+        pub fn data_channel(&self) -> &DataChannel {
+            &self.sender.data_channel
+        }
+
+        pub fn sender(&self) -> DataChannelSender {
+            self.sender.clone()
+        }
+
+        pub async fn send(&self, data: &[u8], binary: bool) -> Result<(), DataChannelError> {
+            self.sender.send(data, binary).await
+        }
+
+        pub async fn closed(&self) {
+            self.sender.closed().await
+        }
+    }
+
+    impl Stream for AsyncDataChannel {
+        type Item = DataMessage;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.message_rx.poll_recv(cx)
+        }
+    }
+
+    impl DataChannelSender {
+        pub fn data_channel(&self) -> &DataChannel {
+            &self.data_channel
+        }
+
+        /// Wait for the DataChannel to be open and for enough room in its send buffer.
+        /// Returns DataChannelError::Closed if the channel closes before the data is sent
+        pub async fn send(&self, data: &[u8], binary: bool) -> Result<(), DataChannelError> {
+            loop {
+                // Registered before checking the state so no notification is missed
+                let changed = self.changed.notified();
+                match self.data_channel.state() {
+                    DataState::Closing | DataState::Closed => return Err(DataChannelError::Closed),
+                    DataState::Open => {
+                        let buffered = self.data_channel.buffered_amount();
+                        if buffered == 0 || buffered + data.len() as u64 <= self.max_buffered_amount
+                        {
+                            return self.data_channel.send(data, binary);
+                        }
+                    }
+                    DataState::Connecting => {}
+                }
+                changed.await;
+            }
+        }
+
+        pub async fn closed(&self) {
+            loop {
+                let changed = self.changed.notified();
+                if self.data_channel.state() == DataState::Closed {
+                    return;
+                }
+                changed.await;
+            }
+        }
+
+        pub fn into_sink(self) -> impl Sink<DataMessage, Error = DataChannelError> {
+            sink::unfold(self, |sender, message: DataMessage| async move {
+                sender.send(&message.data, message.binary).await?;
+                Ok(sender)
+            })
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::native::{AsyncDataChannel, DEFAULT_MAX_BUFFERED_AMOUNT};
+    use crate::prelude::*;
+    use futures::StreamExt;
+    use tokio::sync::mpsc;
+
+    /// Connect two local peers, bob creates the DataChannel and alice receives it
+    async fn connect(
+        factory: &PeerConnectionFactory,
+    ) -> (PeerConnection, PeerConnection, DataChannel, DataChannel) {
+        let bob = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+        let alice = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+
+        let (bob_ice_tx, mut bob_ice_rx) = mpsc::unbounded_channel::<IceCandidate>();
+        let (alice_ice_tx, mut alice_ice_rx) = mpsc::unbounded_channel::<IceCandidate>();
+        let (alice_dc_tx, mut alice_dc_rx) = mpsc::unbounded_channel::<DataChannel>();
+        bob.on_ice_candidate(Some(Box::new(move |candidate| {
+            let _ = bob_ice_tx.send(candidate);
+        })));
+        alice.on_ice_candidate(Some(Box::new(move |candidate| {
+            let _ = alice_ice_tx.send(candidate);
+        })));
+        alice.on_data_channel(Some(Box::new(move |dc| {
+            let _ = alice_dc_tx.send(dc);
+        })));
+
+        let bob_dc = bob
+            .create_data_channel("test_dc", DataChannelInit::default())
+            .unwrap();
+
+        let offer = bob.create_offer(OfferOptions::default()).await.unwrap();
+        bob.set_local_description(offer.clone()).await.unwrap();
+        alice.set_remote_description(offer).await.unwrap();
+        let answer = alice.create_answer(AnswerOptions::default()).await.unwrap();
+        alice.set_local_description(answer.clone()).await.unwrap();
+        bob.set_remote_description(answer).await.unwrap();
+
+        tokio::spawn({
+            let alice = alice.clone();
+            async move {
+                while let Some(candidate) = bob_ice_rx.recv().await {
+                    let _ = alice.add_ice_candidate(candidate).await;
+                }
+            }
+        });
+        tokio::spawn({
+            let bob = bob.clone();
+            async move {
+                while let Some(candidate) = alice_ice_rx.recv().await {
+                    let _ = bob.add_ice_candidate(candidate).await;
+                }
+            }
+        });
+
+        let alice_dc = alice_dc_rx.recv().await.unwrap();
+        (bob, alice, bob_dc, alice_dc)
+    }
+
+    #[tokio::test]
+    async fn send_waits_for_open() {
+        let factory = PeerConnectionFactory::default();
+        let bob = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+        let dc = bob
+            .create_data_channel("test_dc", DataChannelInit::default())
+            .unwrap();
+        assert_eq!(dc.state(), DataState::Connecting);
+
+        // Never connected, the send is still pending
+        let dc = AsyncDataChannel::new(dc, DEFAULT_MAX_BUFFERED_AMOUNT);
+        let send = tokio::time::timeout(
+            std::time::Duration::from_millis(100),
+            dc.send(b"test", true),
+        );
+        assert!(send.await.is_err());
+        bob.close();
+    }
+
+    #[tokio::test]
+    async fn send_and_receive() {
+        let factory = PeerConnectionFactory::default();
+        let (bob, alice, bob_dc, alice_dc) = connect(&factory).await;
+        let bob_dc = AsyncDataChannel::new(bob_dc, DEFAULT_MAX_BUFFERED_AMOUNT);
+        let mut alice_dc = AsyncDataChannel::new(alice_dc, DEFAULT_MAX_BUFFERED_AMOUNT);
+
+        bob_dc.send(b"binary", true).await.unwrap();
+        bob_dc.send(b"text", false).await.unwrap();
+        assert!(matches!(
+            bob_dc.send(&[0xff], false).await,
+            Err(DataChannelError::Utf8(_))
+        ));
+
+        let message = alice_dc.next().await.unwrap();
+        assert_eq!(message.data, b"binary");
+        assert!(message.binary);
+        let message = alice_dc.next().await.unwrap();
+        assert_eq!(message.data, b"text");
+        assert!(!message.binary);
+
+        alice.close();
+        bob.close();
+    }
+
+    #[tokio::test]
+    async fn send_waits_for_the_buffer_to_drain() {
+        const MAX_BUFFERED_AMOUNT: u64 = 32 * 1024;
+        const MESSAGE_SIZE: usize = 16 * 1024;
+        const MESSAGES: usize = 64;
+
+        let factory = PeerConnectionFactory::default();
+        let (bob, alice, bob_dc, alice_dc) = connect(&factory).await;
+        let sender = AsyncDataChannel::new(bob_dc, MAX_BUFFERED_AMOUNT).sender();
+        let mut alice_dc = AsyncDataChannel::new(alice_dc, MAX_BUFFERED_AMOUNT);
+
+        let send = tokio::spawn(async move {
+            for i in 0..MESSAGES {
+                sender
+                    .send(&vec![i as u8; MESSAGE_SIZE], true)
+                    .await
+                    .unwrap();
+                assert!(sender.data_channel().buffered_amount() <= MAX_BUFFERED_AMOUNT);
+            }
+        });
+
+        for i in 0..MESSAGES {
+            let message = alice_dc.next().await.unwrap();
+            assert_eq!(message.data, vec![i as u8; MESSAGE_SIZE]);
+        }
+        send.await.unwrap();
+
+        alice.close();
+        bob.close();
+    }
+
+    #[tokio::test]
+    async fn send_fails_once_closed() {
+        let factory = PeerConnectionFactory::default();
+        let (bob, alice, bob_dc, alice_dc) = connect(&factory).await;
+        let bob_dc = AsyncDataChannel::new(bob_dc, DEFAULT_MAX_BUFFERED_AMOUNT);
+        let mut alice_dc = AsyncDataChannel::new(alice_dc, DEFAULT_MAX_BUFFERED_AMOUNT);
+
+        bob_dc.send(b"test", true).await.unwrap();
+        assert!(alice_dc.next().await.is_some());
+
+        alice_dc.data_channel().close();
+        bob_dc.closed().await;
+        alice_dc.closed().await;
+        assert!(matches!(
+            bob_dc.send(b"test", true).await,
+            Err(DataChannelError::Closed)
+        ));
+
+        // The stream ends once the channel is closed
+        assert!(alice_dc.next().await.is_none());
+
+        alice.close();
+        bob.close();
+    }
+}
@@ -1,6 +1,41 @@
 use crate::{imp::ice_candidate as imp_ic, session_description::SdpParseError};
 use std::fmt::Debug;
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CandidateType {
+    Host,
+    /// Server reflexive, public address discovered with STUN
+    Srflx,
+    /// Peer reflexive, address discovered during the connectivity checks
+    Prflx,
+    Relay,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TcpType {
+    Active,
+    Passive,
+    SimultaneousOpen,
+}
+
+/// Parsed "candidate:" attribute (RFC 8839)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub foundation: String,
+    pub component: i32,
+    /// udp, tcp, ssltcp or tls
+    pub protocol: String,
+    pub priority: u32,
+    /// IP address, or hostname for mDNS candidates
+    pub address: String,
+    pub port: u16,
+    /// None if the type isn't one of the known RFC 8445 types
+    pub candidate_type: Option<CandidateType>,
+    pub related_address: Option<String>,
+    pub related_port: Option<u16>,
+    pub tcp_type: Option<TcpType>,
+}
+
 pub struct IceCandidate {
     pub(crate) handle: imp_ic::IceCandidate,
 }
@@ -22,7 +57,7 @@ impl IceCandidate {
         self.handle.sdp_mline_index()
     }
 
-    pub fn candidate(&self) -> String {
+    pub fn candidate(&self) -> Candidate {
         self.handle.candidate()
     }
 }
@@ -35,6 +70,9 @@ impl ToString for IceCandidate {
 
 impl Debug for IceCandidate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("IceCandidate").finish()
+        f.debug_struct("IceCandidate")
+            .field("sdp_mid", &self.sdp_mid())
+            .field("candidate", &self.candidate())
+            .finish()
     }
 }
@@ -41,6 +41,7 @@ pub mod rtp_parameters;
 pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
+pub mod sdp;
 pub mod session_description;
 pub mod video_frame;
 pub mod video_source;
@@ -50,6 +51,8 @@ pub mod video_track;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
     pub use crate::imp::audio_resampler;
+    pub use crate::imp::snapshot;
+    pub use crate::imp::video_codec;
     pub use crate::imp::yuv_helper;
     pub use webrtc_sys::webrtc::ffi::create_random_uuid;
 }
@@ -22,6 +22,14 @@ impl MediaStreamTrack {
     );
 }
 
+#[cfg(target_arch = "wasm32")]
+impl MediaStreamTrack {
+    enum_dispatch!(
+        [Video, Audio];
+        pub fn sys_handle(self: &Self) -> web_sys::MediaStreamTrack;
+    );
+}
+
 impl MediaStreamTrack {
     enum_dispatch!(
         [Video, Audio];
@@ -56,6 +64,11 @@ macro_rules! media_stream_track {
         ) -> cxx::SharedPtr<webrtc_sys::media_stream::ffi::MediaStreamTrack> {
             self.handle.sys_handle()
         }
+
+        #[cfg(target_arch = "wasm32")]
+        pub fn sys_handle(&self) -> web_sys::MediaStreamTrack {
+            self.handle.sys_handle()
+        }
     };
 }
 
@@ -1,19 +1,53 @@
+use super::audio_source::AudioSourceInner;
+use crate::audio_frame::{remix_interleaved, AudioFrame, Sample};
 use cxx::UniquePtr;
+use thiserror::Error;
 use webrtc_sys::audio_resampler as sys_ar;
 
+/// Limits of the libwebrtc resampler
+pub const MAX_CHANNELS: u32 = 24;
+pub const MAX_SAMPLE_RATE: u32 = 384_000;
+
+#[derive(Error, Debug)]
+pub enum ResampleError {
+    #[error("invalid sample rate: {0}Hz")]
+    InvalidSampleRate(u32),
+    #[error("invalid number of channels: {0}")]
+    InvalidChannels(u32),
+    #[error("invalid size: {0} samples")]
+    InvalidSize(usize),
+}
+
+fn check_format(sample_rate: u32, num_channels: u32) -> Result<(), ResampleError> {
+    if sample_rate == 0 || sample_rate % 100 != 0 || sample_rate > MAX_SAMPLE_RATE {
+        return Err(ResampleError::InvalidSampleRate(sample_rate));
+    }
+
+    if num_channels == 0 || num_channels > MAX_CHANNELS {
+        return Err(ResampleError::InvalidChannels(num_channels));
+    }
+
+    Ok(())
+}
+
 pub struct AudioResampler {
     sys_handle: UniquePtr<sys_ar::ffi::AudioResampler>,
+    remixed: Vec<i16>,
+    remixed_f32: Vec<f32>,
 }
 
 impl Default for AudioResampler {
     fn default() -> Self {
         Self {
             sys_handle: sys_ar::ffi::create_audio_resampler(),
+            remixed: Vec::new(),
+            remixed_f32: Vec::new(),
         }
     }
 }
 
 impl AudioResampler {
+    /// src must contain 10ms of audio
     pub fn remix_and_resample(
         &mut self,
         src: &[i16],
@@ -22,18 +56,260 @@ impl AudioResampler {
         sample_rate: u32,
         dst_num_channels: u32,
         dst_sample_rate: u32,
-    ) -> &[i16] {
-        unsafe {
-            let len = self.sys_handle.pin_mut().remix_and_resample(
-                src.as_ptr(),
-                samples_per_channel as usize,
-                num_channels as usize,
-                sample_rate as i32,
-                dst_num_channels as usize,
-                dst_sample_rate as i32,
-            );
-
-            std::slice::from_raw_parts(self.sys_handle.data(), len / 2)
+    ) -> Result<&[i16], ResampleError> {
+        let src = check_10ms(
+            src,
+            samples_per_channel,
+            num_channels,
+            sample_rate,
+            dst_num_channels,
+            dst_sample_rate,
+        )?;
+
+        self.remixed = remix_interleaved(src, num_channels as usize, dst_num_channels as usize);
+        Ok(self.sys_handle.pin_mut().resample(
+            &self.remixed,
+            dst_num_channels as usize,
+            sample_rate as i32,
+            dst_sample_rate as i32,
+        ))
+    }
+
+    /// Same as remix_and_resample, without converting the samples to i16
+    pub fn remix_and_resample_f32(
+        &mut self,
+        src: &[f32],
+        samples_per_channel: u32,
+        num_channels: u32,
+        sample_rate: u32,
+        dst_num_channels: u32,
+        dst_sample_rate: u32,
+    ) -> Result<&[f32], ResampleError> {
+        let src = check_10ms(
+            src,
+            samples_per_channel,
+            num_channels,
+            sample_rate,
+            dst_num_channels,
+            dst_sample_rate,
+        )?;
+
+        self.remixed_f32 = remix_interleaved(src, num_channels as usize, dst_num_channels as usize);
+        Ok(self.sys_handle.pin_mut().resample_f32(
+            &self.remixed_f32,
+            dst_num_channels as usize,
+            sample_rate as i32,
+            dst_sample_rate as i32,
+        ))
+    }
+}
+
+/// Returns the 10ms of src to resample
+fn check_10ms<S>(
+    src: &[S],
+    samples_per_channel: u32,
+    num_channels: u32,
+    sample_rate: u32,
+    dst_num_channels: u32,
+    dst_sample_rate: u32,
+) -> Result<&[S], ResampleError> {
+    check_format(sample_rate, num_channels)?;
+    check_format(dst_sample_rate, dst_num_channels)?;
+
+    let len = (samples_per_channel * num_channels) as usize;
+    if samples_per_channel != sample_rate / 100 || src.len() < len {
+        return Err(ResampleError::InvalidSize(src.len()));
+    }
+
+    Ok(&src[..len])
+}
+
+/// Resample frames of any size to a fixed sample rate and number of channels.
+/// Incomplete 10ms chunks are kept for the next call, the resampling is done in f32.
+pub struct StreamingResampler {
+    resampler: AudioResampler,
+    input: AudioSourceInner<f32>,
+    sample_rate: u32,
+    num_channels: u32,
+}
+
+impl StreamingResampler {
+    /// Fails if the output format is not supported (see MAX_SAMPLE_RATE and MAX_CHANNELS)
+    pub fn new(sample_rate: u32, num_channels: u32) -> Result<Self, ResampleError> {
+        check_format(sample_rate, num_channels)?;
+
+        Ok(Self {
+            resampler: AudioResampler::default(),
+            input: AudioSourceInner::default(),
+            sample_rate,
+            num_channels,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn num_channels(&self) -> u32 {
+        self.num_channels
+    }
+
+    /// Returns the resampled audio, or None if there isn't enough data yet to make 10ms
+    pub fn push<S: Sample>(
+        &mut self,
+        frame: &AudioFrame<S>,
+    ) -> Result<Option<AudioFrame<S>>, ResampleError> {
+        check_format(frame.sample_rate, frame.num_channels)?;
+
+        // Timestamps of the first sample buffered from a previous call
+        let buffered_samples = if self.input.sample_rate == frame.sample_rate
+            && self.input.num_channels == frame.num_channels
+        {
+            (self.input.offset / frame.num_channels as usize) as u32
+        } else {
+            0
+        };
+        let buffered_ms = (buffered_samples as u64 * 1000 / frame.sample_rate as u64) as i64;
+
+        let samples: Vec<f32> = frame.data.iter().map(|s| s.to_f32()).collect();
+        let mut data = Vec::new();
+        let mut result = Ok(());
+        let (resampler, sample_rate, num_channels) =
+            (&mut self.resampler, self.sample_rate, self.num_channels);
+        self.input.split_10ms(
+            &samples,
+            frame.sample_rate,
+            frame.num_channels,
+            |chunk, nb_frames| {
+                if frame.sample_rate == sample_rate && frame.num_channels == num_channels {
+                    data.extend(chunk.iter().map(|s| S::from_f32(*s)));
+                    return;
+                }
+
+                match resampler.remix_and_resample_f32(
+                    chunk,
+                    nb_frames as u32,
+                    frame.num_channels,
+                    frame.sample_rate,
+                    num_channels,
+                    sample_rate,
+                ) {
+                    Ok(resampled) => data.extend(resampled.iter().map(|s| S::from_f32(*s))),
+                    Err(err) => result = Err(err),
+                }
+            },
+        );
+        result?;
+
+        if data.is_empty() {
+            return Ok(None);
         }
+
+        Ok(Some(AudioFrame {
+            samples_per_channel: (data.len() / num_channels as usize) as u32,
+            data,
+            sample_rate,
+            num_channels,
+            rtp_timestamp: frame
+                .rtp_timestamp
+                .map(|t| t.wrapping_sub(buffered_samples)),
+            ntp_time_ms: frame.ntp_time_ms.map(|t| t - buffered_ms),
+            receive_time_us: frame.receive_time_us.map(|t| t - buffered_ms * 1000),
+        }))
+    }
+
+    /// Drop the incomplete chunk kept from the previous calls
+    pub fn reset(&mut self) {
+        self.input = AudioSourceInner::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioResampler, ResampleError, StreamingResampler};
+    use crate::audio_frame::AudioFrame;
+
+    fn frame<S: Copy>(sample: S, sample_rate: u32, num_channels: u32, ms: u32) -> AudioFrame<S> {
+        let samples_per_channel = sample_rate / 1000 * ms;
+        AudioFrame {
+            data: vec![sample; (samples_per_channel * num_channels) as usize],
+            sample_rate,
+            num_channels,
+            samples_per_channel,
+            rtp_timestamp: Some(4800),
+            ntp_time_ms: Some(1000),
+            receive_time_us: None,
+        }
+    }
+
+    #[test]
+    fn buffers_incomplete_chunks() {
+        let mut resampler = StreamingResampler::new(16000, 1).unwrap();
+
+        // 15ms, the last 5ms are kept for the next push
+        let resampled = resampler.push(&frame(0i16, 48000, 2, 15)).unwrap().unwrap();
+        assert_eq!(resampled.sample_rate, 16000);
+        assert_eq!(resampled.num_channels, 1);
+        assert_eq!(resampled.samples_per_channel, 160);
+        assert_eq!(resampled.data.len(), 160);
+
+        // The output starts with the 5ms buffered by the previous push
+        let resampled = resampler.push(&frame(0i16, 48000, 2, 5)).unwrap().unwrap();
+        assert_eq!(resampled.samples_per_channel, 160);
+        assert_eq!(resampled.rtp_timestamp, Some(4800 - 240));
+        assert_eq!(resampled.ntp_time_ms, Some(1000 - 5));
+
+        assert!(resampler.push(&frame(0i16, 48000, 2, 5)).unwrap().is_none());
+        resampler.reset();
+        assert!(resampler.push(&frame(0i16, 48000, 2, 5)).unwrap().is_none());
+    }
+
+    #[test]
+    fn keeps_f32_precision() {
+        let mut resampler = StreamingResampler::new(48000, 1).unwrap();
+
+        // Out of range samples are not clipped, and small ones are not rounded to 0
+        for sample in [1.5f32, 1e-6] {
+            let resampled = resampler
+                .push(&frame(sample, 48000, 2, 10))
+                .unwrap()
+                .unwrap();
+            assert!(resampled.data.iter().all(|s| *s == sample));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_formats() {
+        assert!(matches!(
+            StreamingResampler::new(44100, 0),
+            Err(ResampleError::InvalidChannels(0))
+        ));
+
+        let mut resampler = StreamingResampler::new(48000, 1).unwrap();
+        assert!(matches!(
+            resampler.push(&frame(0i16, 22050, 1, 10)),
+            Err(ResampleError::InvalidSampleRate(22050))
+        ));
+        assert!(matches!(
+            resampler.push(&frame(0i16, 48000, 0, 10)),
+            Err(ResampleError::InvalidChannels(0))
+        ));
+
+        let mut resampler = AudioResampler::default();
+        assert!(matches!(
+            resampler.remix_and_resample(&[0; 480], 480, 2, 48000, 1, 16000),
+            Err(ResampleError::InvalidSize(480))
+        ));
+        assert!(matches!(
+            resampler.remix_and_resample(&[0; 960], 480, 2, 48000, 1, 22050),
+            Err(ResampleError::InvalidSampleRate(22050))
+        ));
+        assert_eq!(
+            resampler
+                .remix_and_resample(&[0; 960], 480, 2, 48000, 1, 16000)
+                .unwrap()
+                .len(),
+            160
+        );
     }
 }
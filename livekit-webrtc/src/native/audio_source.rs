@@ -1,83 +1,488 @@
-use crate::audio_frame::AudioFrame;
+use super::audio_resampler::{AudioResampler, MAX_CHANNELS, MAX_SAMPLE_RATE};
+use crate::audio_frame::{AudioFrame, Sample};
+use crate::audio_source::native::{AudioSourceError, EncodedAudioFrameInfo, DEFAULT_QUEUE_SIZE_MS};
+use crate::audio_source::AudioSourceOptions;
 use cxx::SharedPtr;
 use parking_lot::Mutex;
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
 use webrtc_sys::audio_track as sys_at;
 
 #[derive(Clone)]
 pub struct NativeAudioSource {
-    sys_handle: SharedPtr<sys_at::ffi::AudioTrackSource>,
-    inner: Arc<Mutex<AudioSourceInner>>,
+    shared: Arc<SourceShared>,
 }
 
-#[derive(Default)]
-struct AudioSourceInner {
-    buf: Vec<i16>,
-    offset: usize,
+struct SourceShared {
+    sys_handle: SharedPtr<sys_at::ffi::AudioTrackSource>,
     sample_rate: u32,
     num_channels: u32,
+    queue_size: usize, // Max number of queued samples (all channels)
+    input: tokio::sync::Mutex<AudioInput>,
+    queue: Mutex<VecDeque<i16>>,
+    queue_notify: Notify,
+    reverse: Mutex<AudioSourceInner>,
+    runtime: Option<Handle>,
+    send_task: Mutex<Option<JoinHandle<()>>>,
 }
 
-impl Default for NativeAudioSource {
-    fn default() -> Self {
-        Self {
-            sys_handle: sys_at::ffi::new_audio_track_source(),
-            inner: Default::default(),
-        }
-    }
+#[derive(Default)]
+struct AudioInput {
+    inner: AudioSourceInner,
+    resampler: AudioResampler,
 }
 
-impl NativeAudioSource {
-    pub fn sys_handle(&self) -> SharedPtr<sys_at::ffi::AudioTrackSource> {
-        self.sys_handle.clone()
-    }
+#[derive(Default)]
+pub(super) struct AudioSourceInner<T = i16> {
+    buf: Vec<T>,
+    pub(super) offset: usize,
+    pub(super) sample_rate: u32,
+    pub(super) num_channels: u32,
+}
 
-    pub fn capture_frame(&self, frame: &AudioFrame) {
-        let mut inner = self.inner.lock();
-        let samples_10ms = (frame.sample_rate / 100 * frame.num_channels) as usize;
-        if inner.sample_rate != frame.sample_rate || inner.num_channels != frame.num_channels {
-            inner.buf.resize(samples_10ms as usize, 0);
-            inner.offset = 0;
-            inner.sample_rate = frame.sample_rate;
-            inner.num_channels = frame.num_channels;
+impl<T: Sample> AudioSourceInner<T> {
+    /// Split the samples into 10ms chunks, incomplete chunks are kept for the next call.
+    /// The format must be validated with check_format
+    pub(super) fn split_10ms(
+        &mut self,
+        data: &[T],
+        sample_rate: u32,
+        num_channels: u32,
+        mut on_10ms: impl FnMut(&[T], usize),
+    ) {
+        debug_assert!(check_format(sample_rate, num_channels).is_ok());
+
+        let samples_10ms = (sample_rate / 100 * num_channels) as usize;
+        if self.sample_rate != sample_rate || self.num_channels != num_channels {
+            self.buf.resize(samples_10ms, T::default());
+            self.offset = 0;
+            self.sample_rate = sample_rate;
+            self.num_channels = num_channels;
         }
 
-        // Split the frame into 10ms chunks
         let mut i = 0;
         loop {
-            let buf_offset = inner.offset;
-            let remaining_data = frame.data.len() - i; // Remaining data to read inside the frame
-            let needed_data = samples_10ms - buf_offset; // Needed data of "frame.data" to make a complete 10ms from inner.buf
+            let buf_offset = self.offset;
+            let remaining_data = data.len() - i; // Remaining data to read inside the frame
+            let needed_data = samples_10ms - buf_offset; // Needed data of "data" to make a complete 10ms from self.buf
             if remaining_data < needed_data {
                 if remaining_data > 0 {
-                    // Not enough data to make a complete 10ms frame, store the remaining data inside inner.buf
+                    // Not enough data to make a complete 10ms frame, store the remaining data inside self.buf
                     // It'll be used on the next capture.
-                    inner.buf[buf_offset..buf_offset + remaining_data]
-                        .copy_from_slice(&frame.data[i..]);
-                    inner.offset += remaining_data;
+                    self.buf[buf_offset..buf_offset + remaining_data].copy_from_slice(&data[i..]);
+                    self.offset += remaining_data;
                 }
 
                 break;
             }
 
-            let data = if inner.offset != 0 {
+            let chunk = if self.offset != 0 {
                 // Use the data from the previous capture
-                let data = &mut inner.buf[buf_offset..];
-                data.copy_from_slice(&frame.data[i..i + needed_data]);
-                inner.offset = 0;
-                &inner.buf
+                self.buf[buf_offset..].copy_from_slice(&data[i..i + needed_data]);
+                self.offset = 0;
+                &self.buf
             } else {
-                &frame.data[i..i + samples_10ms]
+                &data[i..i + samples_10ms]
             };
 
-            self.sys_handle.on_captured_frame(
-                data,
-                frame.sample_rate as i32,
-                frame.num_channels as usize,
-                samples_10ms / frame.num_channels as usize,
-            );
-
+            on_10ms(chunk, samples_10ms / num_channels as usize);
             i += needed_data;
         }
     }
 }
+
+/// 10ms must be a whole number of samples, and libwebrtc must be able to resample it
+pub(super) fn check_format(sample_rate: u32, num_channels: u32) -> Result<(), AudioSourceError> {
+    if sample_rate == 0 || sample_rate % 100 != 0 || sample_rate > MAX_SAMPLE_RATE {
+        return Err(AudioSourceError::InvalidSampleRate(sample_rate));
+    }
+
+    if num_channels == 0 || num_channels > MAX_CHANNELS {
+        return Err(AudioSourceError::InvalidChannels(num_channels));
+    }
+
+    Ok(())
+}
+
+impl Default for NativeAudioSource {
+    fn default() -> Self {
+        Self::new(
+            AudioSourceOptions::default(),
+            48000,
+            2,
+            DEFAULT_QUEUE_SIZE_MS,
+            None,
+        )
+        .unwrap() // 48kHz stereo is always valid
+    }
+}
+
+impl NativeAudioSource {
+    pub fn new(
+        options: AudioSourceOptions,
+        sample_rate: u32,
+        num_channels: u32,
+        queue_size_ms: u32,
+        runtime: Option<Handle>,
+    ) -> Result<Self, AudioSourceError> {
+        check_format(sample_rate, num_channels)?;
+
+        let samples_10ms = (sample_rate / 100 * num_channels) as usize;
+        Ok(Self {
+            shared: Arc::new(SourceShared {
+                sys_handle: sys_at::ffi::new_audio_track_source(options.into()),
+                sample_rate,
+                num_channels,
+                queue_size: (queue_size_ms as usize / 10).max(1) * samples_10ms,
+                input: Default::default(),
+                queue: Default::default(),
+                queue_notify: Notify::new(),
+                reverse: Default::default(),
+                runtime,
+                send_task: Default::default(),
+            }),
+        })
+    }
+
+    pub fn sys_handle(&self) -> SharedPtr<sys_at::ffi::AudioTrackSource> {
+        self.shared.sys_handle.clone()
+    }
+
+    pub fn audio_options(&self) -> AudioSourceOptions {
+        self.shared.sys_handle.audio_options().into()
+    }
+
+    pub fn set_audio_options(&self, options: AudioSourceOptions) {
+        self.shared.sys_handle.set_audio_options(&options.into())
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.shared.sample_rate
+    }
+
+    pub fn num_channels(&self) -> u32 {
+        self.shared.num_channels
+    }
+
+    pub fn queue_depth(&self) -> u32 {
+        (self.shared.queue.lock().len() / self.shared.num_channels as usize) as u32
+    }
+
+    pub fn latency(&self) -> Duration {
+        Duration::from_secs_f64(self.queue_depth() as f64 / self.shared.sample_rate as f64)
+    }
+
+    pub async fn capture_frame<S: Sample>(
+        &self,
+        frame: &AudioFrame<S>,
+    ) -> Result<(), AudioSourceError> {
+        let shared = &self.shared;
+        check_format(frame.sample_rate, frame.num_channels)?;
+        self.ensure_send_task()?;
+
+        // Keep the order of the frames when there are multiple producers
+        let mut input = shared.input.lock().await;
+
+        // Size of the frame once resampled
+        let needed = (frame.samples_per_channel as u64 * shared.sample_rate as u64
+            / frame.sample_rate.max(1) as u64) as usize
+            * shared.num_channels as usize;
+
+        loop {
+            let notified = shared.queue_notify.notified();
+            {
+                let queue = shared.queue.lock();
+                // Always accept frames bigger than the queue once it is empty
+                if queue.is_empty() || queue.len() + needed <= shared.queue_size {
+                    break;
+                }
+            }
+            notified.await;
+        }
+
+        let AudioInput { inner, resampler } = &mut *input;
+        let data = S::to_i16_slice(&frame.data);
+        inner.split_10ms(
+            &data,
+            frame.sample_rate,
+            frame.num_channels,
+            |data, nb_frames| {
+                if frame.sample_rate == shared.sample_rate
+                    && frame.num_channels == shared.num_channels
+                {
+                    shared.queue.lock().extend(data);
+                    return;
+                }
+
+                match resampler.remix_and_resample(
+                    data,
+                    nb_frames as u32,
+                    frame.num_channels,
+                    frame.sample_rate,
+                    shared.num_channels,
+                    shared.sample_rate,
+                ) {
+                    Ok(data) => shared.queue.lock().extend(data),
+                    Err(err) => log::error!("failed to resample the captured audio: {}", err),
+                }
+            },
+        );
+
+        Ok(())
+    }
+
+    /// The task is (re)spawned if it isn't running, e.g when its runtime was shut down
+    fn ensure_send_task(&self) -> Result<(), AudioSourceError> {
+        let mut send_task = self.shared.send_task.lock();
+        if let Some(task) = send_task.as_ref() {
+            if !task.is_finished() {
+                return Ok(());
+            }
+        }
+
+        let runtime = match &self.shared.runtime {
+            Some(runtime) => runtime.clone(),
+            None => Handle::try_current().map_err(|_| AudioSourceError::NoRuntime)?,
+        };
+
+        *send_task = Some(runtime.spawn(send_task_loop(Arc::downgrade(&self.shared))));
+        Ok(())
+    }
+
+    pub fn capture_reverse_frame<S: Sample>(
+        &self,
+        frame: &AudioFrame<S>,
+    ) -> Result<(), AudioSourceError> {
+        check_format(frame.sample_rate, frame.num_channels)?;
+
+        let data = S::to_i16_slice(&frame.data);
+        self.shared.reverse.lock().split_10ms(
+            &data,
+            frame.sample_rate,
+            frame.num_channels,
+            |data, nb_frames| {
+                self.shared.sys_handle.on_reverse_frame(
+                    data,
+                    frame.sample_rate as i32,
+                    frame.num_channels as usize,
+                    nb_frames,
+                );
+            },
+        );
+
+        Ok(())
+    }
+}
+
+/// Send the queued samples to libwebrtc in real time, 10ms at a time
+async fn send_task_loop(shared: Weak<SourceShared>) {
+    let mut interval = tokio::time::interval(Duration::from_millis(10));
+    let mut data = Vec::new();
+
+    loop {
+        interval.tick().await;
+
+        let shared = match shared.upgrade() {
+            Some(shared) => shared,
+            None => break, // All the NativeAudioSource were dropped
+        };
+
+        let samples_10ms = (shared.sample_rate / 100 * shared.num_channels) as usize;
+        {
+            let mut queue = shared.queue.lock();
+            if queue.len() < samples_10ms {
+                continue;
+            }
+
+            data.clear();
+            data.extend(queue.drain(..samples_10ms));
+        }
+
+        shared.sys_handle.on_captured_frame(
+            &data,
+            shared.sample_rate as i32,
+            shared.num_channels as usize,
+            samples_10ms / shared.num_channels as usize,
+        );
+        shared.queue_notify.notify_waiters();
+    }
+}
+
+#[derive(Clone)]
+pub struct EncodedAudioSource {
+    sys_handle: SharedPtr<sys_at::ffi::AudioTrackSource>,
+}
+
+impl Default for EncodedAudioSource {
+    fn default() -> Self {
+        Self {
+            // Never process the carriers of the encoded packets
+            sys_handle: sys_at::ffi::new_audio_track_source(AudioSourceOptions::default().into()),
+        }
+    }
+}
+
+impl EncodedAudioSource {
+    pub fn sys_handle(&self) -> SharedPtr<sys_at::ffi::AudioTrackSource> {
+        self.sys_handle.clone()
+    }
+
+    pub fn capture_frame(&self, data: &[u8], info: &EncodedAudioFrameInfo) -> bool {
+        self.sys_handle.on_encoded_frame(
+            data,
+            sys_at::ffi::EncodedAudioFrameInfo {
+                sample_rate: info.sample_rate,
+                samples_per_channel: info.samples_per_channel,
+            },
+        )
+    }
+}
+
+impl From<AudioSourceOptions> for sys_at::ffi::AudioSourceOptions {
+    fn from(value: AudioSourceOptions) -> Self {
+        Self {
+            echo_cancellation: value.echo_cancellation,
+            noise_suppression: value.noise_suppression,
+            auto_gain_control: value.auto_gain_control,
+        }
+    }
+}
+
+impl From<sys_at::ffi::AudioSourceOptions> for AudioSourceOptions {
+    fn from(value: sys_at::ffi::AudioSourceOptions) -> Self {
+        Self {
+            echo_cancellation: value.echo_cancellation,
+            noise_suppression: value.noise_suppression,
+            auto_gain_control: value.auto_gain_control,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_format, AudioSourceInner};
+    use crate::audio_frame::AudioFrame;
+    use crate::audio_source::native::{AudioSourceError, NativeAudioSource};
+    use crate::audio_source::AudioSourceOptions;
+    use std::time::{Duration, Instant};
+
+    fn mono_frame(sample_rate: u32, samples_per_channel: u32, first: i16) -> AudioFrame {
+        let mut frame = AudioFrame::new(sample_rate, 1, samples_per_channel);
+        for (i, sample) in frame.data.iter_mut().enumerate() {
+            *sample = first + i as i16;
+        }
+        frame
+    }
+
+    #[test]
+    fn split_keeps_incomplete_chunks() {
+        let mut inner = AudioSourceInner::default();
+        let mut chunks = Vec::new();
+
+        // 5ms, nothing to send yet
+        let frame = mono_frame(48000, 240, 0);
+        inner.split_10ms(&frame.data, 48000, 1, |data, _| chunks.push(data.to_vec()));
+        assert!(chunks.is_empty());
+
+        // 10ms, completes the first chunk and keeps the second half
+        let frame = mono_frame(48000, 480, 240);
+        inner.split_10ms(&frame.data, 48000, 1, |data, nb_frames| {
+            assert_eq!(nb_frames, 480);
+            chunks.push(data.to_vec())
+        });
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0], (0..480).collect::<Vec<i16>>());
+        assert_eq!(inner.offset, 240);
+
+        // A new format drops the buffered samples
+        let frame = mono_frame(16000, 160, 0);
+        inner.split_10ms(&frame.data, 16000, 1, |data, _| chunks.push(data.to_vec()));
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1], (0..160).collect::<Vec<i16>>());
+        assert_eq!(inner.offset, 0);
+    }
+
+    #[test]
+    fn check_rejects_invalid_formats() {
+        assert!(matches!(
+            check_format(22050, 1),
+            Err(AudioSourceError::InvalidSampleRate(22050))
+        ));
+        assert!(matches!(
+            check_format(0, 1),
+            Err(AudioSourceError::InvalidSampleRate(0))
+        ));
+        assert!(matches!(
+            check_format(48000, 0),
+            Err(AudioSourceError::InvalidChannels(0))
+        ));
+        assert!(check_format(44100, 2).is_ok());
+        assert!(matches!(
+            NativeAudioSource::new(AudioSourceOptions::default(), 48000, 0, 20),
+            Err(AudioSourceError::InvalidChannels(0))
+        ));
+    }
+
+    #[tokio::test]
+    async fn capture_waits_for_the_queue() {
+        let source = NativeAudioSource::new(AudioSourceOptions::default(), 48000, 1, 20).unwrap();
+        let frame = mono_frame(48000, 480, 0);
+
+        let start = Instant::now();
+        for _ in 0..5 {
+            source.capture_frame(&frame).await.unwrap();
+            assert!(source.queue_depth() <= 960);
+        }
+
+        // 50ms were captured but only 20ms can be queued ahead of real time
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        let resampled = mono_frame(16000, 160, 0);
+        source.capture_frame(&resampled).await.unwrap();
+        assert!(matches!(
+            source.capture_frame(&mono_frame(22050, 220, 0)).await,
+            Err(AudioSourceError::InvalidSampleRate(22050))
+        ));
+    }
+
+    #[test]
+    fn capture_uses_the_given_runtime() {
+        let frame = mono_frame(48000, 480, 0);
+
+        // Polled outside of a tokio runtime
+        let source = NativeAudioSource::new(AudioSourceOptions::default(), 48000, 1, 20).unwrap();
+        assert!(matches!(
+            futures::executor::block_on(source.capture_frame(&frame)),
+            Err(AudioSourceError::NoRuntime)
+        ));
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let explicit = NativeAudioSource::with_runtime(
+            AudioSourceOptions::default(),
+            48000,
+            1,
+            20,
+            runtime.handle().clone(),
+        )
+        .unwrap();
+        futures::executor::block_on(async {
+            for _ in 0..5 {
+                explicit.capture_frame(&frame).await.unwrap();
+            }
+        });
+
+        // The send task is spawned again once its runtime is shut down
+        runtime.block_on(source.capture_frame(&frame)).unwrap();
+        drop(runtime);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            for _ in 0..5 {
+                source.capture_frame(&frame).await.unwrap();
+            }
+        });
+    }
+}
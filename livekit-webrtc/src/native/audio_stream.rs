@@ -1,5 +1,8 @@
+use super::encoded_stream::NativeEncodedStream;
 use crate::audio_frame::AudioFrame;
+use crate::audio_stream::native::EncodedAudioFrame;
 use crate::audio_track::RtcAudioTrack;
+use crate::rtp_receiver::RtpReceiver;
 use cxx::SharedPtr;
 use futures::stream::Stream;
 use std::pin::Pin;
@@ -11,11 +14,12 @@ use webrtc_sys::audio_track as sys_at;
 pub struct NativeAudioStream {
     native_sink: SharedPtr<sys_at::ffi::NativeAudioSink>,
     audio_track: RtcAudioTrack,
+    receiver: Option<RtpReceiver>,
     frame_rx: mpsc::UnboundedReceiver<AudioFrame>,
 }
 
 impl NativeAudioStream {
-    pub fn new(audio_track: RtcAudioTrack) -> Self {
+    pub fn new(audio_track: RtcAudioTrack, receiver: Option<RtpReceiver>) -> Self {
         let (frame_tx, frame_rx) = mpsc::unbounded_channel();
         let observer = Arc::new(AudioTrackObserver { frame_tx });
         let native_sink = sys_at::ffi::new_native_audio_sink(Box::new(
@@ -28,6 +32,7 @@ impl NativeAudioStream {
         Self {
             native_sink,
             audio_track,
+            receiver,
             frame_rx,
         }
     }
@@ -53,8 +58,26 @@ impl Drop for NativeAudioStream {
 impl Stream for NativeAudioStream {
     type Item = AudioFrame;
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        self.frame_rx.poll_recv(cx)
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.frame_rx.poll_recv(cx).map(|frame| {
+            frame.map(|mut frame| {
+                // The sink of the prebuilt libwebrtc doesn't get the RTP info of the decoded
+                // audio, use the last packet played out by the receiver instead
+                let source = this.receiver.as_ref().and_then(|receiver| {
+                    receiver
+                        .get_synchronization_sources()
+                        .into_iter()
+                        .max_by_key(|source| source.timestamp_ms)
+                });
+
+                if let Some(source) = source {
+                    frame.rtp_timestamp = Some(source.rtp_timestamp);
+                    frame.receive_time_us = Some(source.timestamp_ms * 1000);
+                }
+                frame
+            })
+        })
     }
 }
 
@@ -63,13 +86,60 @@ pub struct AudioTrackObserver {
 }
 
 impl sys_at::AudioSink for AudioTrackObserver {
-    fn on_data(&self, data: &[i16], sample_rate: i32, nb_channels: usize, nb_frames: usize) {
+    fn on_data(
+        &self,
+        data: &[i16],
+        sample_rate: i32,
+        nb_channels: usize,
+        nb_frames: usize,
+        timing: sys_at::ffi::AudioDataTiming,
+    ) {
+        let ntp_time_ms = timing.absolute_capture_timestamp_ms;
+
         // TODO(theomonnom): Should we avoid copy here?
         let _ = self.frame_tx.send(AudioFrame {
             data: data.to_owned(),
             sample_rate: sample_rate as u32,
             num_channels: nb_channels as u32,
             samples_per_channel: nb_frames as u32,
+            rtp_timestamp: None,
+            ntp_time_ms: (ntp_time_ms > 0).then_some(ntp_time_ms),
+            receive_time_us: None,
         });
     }
 }
+
+pub struct NativeEncodedAudioStream {
+    stream: NativeEncodedStream,
+}
+
+impl NativeEncodedAudioStream {
+    pub fn new(receiver: RtpReceiver) -> Self {
+        Self {
+            stream: NativeEncodedStream::new(receiver),
+        }
+    }
+
+    pub fn receiver(&self) -> RtpReceiver {
+        self.stream.receiver()
+    }
+
+    pub fn close(&mut self) {
+        self.stream.close();
+    }
+}
+
+impl Stream for NativeEncodedAudioStream {
+    type Item = EncodedAudioFrame;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next(cx).map(|frame| {
+            frame.map(|frame| EncodedAudioFrame {
+                data: frame.data,
+                mime_type: frame.mime_type,
+                rtp_timestamp: frame.info.rtp_timestamp,
+                ssrc: frame.info.ssrc,
+            })
+        })
+    }
+}
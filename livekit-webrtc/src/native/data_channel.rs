@@ -81,6 +81,10 @@ impl DataChannel {
         self.sys_handle.state().into()
     }
 
+    pub fn buffered_amount(&self) -> u64 {
+        self.sys_handle.buffered_amount()
+    }
+
     pub fn close(&self) {
         self.sys_handle.close();
     }
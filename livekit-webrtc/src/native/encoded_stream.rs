@@ -0,0 +1,92 @@
+use crate::rtp_receiver::RtpReceiver;
+use cxx::SharedPtr;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use webrtc_sys::encoded_stream as sys_es;
+
+pub struct EncodedFrame {
+    pub data: Vec<u8>,
+    pub mime_type: String,
+    pub info: sys_es::ffi::EncodedFrameInfo,
+}
+
+/// Shared by NativeEncodedVideoStream and NativeEncodedAudioStream
+pub struct NativeEncodedStream {
+    native_sink: SharedPtr<sys_es::ffi::NativeEncodedSink>,
+    receiver: RtpReceiver,
+    frame_rx: mpsc::UnboundedReceiver<(Vec<u8>, sys_es::ffi::EncodedFrameInfo)>,
+    mime_types: HashMap<u8, String>,
+}
+
+impl NativeEncodedStream {
+    pub fn new(receiver: RtpReceiver) -> Self {
+        let (frame_tx, frame_rx) = mpsc::unbounded_channel();
+        let observer = Arc::new(EncodedObserver { frame_tx });
+        let native_sink = sys_es::ffi::new_native_encoded_sink(
+            &receiver.handle.sys_handle,
+            Box::new(sys_es::EncodedSinkWrapper::new(observer)),
+        );
+
+        Self {
+            native_sink,
+            receiver,
+            frame_rx,
+            mime_types: HashMap::new(),
+        }
+    }
+
+    pub fn receiver(&self) -> RtpReceiver {
+        self.receiver.clone()
+    }
+
+    pub fn close(&mut self) {
+        self.native_sink.close();
+        self.frame_rx.close();
+    }
+
+    pub fn poll_next(&mut self, cx: &mut Context) -> Poll<Option<EncodedFrame>> {
+        self.frame_rx.poll_recv(cx).map(|res| {
+            res.map(|(data, info)| EncodedFrame {
+                data,
+                mime_type: self.mime_type(info.payload_type),
+                info,
+            })
+        })
+    }
+
+    fn mime_type(&mut self, payload_type: u8) -> String {
+        if !self.mime_types.contains_key(&payload_type) {
+            // The codecs may have changed after a renegotiation
+            self.mime_types = self
+                .receiver
+                .parameters()
+                .codecs
+                .into_iter()
+                .map(|codec| (codec.payload_type, codec.mime_type))
+                .collect();
+        }
+
+        self.mime_types
+            .get(&payload_type)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for NativeEncodedStream {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+struct EncodedObserver {
+    frame_tx: mpsc::UnboundedSender<(Vec<u8>, sys_es::ffi::EncodedFrameInfo)>,
+}
+
+impl sys_es::EncodedSink for EncodedObserver {
+    fn on_encoded_frame(&self, data: &[u8], info: sys_es::ffi::EncodedFrameInfo) {
+        let _ = self.frame_tx.send((data.to_vec(), info));
+    }
+}
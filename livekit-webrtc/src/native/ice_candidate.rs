@@ -1,8 +1,38 @@
-use crate::ice_candidate as ic;
+use crate::ice_candidate::{self as ic, Candidate, CandidateType, TcpType};
 use crate::session_description::SdpParseError;
 use cxx::SharedPtr;
+use webrtc_sys::candidate as sys_c;
 use webrtc_sys::jsep as sys_jsep;
 
+impl From<sys_c::ffi::CandidateInfo> for Candidate {
+    fn from(value: sys_c::ffi::CandidateInfo) -> Self {
+        Self {
+            foundation: value.foundation,
+            component: value.component,
+            protocol: value.protocol,
+            priority: value.priority,
+            address: value.address,
+            port: value.port,
+            candidate_type: match value.candidate_type {
+                sys_c::ffi::CandidateType::Host => Some(CandidateType::Host),
+                sys_c::ffi::CandidateType::Srflx => Some(CandidateType::Srflx),
+                sys_c::ffi::CandidateType::Prflx => Some(CandidateType::Prflx),
+                sys_c::ffi::CandidateType::Relay => Some(CandidateType::Relay),
+                _ => None,
+            },
+            related_address: value.has_related_address.then_some(value.related_address),
+            related_port: value.has_related_address.then_some(value.related_port),
+            tcp_type: match value.tcp_type {
+                sys_c::ffi::TcpType::None => None,
+                sys_c::ffi::TcpType::Active => Some(TcpType::Active),
+                sys_c::ffi::TcpType::Passive => Some(TcpType::Passive),
+                sys_c::ffi::TcpType::SimultaneousOpen => Some(TcpType::SimultaneousOpen),
+                _ => panic!("unknown TcpType"),
+            },
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct IceCandidate {
     pub(crate) sys_handle: SharedPtr<sys_jsep::ffi::IceCandidate>,
@@ -36,8 +66,8 @@ impl IceCandidate {
         self.sys_handle.sdp_mline_index()
     }
 
-    pub fn candidate(&self) -> String {
-        self.sys_handle.candidate()
+    pub fn candidate(&self) -> Candidate {
+        self.sys_handle.candidate().info().into()
     }
 }
 
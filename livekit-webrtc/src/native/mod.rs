@@ -3,6 +3,7 @@ pub mod audio_source;
 pub mod audio_stream;
 pub mod audio_track;
 pub mod data_channel;
+pub mod encoded_stream;
 pub mod ice_candidate;
 pub mod media_stream;
 pub mod media_stream_track;
@@ -13,6 +14,8 @@ pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
 pub mod session_description;
+pub mod snapshot;
+pub mod video_codec;
 pub mod video_frame;
 pub mod video_source;
 pub mod video_stream;
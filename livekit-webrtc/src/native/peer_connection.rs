@@ -12,10 +12,11 @@ use crate::imp::session_description as imp_sdp;
 use crate::media_stream::MediaStream;
 use crate::media_stream_track::MediaStreamTrack;
 use crate::peer_connection::{
-    AnswerOptions, IceCandidateError, IceConnectionState, IceGatheringState, OfferOptions,
-    OnConnectionChange, OnDataChannel, OnIceCandidate, OnIceCandidateError, OnIceConnectionChange,
-    OnIceGatheringChange, OnNegotiationNeeded, OnSignalingChange, OnTrack, PeerConnectionState,
-    SignalingState, TrackEvent,
+    AnswerOptions, CandidatePair, CandidatePairChangeEvent, IceCandidateError, IceConnectionState,
+    IceGatheringState, OfferOptions, OnConnectionChange, OnDataChannel, OnIceCandidate,
+    OnIceCandidateError, OnIceConnectionChange, OnIceGatheringChange,
+    OnIceSelectedCandidatePairChange, OnNegotiationNeeded, OnSignalingChange, OnTrack,
+    PeerConnectionState, SignalingState, TrackEvent,
 };
 use crate::rtp_receiver::RtpReceiver;
 use crate::rtp_sender::RtpSender;
@@ -436,6 +437,16 @@ impl PeerConnection {
         *self.observer.ice_gathering_change_handler.lock() = f;
     }
 
+    pub fn on_ice_selected_candidate_pair_change(
+        &self,
+        f: Option<OnIceSelectedCandidatePairChange>,
+    ) {
+        *self
+            .observer
+            .ice_selected_candidate_pair_change_handler
+            .lock() = f;
+    }
+
     pub fn on_negotiation_needed(&self, f: Option<OnNegotiationNeeded>) {
         *self.observer.negotiation_needed_handler.lock() = f;
     }
@@ -457,6 +468,7 @@ pub struct PeerObserver {
     pub ice_candidate_error_handler: Mutex<Option<OnIceCandidateError>>,
     pub ice_connection_change_handler: Mutex<Option<OnIceConnectionChange>>,
     pub ice_gathering_change_handler: Mutex<Option<OnIceGatheringChange>>,
+    pub ice_selected_candidate_pair_change_handler: Mutex<Option<OnIceSelectedCandidatePairChange>>,
     pub negotiation_needed_handler: Mutex<Option<OnNegotiationNeeded>>,
     pub signaling_change_handler: Mutex<Option<OnSignalingChange>>,
     pub track_handler: Mutex<Option<OnTrack>>,
@@ -546,10 +558,31 @@ impl sys_pc::PeerConnectionObserver for PeerObserver {
 
     fn on_ice_connection_receiving_change(&self, _receiving: bool) {}
 
-    fn on_ice_selected_candidate_pair_changed(
-        &self,
-        _event: sys_pc::ffi::CandidatePairChangeEvent,
-    ) {
+    fn on_ice_selected_candidate_pair_changed(&self, event: sys_pc::ffi::CandidatePairChangeEvent) {
+        let pair = &event.selected_candidate_pair;
+        if pair.local.is_null() || pair.remote.is_null() {
+            log::warn!(
+                "selected candidate pair changed without candidates: {}",
+                event.reason
+            );
+            return;
+        }
+
+        if let Some(f) = self
+            .ice_selected_candidate_pair_change_handler
+            .lock()
+            .as_mut()
+        {
+            f(CandidatePairChangeEvent {
+                selected_candidate_pair: CandidatePair {
+                    local: pair.local.info().into(),
+                    remote: pair.remote.info().into(),
+                },
+                last_data_received_ms: event.last_data_received_ms,
+                reason: event.reason.clone(),
+                estimated_disconnected_time_ms: event.estimated_disconnected_time_ms,
+            });
+        }
     }
 
     fn on_add_track(
@@ -1,14 +1,19 @@
-use crate::audio_source::native::NativeAudioSource;
+use crate::audio_source::native::{EncodedAudioSource, NativeAudioSource};
 use crate::audio_track::RtcAudioTrack;
 use crate::imp::audio_track as imp_at;
 use crate::imp::peer_connection as imp_pc;
+use crate::imp::video_codec::{
+    new_decoder_factory_wrapper, new_encoder_factory_wrapper, VideoDecoderFactory,
+    VideoEncoderFactory,
+};
 use crate::imp::video_track as imp_vt;
 use crate::peer_connection::PeerConnection;
 use crate::peer_connection_factory::{
-    ContinualGatheringPolicy, IceServer, IceTransportsType, RtcConfiguration,
+    ContinualGatheringPolicy, IceServer, IceTransportsType, NetworkType,
+    PeerConnectionFactoryOptions, RtcConfiguration,
 };
 use crate::rtp_parameters::RtpCapabilities;
-use crate::video_source::native::NativeVideoSource;
+use crate::video_source::native::{EncodedVideoSource, NativeVideoSource};
 use crate::video_track::RtcVideoTrack;
 use crate::MediaType;
 use crate::RtcError;
@@ -17,15 +22,29 @@ use cxx::UniquePtr;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use std::sync::Arc;
+use webrtc_sys::audio_device as sys_ad;
 use webrtc_sys::peer_connection as sys_pc;
 use webrtc_sys::peer_connection_factory as sys_pcf;
 use webrtc_sys::rtc_error as sys_err;
+use webrtc_sys::video_codec as sys_vc;
 use webrtc_sys::webrtc as sys_rtc;
 
 lazy_static! {
     static ref LOG_SINK: Mutex<Option<UniquePtr<sys_rtc::ffi::LogSink>>> = Default::default();
 }
 
+#[derive(Clone)]
+pub struct AudioDeviceModule {
+    sys_handle: SharedPtr<sys_ad::ffi::AudioDeviceModule>,
+}
+
+impl AudioDeviceModule {
+    pub fn platform() -> Option<Self> {
+        let sys_handle = sys_ad::ffi::create_platform_audio_device_module();
+        (!sys_handle.is_null()).then_some(Self { sys_handle })
+    }
+}
+
 #[derive(Clone)]
 pub struct PeerConnectionFactory {
     sys_handle: SharedPtr<sys_pcf::ffi::PeerConnectionFactory>,
@@ -33,6 +52,12 @@ pub struct PeerConnectionFactory {
 
 impl Default for PeerConnectionFactory {
     fn default() -> Self {
+        Self::with_options(PeerConnectionFactoryOptions::default())
+    }
+}
+
+impl PeerConnectionFactory {
+    pub fn with_options(mut options: PeerConnectionFactoryOptions) -> Self {
         let mut log_sink = LOG_SINK.lock();
         if log_sink.is_none() {
             *log_sink = Some(sys_rtc::ffi::new_log_sink(|msg, severity| {
@@ -54,13 +79,19 @@ impl Default for PeerConnectionFactory {
             }));
         }
 
+        let audio_device = options
+            .audio_device
+            .take()
+            .map_or_else(SharedPtr::null, |adm| adm.handle.sys_handle);
+
         Self {
-            sys_handle: sys_pcf::ffi::create_peer_connection_factory(),
+            sys_handle: sys_pcf::ffi::create_peer_connection_factory_with_options(
+                options.into(),
+                audio_device,
+            ),
         }
     }
-}
 
-impl PeerConnectionFactory {
     pub fn create_peer_connection(
         &self,
         config: RtcConfiguration,
@@ -102,6 +133,48 @@ impl PeerConnectionFactory {
         }
     }
 
+    pub fn create_encoded_video_track(
+        &self,
+        label: &str,
+        source: EncodedVideoSource,
+    ) -> RtcVideoTrack {
+        RtcVideoTrack {
+            handle: imp_vt::RtcVideoTrack {
+                sys_handle: self
+                    .sys_handle
+                    .create_video_track(label.to_string(), source.handle.sys_handle()),
+            },
+        }
+    }
+
+    pub fn create_encoded_audio_track(
+        &self,
+        label: &str,
+        source: EncodedAudioSource,
+    ) -> RtcAudioTrack {
+        RtcAudioTrack {
+            handle: imp_at::RtcAudioTrack {
+                sys_handle: self
+                    .sys_handle
+                    .create_audio_track(label.to_string(), source.handle.sys_handle()),
+            },
+        }
+    }
+
+    pub fn register_video_encoder_factory(&self, factory: Box<dyn VideoEncoderFactory>) {
+        sys_vc::ffi::register_video_encoder_factory(
+            &self.sys_handle,
+            new_encoder_factory_wrapper(factory),
+        );
+    }
+
+    pub fn register_video_decoder_factory(&self, factory: Box<dyn VideoDecoderFactory>) {
+        sys_vc::ffi::register_video_decoder_factory(
+            &self.sys_handle,
+            new_decoder_factory_wrapper(factory),
+        );
+    }
+
     pub fn get_rtp_sender_capabilities(&self, media_type: MediaType) -> RtpCapabilities {
         self.sys_handle
             .get_rtp_sender_capabilities(media_type.into())
@@ -156,10 +229,41 @@ impl From<RtcConfiguration> for sys_pcf::ffi::RtcConfiguration {
             ice_servers: value.ice_servers.into_iter().map(Into::into).collect(),
             continual_gathering_policy: value.continual_gathering_policy.into(),
             ice_transport_type: value.ice_transport_type.into(),
+            has_port_range: value.port_range.is_some(),
+            min_port: value.port_range.as_ref().map_or(0, |r| *r.start()),
+            max_port: value.port_range.as_ref().map_or(0, |r| *r.end()),
+            disable_ipv6: value.disable_ipv6,
+            disable_link_local_networks: value.disable_link_local_networks,
+            disable_tcp: value.disable_tcp,
         }
     }
 }
 
+impl From<PeerConnectionFactoryOptions> for sys_pcf::ffi::PeerConnectionFactoryOptions {
+    fn from(value: PeerConnectionFactoryOptions) -> Self {
+        Self {
+            field_trials: value.field_trials,
+            network_threads: value.network_threads,
+            worker_threads: value.worker_threads,
+            network_ignore_mask: value
+                .ignored_network_types
+                .iter()
+                .fold(0, |mask, network_type| mask | network_mask(*network_type)),
+        }
+    }
+}
+
+/// rtc::AdapterType
+fn network_mask(network_type: NetworkType) -> i32 {
+    match network_type {
+        NetworkType::Ethernet => 1 << 0,
+        NetworkType::Wifi => 1 << 1,
+        NetworkType::Cellular => 1 << 2,
+        NetworkType::Vpn => 1 << 3,
+        NetworkType::Loopback => 1 << 4,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
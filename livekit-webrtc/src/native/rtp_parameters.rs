@@ -34,6 +34,7 @@ impl From<sys_rp::ffi::RtpParameters> for RtpParameters {
                 .map(Into::into)
                 .collect(),
             rtcp: value.rtcp.into(),
+            encodings: value.encodings.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -71,6 +72,7 @@ impl From<sys_rp::ffi::RtpEncodingParameters> for RtpEncodingParameters {
             scale_resolution_down_by: value
                 .has_scale_resolution_down_by
                 .then_some(value.scale_resolution_down_by),
+            scalability_mode: value.has_scalability_mode.then_some(value.scalability_mode),
         }
     }
 }
@@ -156,7 +158,7 @@ impl From<RtpParameters> for sys_rp::ffi::RtpParameters {
                 .into_iter()
                 .map(Into::into)
                 .collect(),
-            encodings: Vec::new(),
+            encodings: value.encodings.into_iter().map(Into::into).collect(),
             rtcp: value.rtcp.into(),
             transaction_id: "".to_string(),
             mid: "".to_string(),
@@ -217,8 +219,8 @@ impl From<RtpEncodingParameters> for sys_rp::ffi::RtpEncodingParameters {
             min_bitrate_bps: 0,
             has_num_temporal_layers: false,
             num_temporal_layers: 0,
-            has_scalability_mode: false,
-            scalability_mode: "".to_string(),
+            has_scalability_mode: value.scalability_mode.is_some(),
+            scalability_mode: value.scalability_mode.unwrap_or_default(),
             has_ssrc: false,
             ssrc: 0,
         }
@@ -282,3 +284,15 @@ impl From<RtpCodecCapability> for sys_rp::ffi::RtpCodecCapability {
         }
     }
 }
+
+impl From<RtpHeaderExtensionCapability> for sys_rp::ffi::RtpHeaderExtensionCapability {
+    fn from(value: RtpHeaderExtensionCapability) -> Self {
+        Self {
+            uri: value.uri,
+            has_preferred_id: false,
+            preferred_id: 0,
+            preferred_encrypt: false,
+            direction: value.direction.into(),
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::imp::media_stream_track::new_media_stream_track;
 use crate::media_stream_track::MediaStreamTrack;
 use crate::rtp_parameters::RtpParameters;
+use crate::rtp_receiver::{RtpSource, RtpSourceType};
 use cxx::SharedPtr;
+use std::time::Duration;
 use webrtc_sys::rtp_receiver as sys_rr;
 
 #[derive(Clone)]
@@ -22,4 +24,41 @@ impl RtpReceiver {
     pub fn parameters(&self) -> RtpParameters {
         self.sys_handle.get_parameters().into()
     }
+
+    pub fn set_jitter_buffer_minimum_delay(&self, delay: Option<Duration>) {
+        self.sys_handle.set_jitter_buffer_minimum_delay(
+            delay.is_some(),
+            delay.map(|d| d.as_secs_f64()).unwrap_or_default(),
+        );
+    }
+
+    pub fn sources(&self) -> Vec<RtpSource> {
+        self.sys_handle
+            .get_sources()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+}
+
+impl From<sys_rr::ffi::RtpSourceType> for RtpSourceType {
+    fn from(value: sys_rr::ffi::RtpSourceType) -> Self {
+        match value {
+            sys_rr::ffi::RtpSourceType::Ssrc => Self::Ssrc,
+            sys_rr::ffi::RtpSourceType::Csrc => Self::Csrc,
+            _ => panic!("unknown RtpSourceType"),
+        }
+    }
+}
+
+impl From<sys_rr::ffi::RtpSource> for RtpSource {
+    fn from(value: sys_rr::ffi::RtpSource) -> Self {
+        Self {
+            source_id: value.source_id,
+            source_type: value.source_type.into(),
+            timestamp_ms: value.timestamp_ms,
+            rtp_timestamp: value.rtp_timestamp,
+            audio_level: value.has_audio_level.then_some(value.audio_level),
+        }
+    }
 }
@@ -3,6 +3,7 @@ use crate::media_stream_track::MediaStreamTrack;
 use crate::{rtp_parameters::RtpParameters, RtcError, RtcErrorType};
 use cxx::SharedPtr;
 use webrtc_sys::rtc_error as sys_err;
+use webrtc_sys::rtp_parameters as sys_rp;
 use webrtc_sys::rtp_sender as sys_rs;
 
 #[derive(Clone)]
@@ -38,9 +39,20 @@ impl RtpSender {
         self.sys_handle.get_parameters().into()
     }
 
+    /// libwebrtc only accepts the parameters of the last get_parameters call, the fields
+    /// that aren't exposed (e.g the transaction id) are copied from the current parameters
     pub fn set_parameters(&self, parameters: RtpParameters) -> Result<(), RtcError> {
+        let current = self.sys_handle.get_parameters();
+        let parameters = sys_rp::ffi::RtpParameters {
+            transaction_id: current.transaction_id,
+            mid: current.mid,
+            has_degradation_preference: current.has_degradation_preference,
+            degradation_preference: current.degradation_preference,
+            ..parameters.into()
+        };
+
         self.sys_handle
-            .set_parameters(parameters.into())
+            .set_parameters(parameters)
             .map_err(|e| unsafe { sys_err::ffi::RtcError::from(e.what()).into() })
     }
 }
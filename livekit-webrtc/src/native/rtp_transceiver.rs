@@ -1,6 +1,6 @@
 use crate::imp::rtp_receiver::RtpReceiver;
 use crate::imp::rtp_sender::RtpSender;
-use crate::rtp_parameters::RtpCodecCapability;
+use crate::rtp_parameters::{RtpCodecCapability, RtpHeaderExtensionCapability};
 use crate::rtp_receiver;
 use crate::rtp_sender;
 use crate::rtp_transceiver::RtpTransceiverDirection;
@@ -64,6 +64,12 @@ impl RtpTransceiver {
         self.sys_handle.direction().into()
     }
 
+    pub fn set_direction(&self, direction: RtpTransceiverDirection) -> Result<(), RtcError> {
+        self.sys_handle
+            .set_direction(direction.into())
+            .map_err(|e| unsafe { sys_err::ffi::RtcError::from(e.what()).into() })
+    }
+
     pub fn sender(&self) -> rtp_sender::RtpSender {
         rtp_sender::RtpSender {
             handle: RtpSender {
@@ -86,6 +92,31 @@ impl RtpTransceiver {
             .map_err(|e| unsafe { sys_err::ffi::RtcError::from(e.what()).into() })
     }
 
+    pub fn header_extensions_to_offer(&self) -> Vec<RtpHeaderExtensionCapability> {
+        self.sys_handle
+            .header_extensions_to_offer()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    pub fn header_extensions_negotiated(&self) -> Vec<RtpHeaderExtensionCapability> {
+        self.sys_handle
+            .header_extensions_negotiated()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    pub fn set_header_extensions_to_offer(
+        &self,
+        extensions: Vec<RtpHeaderExtensionCapability>,
+    ) -> Result<(), RtcError> {
+        self.sys_handle
+            .set_offered_rtp_header_extensions(extensions.into_iter().map(Into::into).collect())
+            .map_err(|e| unsafe { sys_err::ffi::RtcError::from(e.what()).into() })
+    }
+
     pub fn stop(&self) -> Result<(), RtcError> {
         self.sys_handle
             .stop_standard()
@@ -0,0 +1,75 @@
+//! Encode video frames to still images (e.g thumbnails)
+
+use super::yuv_helper::{self, ConvertError};
+use crate::video_frame::native::VideoFrameExt;
+use crate::video_frame::{VideoFrame, VideoFrameBuffer};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::{ColorType, ImageEncoder};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg { quality: u8 }, // 1-100
+}
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("failed to convert the frame: {0}")]
+    Convert(#[from] ConvertError),
+    #[error("failed to encode the image: {0}")]
+    Encode(String),
+}
+
+/// Encode the frame to a PNG or a JPEG image.
+/// The pending rotation of the frame is applied.
+pub fn encode_frame<T: AsRef<dyn VideoFrameBuffer>>(
+    frame: &VideoFrame<T>,
+    format: ImageFormat,
+) -> Result<Vec<u8>, SnapshotError> {
+    let frame = frame.to_upright_i420();
+    let buffer = &frame.buffer;
+    let (width, height) = (buffer.width(), buffer.height());
+
+    // ABGR is RGBA in memory
+    let mut rgba = vec![0u8; (width * height * 4) as usize];
+    let (stride_y, stride_u, stride_v) = buffer.strides();
+    let (data_y, data_u, data_v) = buffer.data();
+    yuv_helper::i420_to_abgr(
+        data_y,
+        stride_y,
+        data_u,
+        stride_u,
+        data_v,
+        stride_v,
+        &mut rgba,
+        width * 4,
+        width as i32,
+        height as i32,
+    )?;
+
+    let mut data = Vec::new();
+    let res = match format {
+        ImageFormat::Png => {
+            PngEncoder::new(&mut data).write_image(&rgba, width, height, ColorType::Rgba8)
+        }
+        ImageFormat::Jpeg { quality } => {
+            // JPEG doesn't support the alpha channel
+            let rgb: Vec<u8> = rgba
+                .chunks_exact(4)
+                .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+                .collect();
+
+            JpegEncoder::new_with_quality(&mut data, quality.clamp(1, 100)).write_image(
+                &rgb,
+                width,
+                height,
+                ColorType::Rgb8,
+            )
+        }
+    };
+
+    res.map_err(|err| SnapshotError::Encode(err.to_string()))?;
+    Ok(data)
+}
@@ -0,0 +1,578 @@
+//! Register video encoders/decoders implemented in Rust into the PeerConnectionFactory.
+//! Registered factories take precedence over the builtin ones when they support the same codec.
+
+use super::video_frame::new_video_frame_buffer;
+use crate::video_frame::{BoxVideoFrame, VideoFrame, VideoFrameBuffer};
+use std::collections::HashMap;
+use thiserror::Error;
+use webrtc_sys::video_codec as sys_vc;
+use webrtc_sys::video_frame as vf_sys;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdpVideoFormat {
+    pub name: String, // e.g "AV1", "H264"
+    pub parameters: HashMap<String, String>,
+}
+
+impl SdpVideoFormat {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            parameters: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VideoEncoderSettings {
+    pub width: u16,
+    pub height: u16,
+    pub start_bitrate: u32, // kbps
+    pub max_bitrate: u32,   // kbps
+    pub min_bitrate: u32,   // kbps
+    pub max_framerate: u32,
+    pub number_of_cores: i32,
+    pub max_payload_size: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct VideoDecoderSettings {
+    pub max_width: i32,
+    pub max_height: i32,
+    pub number_of_cores: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EncodedImageInfo {
+    pub rtp_timestamp: u32,
+    pub capture_time_ms: i64,
+    pub keyframe: bool,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum VideoCodecError {
+    #[error("codec error")]
+    Error,
+    #[error("out of memory")]
+    Memory,
+    #[error("invalid parameter")]
+    Parameter,
+    #[error("codec not initialized")]
+    Uninitialized,
+    #[error("fallback to the software codec requested")]
+    FallbackSoftware,
+}
+
+pub type VideoCodecResult = Result<(), VideoCodecError>;
+
+pub trait VideoEncoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<SdpVideoFormat>;
+
+    /// Only called with a format returned by supported_formats
+    fn create_encoder(&self, format: &SdpVideoFormat) -> Box<dyn VideoEncoder>;
+}
+
+pub trait VideoEncoder: Send {
+    fn init_encode(&mut self, settings: &VideoEncoderSettings) -> VideoCodecResult;
+
+    /// The encoded images must be forwarded to the sink before returning, with the rtp_timestamp
+    /// of the source frame
+    fn encode(
+        &mut self,
+        frame: &BoxVideoFrame,
+        rtp_timestamp: u32,
+        keyframe: bool,
+        sink: &EncodedImageSink,
+    ) -> VideoCodecResult;
+
+    fn set_rates(&mut self, bitrate_bps: u32, framerate: f64);
+
+    fn release(&mut self) -> VideoCodecResult {
+        Ok(())
+    }
+}
+
+pub trait VideoDecoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<SdpVideoFormat>;
+
+    /// Only called with a format returned by supported_formats
+    fn create_decoder(&self, format: &SdpVideoFormat) -> Box<dyn VideoDecoder>;
+}
+
+pub trait VideoDecoder: Send {
+    fn configure(&mut self, settings: &VideoDecoderSettings) -> bool;
+
+    fn decode(
+        &mut self,
+        data: &[u8],
+        info: &EncodedImageInfo,
+        sink: &DecodedImageSink,
+    ) -> VideoCodecResult;
+
+    fn release(&mut self) -> VideoCodecResult {
+        Ok(())
+    }
+}
+
+pub struct EncodedImageSink<'a> {
+    sys_handle: &'a sys_vc::ffi::EncodedImageSink,
+}
+
+impl<'a> EncodedImageSink<'a> {
+    /// Returns false if libwebrtc failed to packetize the image
+    pub fn on_encoded_image(&self, data: &[u8], info: &EncodedImageInfo) -> bool {
+        self.sys_handle.on_encoded_image(data, info.clone().into())
+    }
+}
+
+pub struct DecodedImageSink<'a> {
+    sys_handle: &'a sys_vc::ffi::DecodedImageSink,
+}
+
+impl<'a> DecodedImageSink<'a> {
+    /// rtp_timestamp must be the one of the decoded EncodedImageInfo
+    pub fn on_decoded_frame<T: AsRef<dyn VideoFrameBuffer>>(
+        &self,
+        frame: &VideoFrame<T>,
+        rtp_timestamp: u32,
+    ) {
+        let mut builder = vf_sys::ffi::new_video_frame_builder();
+        builder.pin_mut().set_rotation(frame.rotation.into());
+        builder.pin_mut().set_timestamp_us(frame.timestamp);
+        builder
+            .pin_mut()
+            .set_video_frame_buffer(frame.buffer.as_ref().sys_handle());
+        self.sys_handle
+            .on_decoded_frame(builder.pin_mut().build(), rtp_timestamp);
+    }
+}
+
+pub(crate) fn new_encoder_factory_wrapper(
+    factory: Box<dyn VideoEncoderFactory>,
+) -> Box<sys_vc::VideoEncoderFactoryWrapper> {
+    Box::new(sys_vc::VideoEncoderFactoryWrapper::new(Box::new(
+        EncoderFactoryAdapter { factory },
+    )))
+}
+
+pub(crate) fn new_decoder_factory_wrapper(
+    factory: Box<dyn VideoDecoderFactory>,
+) -> Box<sys_vc::VideoDecoderFactoryWrapper> {
+    Box::new(sys_vc::VideoDecoderFactoryWrapper::new(Box::new(
+        DecoderFactoryAdapter { factory },
+    )))
+}
+
+struct EncoderFactoryAdapter {
+    factory: Box<dyn VideoEncoderFactory>,
+}
+
+impl sys_vc::VideoEncoderFactory for EncoderFactoryAdapter {
+    fn supported_formats(&self) -> Vec<sys_vc::ffi::SdpVideoFormat> {
+        self.factory
+            .supported_formats()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    fn create_encoder(
+        &self,
+        format: &sys_vc::ffi::SdpVideoFormat,
+    ) -> Box<dyn sys_vc::VideoEncoder> {
+        Box::new(EncoderAdapter {
+            encoder: self.factory.create_encoder(&format.clone().into()),
+        })
+    }
+}
+
+struct EncoderAdapter {
+    encoder: Box<dyn VideoEncoder>,
+}
+
+impl sys_vc::VideoEncoder for EncoderAdapter {
+    fn init_encode(&mut self, settings: &sys_vc::ffi::VideoEncoderSettings) -> i32 {
+        to_codec_status(self.encoder.init_encode(&settings.clone().into()))
+    }
+
+    fn encode(
+        &mut self,
+        frame: &vf_sys::ffi::VideoFrame,
+        keyframe: bool,
+        sink: &sys_vc::ffi::EncodedImageSink,
+    ) -> i32 {
+        let rust_frame = VideoFrame {
+            rotation: frame.rotation().into(),
+            timestamp: frame.timestamp_us(),
+            rtp_timestamp: Some(frame.timestamp()),
+            ntp_time_ms: None,
+            receive_time_us: None,
+            buffer: new_video_frame_buffer(unsafe { frame.video_frame_buffer() }),
+        };
+
+        to_codec_status(self.encoder.encode(
+            &rust_frame,
+            frame.timestamp(),
+            keyframe,
+            &EncodedImageSink { sys_handle: sink },
+        ))
+    }
+
+    fn set_rates(&mut self, bitrate_bps: u32, framerate: f64) {
+        self.encoder.set_rates(bitrate_bps, framerate)
+    }
+
+    fn release(&mut self) -> i32 {
+        to_codec_status(self.encoder.release())
+    }
+}
+
+struct DecoderFactoryAdapter {
+    factory: Box<dyn VideoDecoderFactory>,
+}
+
+impl sys_vc::VideoDecoderFactory for DecoderFactoryAdapter {
+    fn supported_formats(&self) -> Vec<sys_vc::ffi::SdpVideoFormat> {
+        self.factory
+            .supported_formats()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    fn create_decoder(
+        &self,
+        format: &sys_vc::ffi::SdpVideoFormat,
+    ) -> Box<dyn sys_vc::VideoDecoder> {
+        Box::new(DecoderAdapter {
+            decoder: self.factory.create_decoder(&format.clone().into()),
+        })
+    }
+}
+
+struct DecoderAdapter {
+    decoder: Box<dyn VideoDecoder>,
+}
+
+impl sys_vc::VideoDecoder for DecoderAdapter {
+    fn configure(&mut self, settings: &sys_vc::ffi::VideoDecoderSettings) -> bool {
+        self.decoder.configure(&settings.clone().into())
+    }
+
+    fn decode(
+        &mut self,
+        data: &[u8],
+        info: sys_vc::ffi::EncodedImageInfo,
+        sink: &sys_vc::ffi::DecodedImageSink,
+    ) -> i32 {
+        to_codec_status(self.decoder.decode(
+            data,
+            &info.into(),
+            &DecodedImageSink { sys_handle: sink },
+        ))
+    }
+
+    fn release(&mut self) -> i32 {
+        to_codec_status(self.decoder.release())
+    }
+}
+
+fn to_codec_status(result: VideoCodecResult) -> i32 {
+    match result {
+        Ok(()) => sys_vc::VIDEO_CODEC_OK,
+        Err(VideoCodecError::Error) => sys_vc::VIDEO_CODEC_ERROR,
+        Err(VideoCodecError::Memory) => sys_vc::VIDEO_CODEC_MEMORY,
+        Err(VideoCodecError::Parameter) => sys_vc::VIDEO_CODEC_ERR_PARAMETER,
+        Err(VideoCodecError::Uninitialized) => sys_vc::VIDEO_CODEC_UNINITIALIZED,
+        Err(VideoCodecError::FallbackSoftware) => sys_vc::VIDEO_CODEC_FALLBACK_SOFTWARE,
+    }
+}
+
+impl From<SdpVideoFormat> for sys_vc::ffi::SdpVideoFormat {
+    fn from(value: SdpVideoFormat) -> Self {
+        Self {
+            name: value.name,
+            parameters: value
+                .parameters
+                .into_iter()
+                .map(|(key, value)| sys_vc::ffi::SdpVideoFormatParameter { key, value })
+                .collect(),
+        }
+    }
+}
+
+impl From<sys_vc::ffi::SdpVideoFormat> for SdpVideoFormat {
+    fn from(value: sys_vc::ffi::SdpVideoFormat) -> Self {
+        Self {
+            name: value.name,
+            parameters: value
+                .parameters
+                .into_iter()
+                .map(|param| (param.key, param.value))
+                .collect(),
+        }
+    }
+}
+
+impl From<sys_vc::ffi::VideoEncoderSettings> for VideoEncoderSettings {
+    fn from(value: sys_vc::ffi::VideoEncoderSettings) -> Self {
+        Self {
+            width: value.width,
+            height: value.height,
+            start_bitrate: value.start_bitrate,
+            max_bitrate: value.max_bitrate,
+            min_bitrate: value.min_bitrate,
+            max_framerate: value.max_framerate,
+            number_of_cores: value.number_of_cores,
+            max_payload_size: value.max_payload_size,
+        }
+    }
+}
+
+impl From<sys_vc::ffi::VideoDecoderSettings> for VideoDecoderSettings {
+    fn from(value: sys_vc::ffi::VideoDecoderSettings) -> Self {
+        Self {
+            max_width: value.max_width,
+            max_height: value.max_height,
+            number_of_cores: value.number_of_cores,
+        }
+    }
+}
+
+impl From<sys_vc::ffi::EncodedImageInfo> for EncodedImageInfo {
+    fn from(value: sys_vc::ffi::EncodedImageInfo) -> Self {
+        Self {
+            rtp_timestamp: value.rtp_timestamp,
+            capture_time_ms: value.capture_time_ms,
+            keyframe: value.keyframe,
+            width: value.width,
+            height: value.height,
+        }
+    }
+}
+
+impl From<EncodedImageInfo> for sys_vc::ffi::EncodedImageInfo {
+    fn from(value: EncodedImageInfo) -> Self {
+        Self {
+            rtp_timestamp: value.rtp_timestamp,
+            capture_time_ms: value.capture_time_ms,
+            keyframe: value.keyframe,
+            width: value.width,
+            height: value.height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peer_connection::*;
+    use crate::peer_connection_factory::native::PeerConnectionFactoryExt;
+    use crate::peer_connection_factory::*;
+    use crate::rtp_transceiver::{RtpTransceiverDirection, RtpTransceiverInit};
+    use crate::video_frame::native::I420BufferExt;
+    use crate::video_frame::{I420Buffer, VideoRotation};
+    use crate::video_source::native::NativeVideoSource;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    /// Keyframe header of a 320x240 VP8 frame, followed by an opaque payload
+    const VP8_KEYFRAME: &[u8] = &[
+        0x10, 0x02, 0x00, 0x9d, 0x01, 0x2a, 0x40, 0x01, 0xf0, 0x00, 0xde, 0xad, 0xbe, 0xef,
+    ];
+
+    /// The VP9 payload is opaque to the packetizer, the layers are described by the
+    /// codec specific info
+    const VP9_KEYFRAME: &[u8] = &[0x82, 0x49, 0x83, 0x42, 0x00, 0x13, 0xf0, 0x0e, 0xde, 0xad];
+
+    struct PassthroughFactory {
+        codec: &'static str,
+        payload: &'static [u8],
+        decoded_tx: mpsc::UnboundedSender<Vec<u8>>,
+    }
+
+    struct PassthroughEncoder {
+        payload: &'static [u8],
+    }
+
+    struct PassthroughDecoder {
+        decoded_tx: mpsc::UnboundedSender<Vec<u8>>,
+    }
+
+    impl VideoEncoderFactory for PassthroughFactory {
+        fn supported_formats(&self) -> Vec<SdpVideoFormat> {
+            vec![SdpVideoFormat::new(self.codec)]
+        }
+
+        fn create_encoder(&self, _format: &SdpVideoFormat) -> Box<dyn VideoEncoder> {
+            Box::new(PassthroughEncoder {
+                payload: self.payload,
+            })
+        }
+    }
+
+    impl VideoDecoderFactory for PassthroughFactory {
+        fn supported_formats(&self) -> Vec<SdpVideoFormat> {
+            vec![SdpVideoFormat::new(self.codec)]
+        }
+
+        fn create_decoder(&self, _format: &SdpVideoFormat) -> Box<dyn VideoDecoder> {
+            Box::new(PassthroughDecoder {
+                decoded_tx: self.decoded_tx.clone(),
+            })
+        }
+    }
+
+    impl VideoEncoder for PassthroughEncoder {
+        fn init_encode(&mut self, _settings: &VideoEncoderSettings) -> VideoCodecResult {
+            Ok(())
+        }
+
+        fn encode(
+            &mut self,
+            _frame: &BoxVideoFrame,
+            rtp_timestamp: u32,
+            _keyframe: bool,
+            sink: &EncodedImageSink,
+        ) -> VideoCodecResult {
+            let info = EncodedImageInfo {
+                rtp_timestamp,
+                keyframe: true,
+                width: 320,
+                height: 240,
+                ..Default::default()
+            };
+
+            match sink.on_encoded_image(self.payload, &info) {
+                true => Ok(()),
+                false => Err(VideoCodecError::Error),
+            }
+        }
+
+        fn set_rates(&mut self, _bitrate_bps: u32, _framerate: f64) {}
+    }
+
+    impl VideoDecoder for PassthroughDecoder {
+        fn configure(&mut self, _settings: &VideoDecoderSettings) -> bool {
+            true
+        }
+
+        fn decode(
+            &mut self,
+            data: &[u8],
+            _info: &EncodedImageInfo,
+            _sink: &DecodedImageSink,
+        ) -> VideoCodecResult {
+            let _ = self.decoded_tx.send(data.to_vec());
+            Ok(())
+        }
+    }
+
+    /// The images of a Rust encoder must be packetized and depacketized unchanged
+    async fn passthrough_roundtrip(codec: &'static str, payload: &'static [u8]) {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let (decoded_tx, mut decoded_rx) = mpsc::unbounded_channel();
+        let factory = PeerConnectionFactory::default();
+        let codec_factory = || PassthroughFactory {
+            codec,
+            payload,
+            decoded_tx: decoded_tx.clone(),
+        };
+        factory.register_video_encoder_factory(Box::new(codec_factory()));
+        factory.register_video_decoder_factory(Box::new(codec_factory()));
+
+        let bob = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+        let alice = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+
+        let (bob_ice_tx, mut bob_ice_rx) = mpsc::unbounded_channel::<IceCandidate>();
+        let (alice_ice_tx, mut alice_ice_rx) = mpsc::unbounded_channel::<IceCandidate>();
+        bob.on_ice_candidate(Some(Box::new(move |candidate| {
+            let _ = bob_ice_tx.send(candidate);
+        })));
+        alice.on_ice_candidate(Some(Box::new(move |candidate| {
+            let _ = alice_ice_tx.send(candidate);
+        })));
+
+        let source = NativeVideoSource::default();
+        let track = factory.create_video_track("passthrough", source.clone());
+        let transceiver = bob
+            .add_transceiver(
+                track.into(),
+                RtpTransceiverInit {
+                    direction: RtpTransceiverDirection::SendOnly,
+                    stream_ids: vec!["stream".to_owned()],
+                    send_encodings: Vec::new(),
+                },
+            )
+            .unwrap();
+
+        let capabilities = factory.get_rtp_sender_capabilities(crate::MediaType::Video);
+        let codecs = capabilities
+            .codecs
+            .into_iter()
+            .filter(|c| {
+                c.mime_type
+                    .eq_ignore_ascii_case(&format!("video/{}", codec))
+            })
+            .collect::<Vec<_>>();
+        assert!(!codecs.is_empty(), "{} isn't offered", codec);
+        transceiver.set_codec_preferences(codecs).unwrap();
+
+        let offer = bob.create_offer(OfferOptions::default()).await.unwrap();
+        bob.set_local_description(offer.clone()).await.unwrap();
+        alice.set_remote_description(offer).await.unwrap();
+        let answer = alice.create_answer(AnswerOptions::default()).await.unwrap();
+        alice.set_local_description(answer.clone()).await.unwrap();
+        bob.set_remote_description(answer).await.unwrap();
+
+        bob.add_ice_candidate(alice_ice_rx.recv().await.unwrap())
+            .await
+            .unwrap();
+        alice
+            .add_ice_candidate(bob_ice_rx.recv().await.unwrap())
+            .await
+            .unwrap();
+
+        let capture = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(33));
+            loop {
+                interval.tick().await;
+                source.capture_frame(&VideoFrame {
+                    rotation: VideoRotation::VideoRotation0,
+                    timestamp: 0,
+                    rtp_timestamp: None,
+                    ntp_time_ms: None,
+                    receive_time_us: None,
+                    buffer: I420Buffer::new(320, 240),
+                });
+            }
+        });
+
+        let decoded = tokio::time::timeout(Duration::from_secs(10), decoded_rx.recv())
+            .await
+            .expect("no frame received")
+            .unwrap();
+        assert_eq!(decoded, payload);
+
+        capture.abort();
+        alice.close();
+        bob.close();
+    }
+
+    #[tokio::test]
+    async fn vp8_passthrough() {
+        passthrough_roundtrip("VP8", VP8_KEYFRAME).await;
+    }
+
+    #[tokio::test]
+    async fn vp9_passthrough() {
+        passthrough_roundtrip("VP9", VP9_KEYFRAME).await;
+    }
+}
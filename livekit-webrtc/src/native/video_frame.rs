@@ -1,7 +1,8 @@
 use super::yuv_helper::{self, ConvertError};
+use crate::video_frame::native::ScaleFilter;
 use crate::video_frame::VideoRotation;
 use crate::video_frame::{self as vf, VideoFormatType};
-use cxx::UniquePtr;
+use cxx::{SharedPtr, UniquePtr};
 use std::slice;
 use webrtc_sys::video_frame as vf_sys;
 use webrtc_sys::video_frame_buffer as vfb_sys;
@@ -76,6 +77,17 @@ impl From<VideoRotation> for vf_sys::ffi::VideoRotation {
     }
 }
 
+impl From<ScaleFilter> for vfb_sys::ffi::ScaleFilter {
+    fn from(filter: ScaleFilter) -> Self {
+        match filter {
+            ScaleFilter::None => Self::None,
+            ScaleFilter::Linear => Self::Linear,
+            ScaleFilter::Bilinear => Self::Bilinear,
+            ScaleFilter::Box => Self::Box,
+        }
+    }
+}
+
 macro_rules! recursive_cast {
     ($ptr:expr $(, $fnc:ident)*) => {
         {
@@ -274,6 +286,48 @@ impl I420Buffer {
         )
     }
 
+    pub fn scale(&self, width: u32, height: u32, filter: ScaleFilter) -> Option<vf::I420Buffer> {
+        let (width, height) = checked_size(width, height)?;
+        Some(vf::I420Buffer {
+            handle: I420Buffer {
+                sys_handle: self.sys_handle.scale(width, height, filter.into()),
+            },
+        })
+    }
+
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Option<vf::I420Buffer> {
+        // The chroma planes are subsampled, the origin must be even
+        let (x, y) = (x & !1, y & !1);
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+
+        let width = width.min(self.width() - x);
+        let height = height.min(self.height() - y);
+        let (width, height) = checked_size(width, height)?;
+        Some(vf::I420Buffer {
+            handle: I420Buffer {
+                sys_handle: self.sys_handle.crop(x as i32, y as i32, width, height),
+            },
+        })
+    }
+
+    pub fn rotate(&self, rotation: VideoRotation) -> vf::I420Buffer {
+        vf::I420Buffer {
+            handle: I420Buffer {
+                sys_handle: self.sys_handle.rotate(rotation as i32),
+            },
+        }
+    }
+
+    pub fn mirror(&self) -> vf::I420Buffer {
+        vf::I420Buffer {
+            handle: I420Buffer {
+                sys_handle: self.sys_handle.mirror(),
+            },
+        }
+    }
+
     pub fn data(&self) -> (&[u8], &[u8], &[u8]) {
         unsafe {
             let ptr = recursive_cast!(&*self.sys_handle, i420_to_yuv8);
@@ -288,6 +342,17 @@ impl I420Buffer {
 }
 
 impl I420ABuffer {
+    /// The alpha plane is allocated and fully opaque
+    pub fn new(width: u32, height: u32) -> Result<vf::I420ABuffer, ConvertError> {
+        let (width, height) =
+            checked_size(width, height).ok_or(ConvertError::Convert("invalid buffer size"))?;
+        Ok(vf::I420ABuffer {
+            handle: I420ABuffer {
+                sys_handle: vfb_sys::ffi::new_i420a_buffer(width, height),
+            },
+        })
+    }
+
     pub fn sys_handle(&self) -> &vfb_sys::ffi::VideoFrameBuffer {
         unsafe { &*recursive_cast!(&*self.sys_handle, i420a_to_yuv8, yuv8_to_yuv, yuv_to_vfb) }
     }
@@ -387,6 +452,16 @@ impl I420ABuffer {
 }
 
 impl I422Buffer {
+    pub fn new(width: u32, height: u32) -> Result<vf::I422Buffer, ConvertError> {
+        let (width, height) =
+            checked_size(width, height).ok_or(ConvertError::Convert("invalid buffer size"))?;
+        Ok(vf::I422Buffer {
+            handle: I422Buffer {
+                sys_handle: vfb_sys::ffi::new_i422_buffer(width, height),
+            },
+        })
+    }
+
     pub fn sys_handle(&self) -> &vfb_sys::ffi::VideoFrameBuffer {
         unsafe { &*recursive_cast!(&*self.sys_handle, i422_to_yuv8, yuv8_to_yuv, yuv_to_vfb) }
     }
@@ -457,8 +532,30 @@ impl I422Buffer {
         dst_width: i32,
         dst_height: i32,
     ) -> Result<(), ConvertError> {
-        self.to_i420()
-            .to_argb(format, dst, dst_stride, dst_width, dst_height)
+        let convert = match format {
+            VideoFormatType::ARGB => yuv_helper::i422_to_argb,
+            VideoFormatType::ABGR => yuv_helper::i422_to_abgr,
+            _ => {
+                // libyuv only converts the full chroma formats to ARGB and ABGR
+                return self
+                    .to_i420()
+                    .to_argb(format, dst, dst_stride, dst_width, dst_height);
+            }
+        };
+
+        let (data_y, data_u, data_v) = self.data();
+        convert(
+            data_y,
+            self.stride_y(),
+            data_u,
+            self.stride_u(),
+            data_v,
+            self.stride_v(),
+            dst,
+            dst_stride,
+            dst_width,
+            dst_height,
+        )
     }
 
     pub fn data(&self) -> (&[u8], &[u8], &[u8]) {
@@ -473,6 +570,16 @@ impl I422Buffer {
     }
 }
 impl I444Buffer {
+    pub fn new(width: u32, height: u32) -> Result<vf::I444Buffer, ConvertError> {
+        let (width, height) =
+            checked_size(width, height).ok_or(ConvertError::Convert("invalid buffer size"))?;
+        Ok(vf::I444Buffer {
+            handle: I444Buffer {
+                sys_handle: vfb_sys::ffi::new_i444_buffer(width, height),
+            },
+        })
+    }
+
     pub fn sys_handle(&self) -> &vfb_sys::ffi::VideoFrameBuffer {
         unsafe { &*recursive_cast!(&*self.sys_handle, i444_to_yuv8, yuv8_to_yuv, yuv_to_vfb) }
     }
@@ -543,8 +650,30 @@ impl I444Buffer {
         dst_width: i32,
         dst_height: i32,
     ) -> Result<(), ConvertError> {
-        self.to_i420()
-            .to_argb(format, dst, dst_stride, dst_width, dst_height)
+        let convert = match format {
+            VideoFormatType::ARGB => yuv_helper::i444_to_argb,
+            VideoFormatType::ABGR => yuv_helper::i444_to_abgr,
+            _ => {
+                // libyuv only converts the full chroma formats to ARGB and ABGR
+                return self
+                    .to_i420()
+                    .to_argb(format, dst, dst_stride, dst_width, dst_height);
+            }
+        };
+
+        let (data_y, data_u, data_v) = self.data();
+        convert(
+            data_y,
+            self.stride_y(),
+            data_u,
+            self.stride_u(),
+            data_v,
+            self.stride_v(),
+            dst,
+            dst_stride,
+            dst_width,
+            dst_height,
+        )
     }
 
     pub fn data(&self) -> (&[u8], &[u8], &[u8]) {
@@ -560,6 +689,16 @@ impl I444Buffer {
 }
 
 impl I010Buffer {
+    pub fn new(width: u32, height: u32) -> Result<vf::I010Buffer, ConvertError> {
+        let (width, height) =
+            checked_size(width, height).ok_or(ConvertError::Convert("invalid buffer size"))?;
+        Ok(vf::I010Buffer {
+            handle: I010Buffer {
+                sys_handle: vfb_sys::ffi::new_i010_buffer(width, height),
+            },
+        })
+    }
+
     pub fn sys_handle(&self) -> &vfb_sys::ffi::VideoFrameBuffer {
         unsafe { &*recursive_cast!(&*self.sys_handle, i010_to_yuv16b, yuv16b_to_yuv, yuv_to_vfb) }
     }
@@ -658,6 +797,16 @@ impl I010Buffer {
 }
 
 impl NV12Buffer {
+    pub fn new(width: u32, height: u32) -> Result<vf::NV12Buffer, ConvertError> {
+        let (width, height) =
+            checked_size(width, height).ok_or(ConvertError::Convert("invalid buffer size"))?;
+        Ok(vf::NV12Buffer {
+            handle: NV12Buffer {
+                sys_handle: vfb_sys::ffi::new_nv12_buffer(width, height),
+            },
+        })
+    }
+
     pub fn sys_handle(&self) -> &vfb_sys::ffi::VideoFrameBuffer {
         unsafe {
             &*recursive_cast!(
@@ -762,3 +911,106 @@ impl NV12Buffer {
         }
     }
 }
+
+macro_rules! impl_buffer_pool {
+    ($pool:ident, $type:ident, $new:ident) => {
+        #[derive(Clone)]
+        pub struct $pool {
+            sys_handle: SharedPtr<vfb_sys::ffi::$pool>,
+        }
+
+        impl $pool {
+            pub fn new(max_number_of_buffers: usize) -> Self {
+                Self {
+                    sys_handle: vfb_sys::ffi::$new(max_number_of_buffers),
+                }
+            }
+
+            pub fn create(&self, width: u32, height: u32) -> Option<vf::$type> {
+                let sys_handle = self
+                    .sys_handle
+                    .create(width.try_into().unwrap(), height.try_into().unwrap());
+
+                (!sys_handle.is_null()).then(|| vf::$type {
+                    handle: $type { sys_handle },
+                })
+            }
+
+            pub fn release(&self) {
+                self.sys_handle.release()
+            }
+
+            pub fn resize(&self, max_number_of_buffers: usize) -> bool {
+                self.sys_handle.resize(max_number_of_buffers)
+            }
+        }
+    };
+}
+
+impl_buffer_pool!(I420BufferPool, I420Buffer, new_i420_buffer_pool);
+impl_buffer_pool!(I422BufferPool, I422Buffer, new_i422_buffer_pool);
+impl_buffer_pool!(I444BufferPool, I444Buffer, new_i444_buffer_pool);
+impl_buffer_pool!(I010BufferPool, I010Buffer, new_i010_buffer_pool);
+impl_buffer_pool!(NV12BufferPool, NV12Buffer, new_nv12_buffer_pool);
+
+/// libwebrtc aborts when allocating an empty buffer or when the size overflows
+fn checked_size(width: u32, height: u32) -> Option<(i32, i32)> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    i32::try_from(u64::from(width) * u64::from(height)).ok()?;
+    Some((width as i32, height as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::video_frame::native::{I420ABufferExt, I420BufferExt, NV12BufferExt, ScaleFilter};
+    use crate::video_frame::{I420ABuffer, I420Buffer, NV12Buffer, VideoFrameBuffer};
+
+    #[test]
+    fn scale_rejects_invalid_sizes() {
+        let buffer = I420Buffer::new(64, 48);
+
+        let scaled = buffer.scale(32, 24, ScaleFilter::Box).unwrap();
+        assert_eq!((scaled.width(), scaled.height()), (32, 24));
+
+        assert!(buffer.scale(0, 24, ScaleFilter::Box).is_none());
+        assert!(buffer.scale(32, 0, ScaleFilter::Box).is_none());
+        assert!(buffer.scale(u32::MAX, 24, ScaleFilter::Box).is_none());
+        assert!(buffer.scale(65536, 65536, ScaleFilter::Box).is_none());
+    }
+
+    #[test]
+    fn crop_clamps_to_bounds() {
+        let buffer = I420Buffer::new(64, 48);
+
+        let cropped = buffer.crop(15, 9, 100, 100).unwrap();
+        assert_eq!((cropped.width(), cropped.height()), (50, 40));
+
+        let cropped = buffer.crop(0, 0, 16, 16).unwrap();
+        assert_eq!((cropped.width(), cropped.height()), (16, 16));
+
+        assert!(buffer.crop(64, 0, 16, 16).is_none());
+        assert!(buffer.crop(0, 48, 16, 16).is_none());
+        assert!(buffer.crop(u32::MAX, 0, 16, 16).is_none());
+        assert!(buffer.crop(0, 0, 0, 16).is_none());
+    }
+
+    #[test]
+    fn new_rejects_invalid_sizes() {
+        let buffer = NV12Buffer::new(64, 48).unwrap();
+        assert_eq!((buffer.width(), buffer.height()), (64, 48));
+
+        assert!(NV12Buffer::new(0, 48).is_err());
+        assert!(NV12Buffer::new(u32::MAX, 48).is_err());
+        assert!(I420ABuffer::new(65536, 65536).is_err());
+    }
+
+    #[test]
+    fn i420a_is_opaque() {
+        let buffer = I420ABuffer::new(16, 8).unwrap();
+        let (_, _, _, data_a) = buffer.data();
+        assert!(data_a.unwrap().iter().all(|alpha| *alpha == 255));
+    }
+}
@@ -1,17 +1,26 @@
 use crate::video_frame::{VideoFrame, VideoFrameBuffer};
+use crate::video_source::native::{
+    EncodedVideoCodec, EncodedVideoFrameInfo, OnKeyframeRequest, VideoSinkWants,
+};
 use cxx::SharedPtr;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use tokio::sync::watch;
 use webrtc_sys::video_frame as vf_sys;
 use webrtc_sys::video_track as vt_sys;
 
 #[derive(Clone)]
 pub struct NativeVideoSource {
     sys_handle: SharedPtr<vt_sys::ffi::VideoTrackSource>,
+    sink_wants_rx: watch::Receiver<VideoSinkWants>,
 }
 
 impl Default for NativeVideoSource {
     fn default() -> Self {
+        let (sys_handle, sink_wants_rx, _) = new_video_track_source();
         Self {
-            sys_handle: vt_sys::ffi::new_video_track_source(),
+            sys_handle,
+            sink_wants_rx,
         }
     }
 }
@@ -21,9 +30,24 @@ impl NativeVideoSource {
         self.sys_handle.clone()
     }
 
+    pub fn sink_wants(&self) -> VideoSinkWants {
+        self.sink_wants_rx.borrow().clone()
+    }
+
+    pub fn subscribe_sink_wants(&self) -> watch::Receiver<VideoSinkWants> {
+        self.sink_wants_rx.clone()
+    }
+
     pub fn capture_frame<T: AsRef<dyn VideoFrameBuffer>>(&self, frame: &VideoFrame<T>) {
         let mut builder = vf_sys::ffi::new_video_frame_builder();
         builder.pin_mut().set_rotation(frame.rotation.into());
+        builder.pin_mut().set_timestamp_us(frame.timestamp);
+        if let Some(rtp_timestamp) = frame.rtp_timestamp {
+            builder.pin_mut().set_rtp_timestamp(rtp_timestamp);
+        }
+        if let Some(ntp_time_ms) = frame.ntp_time_ms {
+            builder.pin_mut().set_ntp_time_ms(ntp_time_ms);
+        }
         builder
             .pin_mut()
             .set_video_frame_buffer(frame.buffer.as_ref().sys_handle());
@@ -31,3 +55,108 @@ impl NativeVideoSource {
             .on_captured_frame(&builder.pin_mut().build());
     }
 }
+
+#[derive(Clone)]
+pub struct EncodedVideoSource {
+    sys_handle: SharedPtr<vt_sys::ffi::VideoTrackSource>,
+    on_keyframe_request: Arc<Mutex<Option<KeyframeRequestHandler>>>,
+}
+
+impl Default for EncodedVideoSource {
+    fn default() -> Self {
+        // Encoded frames can't be adapted, the sink wants are ignored
+        let (sys_handle, _, on_keyframe_request) = new_video_track_source();
+        Self {
+            sys_handle,
+            on_keyframe_request,
+        }
+    }
+}
+
+impl EncodedVideoSource {
+    pub fn sys_handle(&self) -> SharedPtr<vt_sys::ffi::VideoTrackSource> {
+        self.sys_handle.clone()
+    }
+
+    pub fn capture_frame(&self, data: &[u8], info: &EncodedVideoFrameInfo) -> bool {
+        self.sys_handle.on_encoded_frame(
+            data,
+            vt_sys::ffi::EncodedVideoFrameInfo {
+                codec_name: info.codec.as_str().to_owned(),
+                width: info.width,
+                height: info.height,
+                keyframe: info.keyframe,
+                timestamp_us: info.timestamp_us,
+            },
+        )
+    }
+
+    pub fn on_keyframe_request(&self, callback: Option<OnKeyframeRequest>) {
+        *self.on_keyframe_request.lock() = callback.map(|f| Arc::new(Mutex::new(f)));
+    }
+}
+
+impl EncodedVideoCodec {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::VP8 => "VP8",
+            Self::VP9 => "VP9",
+            Self::H264 => "H264",
+            Self::AV1 => "AV1",
+        }
+    }
+}
+
+type KeyframeRequestHandler = Arc<Mutex<OnKeyframeRequest>>;
+
+fn new_video_track_source() -> (
+    SharedPtr<vt_sys::ffi::VideoTrackSource>,
+    watch::Receiver<VideoSinkWants>,
+    Arc<Mutex<Option<KeyframeRequestHandler>>>,
+) {
+    let (sink_wants_tx, sink_wants_rx) = watch::channel(VideoSinkWants::default());
+    let on_keyframe_request = Arc::new(Mutex::new(None));
+    let observer = Arc::new(SourceObserver {
+        sink_wants_tx,
+        on_keyframe_request: on_keyframe_request.clone(),
+    });
+    let sys_handle = vt_sys::ffi::new_video_track_source(Box::new(
+        vt_sys::VideoTrackSourceObserverWrapper::new(observer),
+    ));
+    (sys_handle, sink_wants_rx, on_keyframe_request)
+}
+
+struct SourceObserver {
+    sink_wants_tx: watch::Sender<VideoSinkWants>,
+    on_keyframe_request: Arc<Mutex<Option<KeyframeRequestHandler>>>,
+}
+
+impl vt_sys::VideoTrackSourceObserver for SourceObserver {
+    fn on_sink_wants_changed(&self, wants: vt_sys::ffi::VideoSinkWants) {
+        self.sink_wants_tx.send_replace(wants.into());
+    }
+
+    // Only requested by the passthrough encoders, i.e for the encoded sources
+    fn on_keyframe_request(&self) {
+        // Called without holding the lock, so the callback can replace itself
+        let handler = self.on_keyframe_request.lock().clone();
+        if let Some(handler) = handler {
+            (handler.lock())();
+        }
+    }
+}
+
+impl From<vt_sys::ffi::VideoSinkWants> for VideoSinkWants {
+    fn from(value: vt_sys::ffi::VideoSinkWants) -> Self {
+        Self {
+            max_pixel_count: value.max_pixel_count,
+            target_pixel_count: value
+                .has_target_pixel_count
+                .then_some(value.target_pixel_count),
+            max_framerate_fps: value.max_framerate_fps,
+            resolution_alignment: value.resolution_alignment,
+            rotation_applied: value.rotation_applied,
+            black_frames: value.black_frames,
+        }
+    }
+}
@@ -1,5 +1,8 @@
+use super::encoded_stream::NativeEncodedStream;
 use super::video_frame::new_video_frame_buffer;
+use crate::rtp_receiver::RtpReceiver;
 use crate::video_frame::{BoxVideoFrame, VideoFrame};
+use crate::video_stream::native::EncodedVideoFrame;
 use crate::video_track::RtcVideoTrack;
 use cxx::{SharedPtr, UniquePtr};
 use futures::stream::Stream;
@@ -65,9 +68,14 @@ struct VideoTrackObserver {
 
 impl sys_vt::VideoSink for VideoTrackObserver {
     fn on_frame(&self, frame: UniquePtr<webrtc_sys::video_frame::ffi::VideoFrame>) {
+        let ntp_time_ms = frame.ntp_time_ms();
+        let receive_time_us = frame.receive_time_us();
         let _ = self.frame_tx.send(VideoFrame {
             rotation: frame.rotation().into(),
             timestamp: frame.timestamp_us(),
+            rtp_timestamp: Some(frame.timestamp()),
+            ntp_time_ms: (ntp_time_ms > 0).then_some(ntp_time_ms),
+            receive_time_us: (receive_time_us > 0).then_some(receive_time_us),
             buffer: new_video_frame_buffer(unsafe { frame.video_frame_buffer() }),
         });
     }
@@ -76,3 +84,41 @@ impl sys_vt::VideoSink for VideoTrackObserver {
 
     fn on_constraints_changed(&self, _constraints: sys_vt::ffi::VideoTrackSourceConstraints) {}
 }
+
+pub struct NativeEncodedVideoStream {
+    stream: NativeEncodedStream,
+}
+
+impl NativeEncodedVideoStream {
+    pub fn new(receiver: RtpReceiver) -> Self {
+        Self {
+            stream: NativeEncodedStream::new(receiver),
+        }
+    }
+
+    pub fn receiver(&self) -> RtpReceiver {
+        self.stream.receiver()
+    }
+
+    pub fn close(&mut self) {
+        self.stream.close();
+    }
+}
+
+impl Stream for NativeEncodedVideoStream {
+    type Item = EncodedVideoFrame;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next(cx).map(|frame| {
+            frame.map(|frame| EncodedVideoFrame {
+                data: frame.data,
+                mime_type: frame.mime_type,
+                rtp_timestamp: frame.info.rtp_timestamp,
+                ssrc: frame.info.ssrc,
+                keyframe: frame.info.keyframe,
+                width: frame.info.width,
+                height: frame.info.height,
+            })
+        })
+    }
+}
@@ -14,18 +14,73 @@ fn argb_assert_safety(
     _width: i32,
     height: i32,
 ) -> Result<(), ConvertError> {
-    let height_abs = height.abs() as u32;
-    let min = (src_stride * height_abs) as usize;
+    plane_assert_safety(
+        src,
+        src_stride,
+        height.unsigned_abs(),
+        "dst isn't large enough",
+    )
+}
 
-    if src.len() < min {
-        return Err(ConvertError::Convert("dst isn't large enough"));
+#[inline]
+fn plane_assert_safety<T>(
+    plane: &[T],
+    stride: u32,
+    height: u32,
+    err: &'static str,
+) -> Result<(), ConvertError> {
+    // Computed in usize, a u32 product can overflow and pass the check
+    match (stride as usize).checked_mul(height as usize) {
+        Some(min) if plane.len() >= min => Ok(()),
+        _ => Err(ConvertError::Convert(err)),
     }
+}
 
+#[inline]
+fn planar_assert_safety<T>(
+    src_y: &[T],
+    src_stride_y: u32,
+    src_u: &[T],
+    src_stride_u: u32,
+    src_v: &[T],
+    src_stride_v: u32,
+    height: i32,
+    chroma_height: u32,
+) -> Result<(), ConvertError> {
+    let height_abs = height.unsigned_abs();
+    plane_assert_safety(src_y, src_stride_y, height_abs, "y isn't large enough")?;
+    plane_assert_safety(src_u, src_stride_u, chroma_height, "u isn't large enough")?;
+    plane_assert_safety(src_v, src_stride_v, chroma_height, "v isn't large enough")?;
     Ok(())
 }
 
 #[inline]
-fn i420_assert_safety(
+fn i420_assert_safety<T>(
+    src_y: &[T],
+    src_stride_y: u32,
+    src_u: &[T],
+    src_stride_u: u32,
+    src_v: &[T],
+    src_stride_v: u32,
+    _width: i32,
+    height: i32,
+) -> Result<(), ConvertError> {
+    let chroma_height = (height.unsigned_abs() + 1) / 2;
+    planar_assert_safety(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        height,
+        chroma_height,
+    )
+}
+
+// Used for both I422 and I444 (the chroma planes aren't vertically subsampled)
+#[inline]
+fn i444_assert_safety(
     src_y: &[u8],
     src_stride_y: u32,
     src_u: &[u8],
@@ -35,29 +90,44 @@ fn i420_assert_safety(
     _width: i32,
     height: i32,
 ) -> Result<(), ConvertError> {
-    let height_abs = height.abs() as u32;
-    let chroma_height = (height_abs + 1) / 2;
-    let min_y = (src_stride_y * height_abs) as usize;
-    let min_u = (src_stride_u * chroma_height) as usize;
-    let min_v = (src_stride_v * chroma_height) as usize;
-
-    if src_y.len() < min_y {
-        return Err(ConvertError::Convert("src_y isn't large enough"));
-    }
-
-    if src_u.len() < min_u {
-        return Err(ConvertError::Convert("src_u isn't large enough"));
-    }
-
-    if src_v.len() < min_v {
-        return Err(ConvertError::Convert("src_v isn't large enough"));
-    }
+    planar_assert_safety(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        height,
+        height.unsigned_abs(),
+    )
+}
 
+#[inline]
+fn nv12_assert_safety(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_uv: &[u8],
+    src_stride_uv: u32,
+    _width: i32,
+    height: i32,
+) -> Result<(), ConvertError> {
+    let height_abs = height.unsigned_abs();
+    let chroma_height = (height_abs + 1) / 2;
+    plane_assert_safety(src_y, src_stride_y, height_abs, "y isn't large enough")?;
+    plane_assert_safety(
+        src_uv,
+        src_stride_uv,
+        chroma_height,
+        "uv isn't large enough",
+    )?;
     Ok(())
 }
 
 macro_rules! i420_to_x {
     ($x:ident) => {
+        i420_to_x!($x, i420_assert_safety);
+    };
+    ($x:ident, $src_assert:ident) => {
         pub fn $x(
             src_y: &[u8],
             src_stride_y: u32,
@@ -71,7 +141,7 @@ macro_rules! i420_to_x {
             height: i32,
         ) -> Result<(), ConvertError> {
             argb_assert_safety(dst, dst_stride, width, height)?;
-            i420_assert_safety(
+            $src_assert(
                 src_y,
                 src_stride_y,
                 src_u,
@@ -183,3 +253,263 @@ i420_to_x!(i420_to_argb);
 i420_to_x!(i420_to_bgra);
 i420_to_x!(i420_to_abgr);
 i420_to_x!(i420_to_rgba);
+
+x_to_i420!(rgba_to_i420);
+x_to_i420!(bgra_to_i420);
+x_to_i420!(rgb24_to_i420);
+
+i420_to_x!(i420_to_rgb24);
+
+i420_to_x!(i422_to_argb, i444_assert_safety);
+i420_to_x!(i422_to_abgr, i444_assert_safety);
+i420_to_x!(i444_to_argb, i444_assert_safety);
+i420_to_x!(i444_to_abgr, i444_assert_safety);
+
+macro_rules! planar_to_planar {
+    ($x:ident, $src:ty, $src_assert:ident, $dst_assert:ident) => {
+        planar_to_planar!($x, $src, u8, $src_assert, $dst_assert);
+    };
+    ($x:ident, $src:ty, $dst:ty, $src_assert:ident, $dst_assert:ident) => {
+        pub fn $x(
+            src_y: &[$src],
+            src_stride_y: u32,
+            src_u: &[$src],
+            src_stride_u: u32,
+            src_v: &[$src],
+            src_stride_v: u32,
+            dst_y: &mut [$dst],
+            dst_stride_y: u32,
+            dst_u: &mut [$dst],
+            dst_stride_u: u32,
+            dst_v: &mut [$dst],
+            dst_stride_v: u32,
+            width: i32,
+            height: i32,
+        ) -> Result<(), ConvertError> {
+            $src_assert(
+                src_y,
+                src_stride_y,
+                src_u,
+                src_stride_u,
+                src_v,
+                src_stride_v,
+                width,
+                height,
+            )?;
+            $dst_assert(
+                dst_y,
+                dst_stride_y,
+                dst_u,
+                dst_stride_u,
+                dst_v,
+                dst_stride_v,
+                width,
+                height,
+            )?;
+
+            unsafe {
+                yuv_sys::ffi::$x(
+                    src_y.as_ptr(),
+                    src_stride_y as i32,
+                    src_u.as_ptr(),
+                    src_stride_u as i32,
+                    src_v.as_ptr(),
+                    src_stride_v as i32,
+                    dst_y.as_mut_ptr(),
+                    dst_stride_y as i32,
+                    dst_u.as_mut_ptr(),
+                    dst_stride_u as i32,
+                    dst_v.as_mut_ptr(),
+                    dst_stride_v as i32,
+                    width,
+                    height,
+                )
+                .unwrap();
+            }
+
+            Ok(())
+        }
+    };
+}
+
+planar_to_planar!(i420_to_i422, u8, i420_assert_safety, i444_assert_safety);
+planar_to_planar!(i420_to_i444, u8, i420_assert_safety, i444_assert_safety);
+planar_to_planar!(i422_to_i420, u8, i444_assert_safety, i420_assert_safety);
+planar_to_planar!(i444_to_i420, u8, i444_assert_safety, i420_assert_safety);
+planar_to_planar!(i010_to_i420, u16, i420_assert_safety, i420_assert_safety);
+planar_to_planar!(
+    i420_to_i010,
+    u8,
+    u16,
+    i420_assert_safety,
+    i420_assert_safety
+);
+
+pub fn i420_to_nv12(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    width: i32,
+    height: i32,
+) -> Result<(), ConvertError> {
+    i420_assert_safety(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        width,
+        height,
+    )?;
+    nv12_assert_safety(dst_y, dst_stride_y, dst_uv, dst_stride_uv, width, height)?;
+
+    unsafe {
+        yuv_sys::ffi::i420_to_nv12(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_u.as_ptr(),
+            src_stride_u as i32,
+            src_v.as_ptr(),
+            src_stride_v as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_uv.as_mut_ptr(),
+            dst_stride_uv as i32,
+            width,
+            height,
+        )
+        .unwrap();
+    }
+
+    Ok(())
+}
+
+pub fn nv12_to_i420(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_uv: &[u8],
+    src_stride_uv: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    width: i32,
+    height: i32,
+) -> Result<(), ConvertError> {
+    nv12_assert_safety(src_y, src_stride_y, src_uv, src_stride_uv, width, height)?;
+    i420_assert_safety(
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+    )?;
+
+    unsafe {
+        yuv_sys::ffi::nv12_to_i420(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_uv.as_ptr(),
+            src_stride_uv as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_u.as_mut_ptr(),
+            dst_stride_u as i32,
+            dst_v.as_mut_ptr(),
+            dst_stride_v as i32,
+            width,
+            height,
+        )
+        .unwrap();
+    }
+
+    Ok(())
+}
+
+macro_rules! nv12_to_x {
+    ($x:ident) => {
+        pub fn $x(
+            src_y: &[u8],
+            src_stride_y: u32,
+            src_uv: &[u8],
+            src_stride_uv: u32,
+            dst: &mut [u8],
+            dst_stride: u32,
+            width: i32,
+            height: i32,
+        ) -> Result<(), ConvertError> {
+            nv12_assert_safety(src_y, src_stride_y, src_uv, src_stride_uv, width, height)?;
+            argb_assert_safety(dst, dst_stride, width, height)?;
+
+            unsafe {
+                yuv_sys::ffi::$x(
+                    src_y.as_ptr(),
+                    src_stride_y as i32,
+                    src_uv.as_ptr(),
+                    src_stride_uv as i32,
+                    dst.as_mut_ptr(),
+                    dst_stride as i32,
+                    width,
+                    height,
+                )
+                .unwrap();
+            }
+
+            Ok(())
+        }
+    };
+}
+
+macro_rules! x_to_nv12 {
+    ($x:ident) => {
+        pub fn $x(
+            src: &[u8],
+            src_stride: u32,
+            dst_y: &mut [u8],
+            dst_stride_y: u32,
+            dst_uv: &mut [u8],
+            dst_stride_uv: u32,
+            width: i32,
+            height: i32,
+        ) -> Result<(), ConvertError> {
+            argb_assert_safety(src, src_stride, width, height)?;
+            nv12_assert_safety(dst_y, dst_stride_y, dst_uv, dst_stride_uv, width, height)?;
+
+            unsafe {
+                yuv_sys::ffi::$x(
+                    src.as_ptr(),
+                    src_stride as i32,
+                    dst_y.as_mut_ptr(),
+                    dst_stride_y as i32,
+                    dst_uv.as_mut_ptr(),
+                    dst_stride_uv as i32,
+                    width,
+                    height,
+                )
+                .unwrap();
+            }
+
+            Ok(())
+        }
+    };
+}
+
+nv12_to_x!(nv12_to_argb);
+nv12_to_x!(nv12_to_abgr);
+
+x_to_nv12!(argb_to_nv12);
+x_to_nv12!(abgr_to_nv12);
+x_to_nv12!(rgb24_to_nv12);
@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 
 use crate::data_channel::{DataChannel, DataChannelInit};
-use crate::ice_candidate::IceCandidate;
+use crate::ice_candidate::{Candidate, IceCandidate};
 use crate::imp::peer_connection as imp_pc;
 use crate::media_stream::MediaStream;
 use crate::media_stream_track::MediaStreamTrack;
@@ -69,6 +69,20 @@ pub struct IceCandidateError {
     pub error_text: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct CandidatePair {
+    pub local: Candidate,
+    pub remote: Candidate,
+}
+
+#[derive(Debug, Clone)]
+pub struct CandidatePairChangeEvent {
+    pub selected_candidate_pair: CandidatePair,
+    pub last_data_received_ms: i64,
+    pub reason: String,
+    pub estimated_disconnected_time_ms: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct TrackEvent {
     pub receiver: RtpReceiver,
@@ -83,6 +97,7 @@ pub type OnIceCandidate = Box<dyn FnMut(IceCandidate) + Send + Sync>;
 pub type OnIceCandidateError = Box<dyn FnMut(IceCandidateError) + Send + Sync>;
 pub type OnIceConnectionChange = Box<dyn FnMut(IceConnectionState) + Send + Sync>;
 pub type OnIceGatheringChange = Box<dyn FnMut(IceGatheringState) + Send + Sync>;
+pub type OnIceSelectedCandidatePairChange = Box<dyn FnMut(CandidatePairChangeEvent) + Send + Sync>;
 pub type OnNegotiationNeeded = Box<dyn FnMut(u32) + Send + Sync>;
 pub type OnSignalingChange = Box<dyn FnMut(SignalingState) + Send + Sync>;
 pub type OnTrack = Box<dyn FnMut(TrackEvent) + Send + Sync>;
@@ -218,6 +233,13 @@ impl PeerConnection {
         self.handle.on_ice_gathering_state_change(f)
     }
 
+    pub fn on_ice_selected_candidate_pair_change(
+        &self,
+        f: Option<OnIceSelectedCandidatePairChange>,
+    ) {
+        self.handle.on_ice_selected_candidate_pair_change(f)
+    }
+
     pub fn on_negotiation_needed(&self, f: Option<OnNegotiationNeeded>) {
         self.handle.on_negotiation_needed(f)
     }
@@ -240,11 +262,13 @@ impl Debug for PeerConnection {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
     use crate::peer_connection::*;
     use crate::peer_connection_factory::*;
     use log::trace;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
     use tokio::sync::mpsc;
 
     #[tokio::test]
@@ -260,6 +284,7 @@ mod tests {
             }],
             continual_gathering_policy: ContinualGatheringPolicy::GatherOnce,
             ice_transport_type: IceTransportsType::All,
+            ..Default::default()
         };
 
         let bob = factory.create_peer_connection(config.clone()).unwrap();
@@ -315,4 +340,114 @@ mod tests {
         alice.close();
         bob.close();
     }
+
+    #[tokio::test]
+    async fn ice_events() {
+        let factory = PeerConnectionFactory::default();
+        let bob = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+        let alice = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+
+        // Bob's candidates are kept to check the selected pair
+        let bob_candidates = Arc::new(Mutex::new(Vec::<Candidate>::new()));
+        let (bob_ice_tx, mut bob_ice_rx) = mpsc::unbounded_channel::<IceCandidate>();
+        let (alice_ice_tx, mut alice_ice_rx) = mpsc::unbounded_channel::<IceCandidate>();
+        let (gathering_tx, mut gathering_rx) = mpsc::unbounded_channel::<IceGatheringState>();
+        let (pair_tx, mut pair_rx) = mpsc::unbounded_channel::<CandidatePairChangeEvent>();
+
+        bob.on_ice_candidate(Some(Box::new({
+            let bob_candidates = bob_candidates.clone();
+            move |candidate| {
+                bob_candidates.lock().push(candidate.candidate());
+                let _ = bob_ice_tx.send(candidate);
+            }
+        })));
+        alice.on_ice_candidate(Some(Box::new(move |candidate| {
+            let _ = alice_ice_tx.send(candidate);
+        })));
+        bob.on_ice_gathering_state_change(Some(Box::new(move |state| {
+            let _ = gathering_tx.send(state);
+        })));
+        bob.on_ice_selected_candidate_pair_change(Some(Box::new(move |event| {
+            let _ = pair_tx.send(event);
+        })));
+
+        let _bob_dc = bob
+            .create_data_channel("test_dc", DataChannelInit::default())
+            .unwrap();
+
+        let offer = bob.create_offer(OfferOptions::default()).await.unwrap();
+        bob.set_local_description(offer.clone()).await.unwrap();
+        alice.set_remote_description(offer).await.unwrap();
+        let answer = alice.create_answer(AnswerOptions::default()).await.unwrap();
+        alice.set_local_description(answer.clone()).await.unwrap();
+        bob.set_remote_description(answer).await.unwrap();
+
+        tokio::spawn({
+            let alice = alice.clone();
+            async move {
+                while let Some(candidate) = bob_ice_rx.recv().await {
+                    let _ = alice.add_ice_candidate(candidate).await;
+                }
+            }
+        });
+        tokio::spawn({
+            let bob = bob.clone();
+            async move {
+                while let Some(candidate) = alice_ice_rx.recv().await {
+                    let _ = bob.add_ice_candidate(candidate).await;
+                }
+            }
+        });
+
+        assert_eq!(
+            gathering_rx.recv().await,
+            Some(IceGatheringState::Gathering)
+        );
+        assert_eq!(gathering_rx.recv().await, Some(IceGatheringState::Complete));
+
+        // The selected local candidate is one of the gathered candidates
+        let pair = pair_rx.recv().await.unwrap().selected_candidate_pair;
+        assert!(bob_candidates.lock().iter().any(|candidate| {
+            candidate.address == pair.local.address
+                && candidate.port == pair.local.port
+                && candidate.protocol == pair.local.protocol
+        }));
+        assert_ne!(pair.remote.port, 0);
+
+        alice.close();
+        bob.close();
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod web_tests {
+    use crate::peer_connection::*;
+    use crate::peer_connection_factory::*;
+    use crate::session_description::SdpType;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    async fn create_offer() {
+        let factory = PeerConnectionFactory::default();
+        let pc = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+
+        let _dc = pc
+            .create_data_channel("test_dc", DataChannelInit::default())
+            .unwrap();
+
+        let offer = pc.create_offer(OfferOptions::default()).await.unwrap();
+        assert_eq!(offer.sdp_type(), SdpType::Offer);
+        pc.set_local_description(offer).await.unwrap();
+        assert_eq!(pc.signaling_state(), SignalingState::HaveLocalOffer);
+
+        pc.close();
+    }
 }
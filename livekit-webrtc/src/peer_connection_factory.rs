@@ -4,6 +4,7 @@ use crate::rtp_parameters::RtpCapabilities;
 use crate::MediaType;
 use crate::RtcError;
 use std::fmt::Debug;
+use std::ops::RangeInclusive;
 
 #[derive(Debug, Clone)]
 pub struct IceServer {
@@ -26,11 +27,28 @@ pub enum IceTransportsType {
     All,
 }
 
+/// Type of the network interfaces used to gather candidates
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NetworkType {
+    Ethernet,
+    Wifi,
+    Cellular,
+    Vpn,
+    Loopback,
+}
+
 #[derive(Debug, Clone)]
 pub struct RtcConfiguration {
     pub ice_servers: Vec<IceServer>,
     pub continual_gathering_policy: ContinualGatheringPolicy,
+    /// Use IceTransportsType::Relay to only gather relay candidates (e.g on metered networks)
     pub ice_transport_type: IceTransportsType,
+    /// Local ports allowed for the candidates
+    pub port_range: Option<RangeInclusive<u16>>,
+    pub disable_ipv6: bool,
+    pub disable_link_local_networks: bool,
+    /// Only gather UDP candidates
+    pub disable_tcp: bool,
 }
 
 impl Default for RtcConfiguration {
@@ -39,6 +57,41 @@ impl Default for RtcConfiguration {
             ice_servers: vec![],
             continual_gathering_policy: ContinualGatheringPolicy::GatherOnce,
             ice_transport_type: IceTransportsType::All,
+            port_range: None,
+            disable_ipv6: false,
+            disable_link_local_networks: false,
+            disable_tcp: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PeerConnectionFactoryOptions {
+    /// WebRTC field trials, e.g "WebRTC-Foo/Enabled/WebRTC-Bar/Disabled/"
+    pub field_trials: String,
+    /// 0 spawns a network thread dedicated to this factory. Otherwise the thread is picked
+    /// round-robin from a pool of this size, shared with the other factories
+    pub network_threads: u32,
+    /// Same as network_threads, for the worker thread
+    pub worker_threads: u32,
+    /// ADM used for the playout and recording, None uses a virtual device: there is no
+    /// hardware access and the remote audio is only available through the audio sinks
+    #[cfg(not(target_arch = "wasm32"))]
+    pub audio_device: Option<native::AudioDeviceModule>,
+    /// Interfaces of these types are ignored when gathering candidates, by all the
+    /// PeerConnections created from this factory
+    pub ignored_network_types: Vec<NetworkType>,
+}
+
+impl Default for PeerConnectionFactoryOptions {
+    fn default() -> Self {
+        Self {
+            field_trials: String::default(),
+            network_threads: 0,
+            worker_threads: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            audio_device: None,
+            ignored_network_types: Vec::new(),
         }
     }
 }
@@ -55,6 +108,12 @@ impl Debug for PeerConnectionFactory {
 }
 
 impl PeerConnectionFactory {
+    pub fn with_options(options: PeerConnectionFactoryOptions) -> Self {
+        Self {
+            handle: imp_pcf::PeerConnectionFactory::with_options(options),
+        }
+    }
+
     pub fn create_peer_connection(
         &self,
         config: RtcConfiguration,
@@ -71,16 +130,56 @@ impl PeerConnectionFactory {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 pub mod native {
     use super::PeerConnectionFactory;
-    use crate::audio_source::native::NativeAudioSource;
+    use crate::audio_source::native::{EncodedAudioSource, NativeAudioSource};
     use crate::audio_track::RtcAudioTrack;
-    use crate::video_source::native::NativeVideoSource;
+    use crate::imp::peer_connection_factory as imp_pcf;
+    use crate::imp::video_codec::{VideoDecoderFactory, VideoEncoderFactory};
+    use crate::video_source::native::{EncodedVideoSource, NativeVideoSource};
     use crate::video_track::RtcVideoTrack;
+    use std::fmt::Debug;
+
+    /// Audio device module given to PeerConnectionFactoryOptions
+    #[derive(Clone)]
+    pub struct AudioDeviceModule {
+        pub(crate) handle: imp_pcf::AudioDeviceModule,
+    }
+
+    impl AudioDeviceModule {
+        /// Default playout/recording devices of the platform, None if it isn't supported
+        pub fn platform() -> Option<Self> {
+            imp_pcf::AudioDeviceModule::platform().map(|handle| Self { handle })
+        }
+    }
+
+    impl Debug for AudioDeviceModule {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_struct("AudioDeviceModule").finish()
+        }
+    }
 
     pub trait PeerConnectionFactoryExt {
         fn create_video_track(&self, label: &str, source: NativeVideoSource) -> RtcVideoTrack;
         fn create_audio_track(&self, label: &str, source: NativeAudioSource) -> RtcAudioTrack;
+        fn create_encoded_video_track(
+            &self,
+            label: &str,
+            source: EncodedVideoSource,
+        ) -> RtcVideoTrack;
+        fn create_encoded_audio_track(
+            &self,
+            label: &str,
+            source: EncodedAudioSource,
+        ) -> RtcAudioTrack;
+
+        /// The factories are queried each time libwebrtc creates an encoder/decoder (on
+        /// negotiation), including for the existing PeerConnections. Running codecs keep their
+        /// implementation and the new formats are only offered by the next negotiations.
+        /// The last registered factory takes precedence.
+        fn register_video_encoder_factory(&self, factory: Box<dyn VideoEncoderFactory>);
+        fn register_video_decoder_factory(&self, factory: Box<dyn VideoDecoderFactory>);
     }
 
     impl PeerConnectionFactoryExt for PeerConnectionFactory {
@@ -91,5 +190,53 @@ pub mod native {
         fn create_audio_track(&self, label: &str, source: NativeAudioSource) -> RtcAudioTrack {
             self.handle.create_audio_track(label, source)
         }
+
+        fn create_encoded_video_track(
+            &self,
+            label: &str,
+            source: EncodedVideoSource,
+        ) -> RtcVideoTrack {
+            self.handle.create_encoded_video_track(label, source)
+        }
+
+        fn create_encoded_audio_track(
+            &self,
+            label: &str,
+            source: EncodedAudioSource,
+        ) -> RtcAudioTrack {
+            self.handle.create_encoded_audio_track(label, source)
+        }
+
+        fn register_video_encoder_factory(&self, factory: Box<dyn VideoEncoderFactory>) {
+            self.handle.register_video_encoder_factory(factory)
+        }
+
+        fn register_video_decoder_factory(&self, factory: Box<dyn VideoDecoderFactory>) {
+            self.handle.register_video_decoder_factory(factory)
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub mod web {
+    use super::PeerConnectionFactory;
+    use crate::audio_source::web::WebAudioSource;
+    use crate::audio_track::RtcAudioTrack;
+    use crate::video_source::web::WebVideoSource;
+    use crate::video_track::RtcVideoTrack;
+
+    pub trait PeerConnectionFactoryExt {
+        fn create_video_track(&self, label: &str, source: WebVideoSource) -> RtcVideoTrack;
+        fn create_audio_track(&self, label: &str, source: WebAudioSource) -> RtcAudioTrack;
+    }
+
+    impl PeerConnectionFactoryExt for PeerConnectionFactory {
+        fn create_video_track(&self, label: &str, source: WebVideoSource) -> RtcVideoTrack {
+            self.handle.create_video_track(label, source.handle)
+        }
+
+        fn create_audio_track(&self, label: &str, source: WebAudioSource) -> RtcAudioTrack {
+            self.handle.create_audio_track(label, source.handle)
+        }
     }
 }
@@ -1,20 +1,21 @@
-pub use crate::audio_frame::AudioFrame;
+pub use crate::audio_frame::{AudioFrame, PlanarAudioFrame, Sample};
 pub use crate::audio_track::RtcAudioTrack;
 pub use crate::data_channel::{
-    DataBuffer, DataChannel, DataChannelError, DataChannelInit, DataState,
+    DataBuffer, DataChannel, DataChannelError, DataChannelInit, DataMessage, DataState,
 };
-pub use crate::ice_candidate::IceCandidate;
+pub use crate::ice_candidate::{Candidate, CandidateType, IceCandidate, TcpType};
 pub use crate::media_stream::MediaStream;
 pub use crate::media_stream_track::{MediaStreamTrack, RtcTrackState};
 pub use crate::peer_connection::{
-    AnswerOptions, IceConnectionState, IceGatheringState, OfferOptions, PeerConnection,
-    PeerConnectionState, SignalingState,
+    AnswerOptions, CandidatePair, CandidatePairChangeEvent, IceCandidateError, IceConnectionState,
+    IceGatheringState, OfferOptions, PeerConnection, PeerConnectionState, SignalingState,
 };
 pub use crate::peer_connection_factory::{
-    ContinualGatheringPolicy, IceServer, IceTransportsType, PeerConnectionFactory, RtcConfiguration,
+    ContinualGatheringPolicy, IceServer, IceTransportsType, NetworkType, PeerConnectionFactory,
+    PeerConnectionFactoryOptions, RtcConfiguration,
 };
 pub use crate::rtp_parameters::*;
-pub use crate::rtp_receiver::RtpReceiver;
+pub use crate::rtp_receiver::{RtpReceiver, RtpSource, RtpSourceType};
 pub use crate::rtp_sender::RtpSender;
 pub use crate::rtp_transceiver::{RtpTransceiver, RtpTransceiverDirection, RtpTransceiverInit};
 pub use crate::session_description::{SdpType, SessionDescription};
@@ -20,6 +20,8 @@ pub struct RtpParameters {
     pub codecs: Vec<RtpCodecParameters>,
     pub header_extensions: Vec<RtpHeaderExtensionParameters>,
     pub rtcp: RtcpParameters,
+    /// Only set on the senders, one per simulcast layer
+    pub encodings: Vec<RtpEncodingParameters>,
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +46,7 @@ pub struct RtpEncodingParameters {
     pub priority: Priority,
     pub rid: String,
     pub scale_resolution_down_by: Option<f64>,
+    pub scalability_mode: Option<String>, // e.g. L3T3_KEY, only used by the SVC codecs
 }
 
 #[derive(Debug, Clone)]
@@ -86,6 +89,7 @@ impl Default for RtpEncodingParameters {
             priority: Priority::Low,
             rid: String::default(),
             scale_resolution_down_by: None,
+            scalability_mode: None,
         }
     }
 }
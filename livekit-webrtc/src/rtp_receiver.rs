@@ -1,10 +1,35 @@
 use std::fmt::Debug;
+use std::time::Duration;
 
 use crate::{
     imp::rtp_receiver as imp_rr, media_stream_track::MediaStreamTrack,
     rtp_parameters::RtpParameters,
 };
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RtpSourceType {
+    Ssrc,
+    Csrc,
+}
+
+/// Source of the packets received in the last 10 seconds
+#[derive(Debug, Clone)]
+pub struct RtpSource {
+    pub source_id: u32, // SSRC or CSRC
+    pub source_type: RtpSourceType,
+    pub timestamp_ms: i64, // Local time when the last packet was received
+    pub rtp_timestamp: u32,
+    pub audio_level: Option<u8>, // ssrc-audio-level extension, in -dBov (127 is silence)
+}
+
+impl RtpSource {
+    /// Audio level converted to a linear scale, between 0.0 and 1.0
+    pub fn audio_level_linear(&self) -> Option<f32> {
+        self.audio_level
+            .map(|level| 10f32.powf(-(level.min(127) as f32) / 20.0))
+    }
+}
+
 #[derive(Clone)]
 pub struct RtpReceiver {
     pub(crate) handle: imp_rr::RtpReceiver,
@@ -18,6 +43,28 @@ impl RtpReceiver {
     pub fn parameters(&self) -> RtpParameters {
         self.handle.parameters()
     }
+
+    /// Minimum delay of the jitter buffer, trading latency for smoothness.
+    /// None restores the default behavior
+    pub fn set_jitter_buffer_minimum_delay(&self, delay: Option<Duration>) {
+        self.handle.set_jitter_buffer_minimum_delay(delay)
+    }
+
+    pub fn get_synchronization_sources(&self) -> Vec<RtpSource> {
+        self.get_sources(RtpSourceType::Ssrc)
+    }
+
+    pub fn get_contributing_sources(&self) -> Vec<RtpSource> {
+        self.get_sources(RtpSourceType::Csrc)
+    }
+
+    fn get_sources(&self, source_type: RtpSourceType) -> Vec<RtpSource> {
+        self.handle
+            .sources()
+            .into_iter()
+            .filter(|source| source.source_type == source_type)
+            .collect()
+    }
 }
 
 impl Debug for RtpReceiver {
@@ -1,5 +1,7 @@
 use crate::imp::rtp_transceiver as imp_rt;
-use crate::rtp_parameters::{RtpCodecCapability, RtpEncodingParameters};
+use crate::rtp_parameters::{
+    RtpCodecCapability, RtpEncodingParameters, RtpHeaderExtensionCapability,
+};
 use crate::rtp_receiver::RtpReceiver;
 use crate::rtp_sender::RtpSender;
 use crate::RtcError;
@@ -39,6 +41,11 @@ impl RtpTransceiver {
         self.handle.direction()
     }
 
+    /// Takes effect on the next negotiation, Stopped isn't a valid direction (see stop)
+    pub fn set_direction(&self, direction: RtpTransceiverDirection) -> Result<(), RtcError> {
+        self.handle.set_direction(direction)
+    }
+
     pub fn sender(&self) -> RtpSender {
         self.handle.sender()
     }
@@ -51,6 +58,23 @@ impl RtpTransceiver {
         self.handle.set_codec_preferences(codecs)
     }
 
+    pub fn header_extensions_to_offer(&self) -> Vec<RtpHeaderExtensionCapability> {
+        self.handle.header_extensions_to_offer()
+    }
+
+    pub fn header_extensions_negotiated(&self) -> Vec<RtpHeaderExtensionCapability> {
+        self.handle.header_extensions_negotiated()
+    }
+
+    /// Used by the next offer or answer, the list must contain all the extensions returned
+    /// by header_extensions_to_offer (a direction of Stopped disables an extension)
+    pub fn set_header_extensions_to_offer(
+        &self,
+        extensions: Vec<RtpHeaderExtensionCapability>,
+    ) -> Result<(), RtcError> {
+        self.handle.set_header_extensions_to_offer(extensions)
+    }
+
     pub fn stop(&self) -> Result<(), RtcError> {
         self.handle.stop()
     }
@@ -0,0 +1,922 @@
+//! Structured and editable model of a SDP (RFC 8866), used to munge a SessionDescription.
+//! Lines that aren't modeled are kept as is and the a= lines keep their original order, so a
+//! parsed SDP is serialized back to the same lines. Only the line endings (always CRLF) and
+//! the spaces between fmtp parameters are normalized.
+
+use crate::session_description::SdpParseError;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Any line that isn't an attribute (e.g "c=IN IP4 0.0.0.0", kind is 'c')
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdpLine {
+    pub kind: char,
+    pub value: String,
+}
+
+/// Generic attribute, "a=name" or "a=name:value"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+/// "a=group:BUNDLE 0 1"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub semantics: String,
+    pub mids: Vec<String>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    SendRecv,
+    SendOnly,
+    RecvOnly,
+    Inactive,
+}
+
+/// "a=extmap:id[/direction] uri [attributes]"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extmap {
+    pub id: u16,
+    pub direction: Option<Direction>,
+    pub uri: String,
+    pub attributes: Option<String>,
+}
+
+/// Parameter of a fmtp line, the value is None for parameters without "=" (e.g RED "111/111")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FmtpParameter {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+/// A payload type of a media section with its rtpmap, rtcp-fb and fmtp lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Codec {
+    pub payload_type: u8,
+    /// Empty for static payload types without rtpmap line
+    pub name: String,
+    pub clock_rate: u32,
+    pub channels: Option<u32>,
+    pub rtcp_fb: Vec<String>,
+    pub fmtp: Vec<FmtpParameter>,
+}
+
+/// "a=ssrc-group:FID 1234 5678"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsrcGroup {
+    pub semantics: String,
+    pub ssrcs: Vec<u32>,
+}
+
+/// "a=ssrc:1234 cname:abcd"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsrcAttribute {
+    pub ssrc: u32,
+    pub name: String,
+    pub value: Option<String>,
+}
+
+/// Position of a parsed a= line, the lines are written back in this order
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Slot {
+    Attribute,
+    Group,
+    Extmap,
+    Direction,
+    Rtpmap(u8),
+    RtcpFb(u8),
+    Fmtp(u8),
+    SsrcGroup,
+    Ssrc,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaSection {
+    /// audio, video or application
+    pub media: String,
+    pub port: u16,
+    pub protocol: String,
+    /// Formats of the m-line that aren't described by a codec (e.g webrtc-datachannel).
+    /// The payload types of the codecs are written first, in the order of `codecs`
+    pub formats: Vec<String>,
+    pub lines: Vec<SdpLine>,
+    pub direction: Option<Direction>,
+    pub extmaps: Vec<Extmap>,
+    pub codecs: Vec<Codec>,
+    pub ssrc_groups: Vec<SsrcGroup>,
+    pub ssrcs: Vec<SsrcAttribute>,
+    pub attributes: Vec<Attribute>,
+    order: Vec<Slot>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sdp {
+    /// Session lines (v=, o=, s=, t=, ...) in their original order
+    pub lines: Vec<SdpLine>,
+    pub groups: Vec<Group>,
+    pub attributes: Vec<Attribute>,
+    pub media: Vec<MediaSection>,
+    order: Vec<Slot>,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SendRecv => "sendrecv",
+            Self::SendOnly => "sendonly",
+            Self::RecvOnly => "recvonly",
+            Self::Inactive => "inactive",
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = &'static str;
+
+    fn from_str(direction: &str) -> Result<Self, Self::Err> {
+        match direction {
+            "sendrecv" => Ok(Self::SendRecv),
+            "sendonly" => Ok(Self::SendOnly),
+            "recvonly" => Ok(Self::RecvOnly),
+            "inactive" => Ok(Self::Inactive),
+            _ => Err("invalid Direction"),
+        }
+    }
+}
+
+impl Attribute {
+    fn parse(value: &str) -> Self {
+        match value.split_once(':') {
+            Some((name, value)) => Self {
+                name: name.to_owned(),
+                value: Some(value.to_owned()),
+            },
+            None => Self {
+                name: value.to_owned(),
+                value: None,
+            },
+        }
+    }
+}
+
+impl Codec {
+    pub fn fmtp_parameter(&self, name: &str) -> Option<&str> {
+        self.fmtp
+            .iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.value.as_deref())
+    }
+
+    /// Add or replace a fmtp parameter (e.g "stereo=1" for Opus)
+    pub fn set_fmtp_parameter(&mut self, name: &str, value: &str) {
+        match self.fmtp.iter_mut().find(|p| p.name == name) {
+            Some(param) => param.value = Some(value.to_owned()),
+            None => self.fmtp.push(FmtpParameter {
+                name: name.to_owned(),
+                value: Some(value.to_owned()),
+            }),
+        }
+    }
+
+    pub fn remove_fmtp_parameter(&mut self, name: &str) {
+        self.fmtp.retain(|p| p.name != name);
+    }
+
+    /// Payload type this codec is associated with (apt), used by RTX
+    pub fn associated_payload_type(&self) -> Option<u8> {
+        self.fmtp_parameter("apt").and_then(|apt| apt.parse().ok())
+    }
+}
+
+impl MediaSection {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.name == name)
+            .and_then(|a| a.value.as_deref())
+    }
+
+    pub fn mid(&self) -> Option<&str> {
+        self.attribute("mid")
+    }
+
+    /// Codecs matching the name, case insensitive (e.g "opus", "VP8")
+    pub fn codecs_by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Codec> {
+        self.codecs
+            .iter()
+            .filter(move |c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn codecs_by_name_mut<'a>(
+        &'a mut self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a mut Codec> {
+        self.codecs
+            .iter_mut()
+            .filter(move |c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Move the codecs matching the name, and their RTX payload types, in front of the
+    /// other codecs. The relative order of the other codecs is kept
+    pub fn prioritize_codec(&mut self, name: &str) {
+        let preferred: Vec<u8> = self.codecs_by_name(name).map(|c| c.payload_type).collect();
+
+        let is_preferred = |c: &Codec| {
+            preferred.contains(&c.payload_type)
+                || matches!(c.associated_payload_type(), Some(apt) if preferred.contains(&apt))
+        };
+
+        let (mut codecs, others): (Vec<_>, Vec<_>) =
+            self.codecs.drain(..).partition(|c| is_preferred(c));
+        codecs.extend(others);
+        self.codecs = codecs;
+    }
+
+    /// Remove the codecs matching the name, and their RTX payload types
+    pub fn remove_codec(&mut self, name: &str) {
+        let removed: Vec<u8> = self.codecs_by_name(name).map(|c| c.payload_type).collect();
+
+        self.codecs.retain(|c| {
+            !removed.contains(&c.payload_type)
+                && !matches!(c.associated_payload_type(), Some(apt) if removed.contains(&apt))
+        });
+    }
+
+    /// Set the "b=AS" bandwidth hint of this section, in kbps
+    pub fn set_bandwidth(&mut self, kbps: u32) {
+        let value = format!("AS:{}", kbps);
+        match self
+            .lines
+            .iter_mut()
+            .find(|l| l.kind == 'b' && l.value.starts_with("AS:"))
+        {
+            Some(line) => line.value = value,
+            None => self.lines.push(SdpLine { kind: 'b', value }),
+        }
+    }
+
+    fn parse_mline(line: &str, value: &str) -> Result<Self, SdpParseError> {
+        let mut fields = value.split_whitespace();
+        let (media, port, protocol) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(media), Some(port), Some(protocol)) => (media, port, protocol),
+            _ => {
+                return Err(parse_error(
+                    line,
+                    "m-line must contain media, port and protocol",
+                ))
+            }
+        };
+
+        let port = port
+            .parse()
+            .map_err(|_| parse_error(line, "invalid port"))?;
+
+        let mut section = Self {
+            media: media.to_owned(),
+            port,
+            protocol: protocol.to_owned(),
+            formats: Vec::new(),
+            lines: Vec::new(),
+            direction: None,
+            extmaps: Vec::new(),
+            codecs: Vec::new(),
+            ssrc_groups: Vec::new(),
+            ssrcs: Vec::new(),
+            attributes: Vec::new(),
+            order: Vec::new(),
+        };
+
+        for format in fields {
+            if !protocol.contains("RTP") {
+                section.formats.push(format.to_owned());
+                continue;
+            }
+
+            let payload_type = format
+                .parse()
+                .map_err(|_| parse_error(line, "invalid payload type"))?;
+
+            section.codecs.push(Codec {
+                payload_type,
+                name: String::new(),
+                clock_rate: 0,
+                channels: None,
+                rtcp_fb: Vec::new(),
+                fmtp: Vec::new(),
+            });
+        }
+
+        Ok(section)
+    }
+
+    fn parse_attribute(&mut self, line: &str, value: &str) -> Result<(), SdpParseError> {
+        if let Ok(direction) = value.parse() {
+            self.direction = Some(direction);
+            self.order.push(Slot::Direction);
+            return Ok(());
+        }
+
+        let attribute = Attribute::parse(value);
+        let attribute_value = match attribute.value.as_deref() {
+            Some(value) => value,
+            None => {
+                self.attributes.push(attribute);
+                self.order.push(Slot::Attribute);
+                return Ok(());
+            }
+        };
+
+        match attribute.name.as_str() {
+            "rtpmap" | "fmtp" | "rtcp-fb" => {
+                let (payload_type, params) = attribute_value
+                    .split_once(' ')
+                    .unwrap_or((attribute_value, ""));
+
+                // e.g "a=rtcp-fb:* nack", keep it as a generic attribute
+                let codec = match payload_type
+                    .parse::<u8>()
+                    .ok()
+                    .and_then(|pt| self.codecs.iter_mut().find(|c| c.payload_type == pt))
+                {
+                    Some(codec) => codec,
+                    None => {
+                        self.attributes.push(attribute);
+                        self.order.push(Slot::Attribute);
+                        return Ok(());
+                    }
+                };
+
+                match attribute.name.as_str() {
+                    "rtpmap" => {
+                        self.order.push(Slot::Rtpmap(codec.payload_type));
+                        let mut encoding = params.split('/');
+                        codec.name = encoding.next().unwrap_or_default().to_owned();
+                        codec.clock_rate = encoding
+                            .next()
+                            .and_then(|rate| rate.parse().ok())
+                            .ok_or_else(|| parse_error(line, "invalid clock rate"))?;
+                        codec.channels = encoding
+                            .next()
+                            .map(|channels| channels.parse())
+                            .transpose()
+                            .map_err(|_| parse_error(line, "invalid channels"))?;
+                    }
+                    "fmtp" => {
+                        self.order.push(Slot::Fmtp(codec.payload_type));
+                        codec.fmtp = params
+                            .split(';')
+                            .map(str::trim)
+                            .filter(|p| !p.is_empty())
+                            .map(|p| match p.split_once('=') {
+                                Some((name, value)) => FmtpParameter {
+                                    name: name.to_owned(),
+                                    value: Some(value.to_owned()),
+                                },
+                                None => FmtpParameter {
+                                    name: p.to_owned(),
+                                    value: None,
+                                },
+                            })
+                            .collect();
+                    }
+                    _ => {
+                        self.order.push(Slot::RtcpFb(codec.payload_type));
+                        codec.rtcp_fb.push(params.to_owned());
+                    }
+                }
+            }
+            "extmap" => {
+                let mut fields = attribute_value.splitn(3, ' ');
+                let (id, uri) = match (fields.next(), fields.next()) {
+                    (Some(id), Some(uri)) => (id, uri),
+                    _ => return Err(parse_error(line, "extmap must contain an id and an uri")),
+                };
+
+                let (id, direction) = match id.split_once('/') {
+                    Some((id, direction)) => (
+                        id,
+                        Some(
+                            direction
+                                .parse()
+                                .map_err(|_| parse_error(line, "invalid extmap direction"))?,
+                        ),
+                    ),
+                    None => (id, None),
+                };
+
+                self.order.push(Slot::Extmap);
+                self.extmaps.push(Extmap {
+                    id: id
+                        .parse()
+                        .map_err(|_| parse_error(line, "invalid extmap id"))?,
+                    direction,
+                    uri: uri.to_owned(),
+                    attributes: fields.next().map(str::to_owned),
+                });
+            }
+            "ssrc-group" => {
+                let mut fields = attribute_value.split_whitespace();
+                let semantics = fields.next().unwrap_or_default().to_owned();
+                let ssrcs = fields
+                    .map(|ssrc| ssrc.parse())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| parse_error(line, "invalid ssrc"))?;
+
+                self.ssrc_groups.push(SsrcGroup { semantics, ssrcs });
+                self.order.push(Slot::SsrcGroup);
+            }
+            "ssrc" => {
+                let (ssrc, ssrc_attribute) = attribute_value
+                    .split_once(' ')
+                    .unwrap_or((attribute_value, ""));
+                let ssrc_attribute = Attribute::parse(ssrc_attribute);
+
+                self.ssrcs.push(SsrcAttribute {
+                    ssrc: ssrc
+                        .parse()
+                        .map_err(|_| parse_error(line, "invalid ssrc"))?,
+                    name: ssrc_attribute.name,
+                    value: ssrc_attribute.value,
+                });
+                self.order.push(Slot::Ssrc);
+            }
+            _ => {
+                self.attributes.push(attribute);
+                self.order.push(Slot::Attribute);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_error(line: &str, description: &str) -> SdpParseError {
+    SdpParseError {
+        line: line.to_owned(),
+        description: description.to_owned(),
+    }
+}
+
+impl Sdp {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.name == name)
+            .and_then(|a| a.value.as_deref())
+    }
+
+    /// Mids of the "a=group:BUNDLE" lines
+    pub fn bundle_groups(&self) -> impl Iterator<Item = &[String]> {
+        self.groups
+            .iter()
+            .filter(|g| g.semantics == "BUNDLE")
+            .map(|g| g.mids.as_slice())
+    }
+
+    pub fn media_by_mid(&self, mid: &str) -> Option<&MediaSection> {
+        self.media.iter().find(|m| m.mid() == Some(mid))
+    }
+
+    pub fn media_by_mid_mut(&mut self, mid: &str) -> Option<&mut MediaSection> {
+        self.media.iter_mut().find(|m| m.mid() == Some(mid))
+    }
+}
+
+impl FromStr for Sdp {
+    type Err = SdpParseError;
+
+    fn from_str(sdp: &str) -> Result<Self, Self::Err> {
+        let mut session = Sdp::default();
+
+        for line in sdp.lines().map(str::trim_end).filter(|l| !l.is_empty()) {
+            let (kind, value) = match line.split_once('=') {
+                Some((kind, value)) if kind.len() == 1 => (kind.chars().next().unwrap(), value),
+                _ => return Err(parse_error(line, "expected <type>=<value>")),
+            };
+
+            if kind == 'm' {
+                session.media.push(MediaSection::parse_mline(line, value)?);
+                continue;
+            }
+
+            match session.media.last_mut() {
+                Some(media) if kind == 'a' => media.parse_attribute(line, value)?,
+                Some(media) => media.lines.push(SdpLine {
+                    kind,
+                    value: value.to_owned(),
+                }),
+                None if kind == 'a' => {
+                    let attribute = Attribute::parse(value);
+                    match (attribute.name.as_str(), attribute.value.as_deref()) {
+                        ("group", Some(group)) => {
+                            let mut fields = group.split_whitespace();
+                            session.groups.push(Group {
+                                semantics: fields.next().unwrap_or_default().to_owned(),
+                                mids: fields.map(str::to_owned).collect(),
+                            });
+                            session.order.push(Slot::Group);
+                        }
+                        _ => {
+                            session.attributes.push(attribute);
+                            session.order.push(Slot::Attribute);
+                        }
+                    }
+                }
+                None => session.lines.push(SdpLine {
+                    kind,
+                    value: value.to_owned(),
+                }),
+            }
+        }
+
+        Ok(session)
+    }
+}
+
+impl Display for SdpLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}\r\n", self.kind, self.value)
+    }
+}
+
+impl Display for Attribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "a={}:{}\r\n", self.name, value),
+            None => write!(f, "a={}\r\n", self.name),
+        }
+    }
+}
+
+impl Display for Group {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "a=group:{}", self.semantics)?;
+        for mid in &self.mids {
+            write!(f, " {}", mid)?;
+        }
+        f.write_str("\r\n")
+    }
+}
+
+impl Display for Extmap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "a=extmap:{}", self.id)?;
+        if let Some(direction) = self.direction {
+            write!(f, "/{}", direction.as_str())?;
+        }
+        write!(f, " {}", self.uri)?;
+        if let Some(attributes) = &self.attributes {
+            write!(f, " {}", attributes)?;
+        }
+        f.write_str("\r\n")
+    }
+}
+
+impl Display for SsrcGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "a=ssrc-group:{}", self.semantics)?;
+        for ssrc in &self.ssrcs {
+            write!(f, " {}", ssrc)?;
+        }
+        f.write_str("\r\n")
+    }
+}
+
+impl Display for SsrcAttribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "a=ssrc:{} {}:{}\r\n", self.ssrc, self.name, value),
+            None => write!(f, "a=ssrc:{} {}\r\n", self.ssrc, self.name),
+        }
+    }
+}
+
+/// Lines of a codec that are already written
+struct CodecLines<'a> {
+    codec: &'a Codec,
+    rtpmap: bool,
+    rtcp_fb: usize,
+    fmtp: bool,
+}
+
+impl<'a> CodecLines<'a> {
+    fn new(codec: &'a Codec) -> Self {
+        Self {
+            codec,
+            rtpmap: false,
+            rtcp_fb: 0,
+            fmtp: false,
+        }
+    }
+
+    fn write_rtpmap(&mut self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.rtpmap || self.codec.name.is_empty() {
+            return Ok(());
+        }
+        self.rtpmap = true;
+
+        let codec = self.codec;
+        write!(
+            f,
+            "a=rtpmap:{} {}/{}",
+            codec.payload_type, codec.name, codec.clock_rate
+        )?;
+        if let Some(channels) = codec.channels {
+            write!(f, "/{}", channels)?;
+        }
+        f.write_str("\r\n")
+    }
+
+    fn write_rtcp_fb(&mut self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(rtcp_fb) = self.codec.rtcp_fb.get(self.rtcp_fb) {
+            self.rtcp_fb += 1;
+            write!(f, "a=rtcp-fb:{} {}\r\n", self.codec.payload_type, rtcp_fb)?;
+        }
+        Ok(())
+    }
+
+    fn write_fmtp(&mut self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.fmtp || self.codec.fmtp.is_empty() {
+            return Ok(());
+        }
+        self.fmtp = true;
+
+        let params: Vec<String> = self
+            .codec
+            .fmtp
+            .iter()
+            .map(|p| match &p.value {
+                Some(value) => format!("{}={}", p.name, value),
+                None => p.name.clone(),
+            })
+            .collect();
+        write!(
+            f,
+            "a=fmtp:{} {}\r\n",
+            self.codec.payload_type,
+            params.join(";")
+        )
+    }
+
+    fn write_remaining(&mut self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_rtpmap(f)?;
+        while self.rtcp_fb < self.codec.rtcp_fb.len() {
+            self.write_rtcp_fb(f)?;
+        }
+        self.write_fmtp(f)
+    }
+}
+
+impl Display for Codec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        CodecLines::new(self).write_remaining(f)
+    }
+}
+
+impl Display for MediaSection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "m={} {} {}", self.media, self.port, self.protocol)?;
+        for codec in &self.codecs {
+            write!(f, " {}", codec.payload_type)?;
+        }
+        for format in &self.formats {
+            write!(f, " {}", format)?;
+        }
+        f.write_str("\r\n")?;
+
+        // The other lines always come before the attributes
+        for line in &self.lines {
+            write!(f, "{}", line)?;
+        }
+
+        // Attributes are written in the order they were parsed, the ones added
+        // afterwards are written at the end
+        let mut attributes = self.attributes.iter();
+        let mut extmaps = self.extmaps.iter();
+        let mut direction = self.direction;
+        let mut codecs: Vec<CodecLines> = self.codecs.iter().map(CodecLines::new).collect();
+        let mut ssrc_groups = self.ssrc_groups.iter();
+        let mut ssrcs = self.ssrcs.iter();
+
+        for slot in &self.order {
+            match *slot {
+                Slot::Attribute => write_next(f, &mut attributes)?,
+                Slot::Extmap => write_next(f, &mut extmaps)?,
+                Slot::Direction => {
+                    if let Some(direction) = direction.take() {
+                        write!(f, "a={}\r\n", direction.as_str())?;
+                    }
+                }
+                Slot::Rtpmap(pt) | Slot::RtcpFb(pt) | Slot::Fmtp(pt) => {
+                    // The codec may have been removed
+                    let codec = match codecs.iter_mut().find(|c| c.codec.payload_type == pt) {
+                        Some(codec) => codec,
+                        None => continue,
+                    };
+
+                    match slot {
+                        Slot::Rtpmap(_) => codec.write_rtpmap(f)?,
+                        Slot::RtcpFb(_) => codec.write_rtcp_fb(f)?,
+                        _ => codec.write_fmtp(f)?,
+                    }
+                }
+                Slot::SsrcGroup => write_next(f, &mut ssrc_groups)?,
+                Slot::Ssrc => write_next(f, &mut ssrcs)?,
+                Slot::Group => {}
+            }
+        }
+
+        for attribute in attributes {
+            write!(f, "{}", attribute)?;
+        }
+        for extmap in extmaps {
+            write!(f, "{}", extmap)?;
+        }
+        if let Some(direction) = direction {
+            write!(f, "a={}\r\n", direction.as_str())?;
+        }
+        for codec in &mut codecs {
+            codec.write_remaining(f)?;
+        }
+        for group in ssrc_groups {
+            write!(f, "{}", group)?;
+        }
+        for ssrc in ssrcs {
+            write!(f, "{}", ssrc)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Sdp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            write!(f, "{}", line)?;
+        }
+
+        let mut groups = self.groups.iter();
+        let mut attributes = self.attributes.iter();
+        for slot in &self.order {
+            match slot {
+                Slot::Group => write_next(f, &mut groups)?,
+                _ => write_next(f, &mut attributes)?,
+            }
+        }
+        for group in groups {
+            write!(f, "{}", group)?;
+        }
+        for attribute in attributes {
+            write!(f, "{}", attribute)?;
+        }
+
+        for media in &self.media {
+            write!(f, "{}", media)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_next<T: Display>(
+    f: &mut Formatter<'_>,
+    mut items: impl Iterator<Item = T>,
+) -> fmt::Result {
+    match items.next() {
+        Some(item) => write!(f, "{}", item),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OFFER: &str = "v=0\r\n\
+        o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+        s=-\r\n\
+        t=0 0\r\n\
+        a=group:BUNDLE 0 1\r\n\
+        a=extmap-allow-mixed\r\n\
+        m=audio 9 UDP/TLS/RTP/SAVPF 111 63\r\n\
+        c=IN IP4 0.0.0.0\r\n\
+        a=mid:0\r\n\
+        a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+        a=sendrecv\r\n\
+        a=rtpmap:111 opus/48000/2\r\n\
+        a=rtcp-fb:111 transport-cc\r\n\
+        a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+        a=rtpmap:63 red/48000/2\r\n\
+        a=fmtp:63 111/111\r\n\
+        a=ssrc:1001 cname:abcd\r\n\
+        m=video 9 UDP/TLS/RTP/SAVPF 96 97 98 99\r\n\
+        c=IN IP4 0.0.0.0\r\n\
+        a=mid:1\r\n\
+        a=recvonly\r\n\
+        a=rtpmap:96 VP8/90000\r\n\
+        a=rtpmap:97 rtx/90000\r\n\
+        a=fmtp:97 apt=96\r\n\
+        a=rtpmap:98 H264/90000\r\n\
+        a=fmtp:98 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f\r\n\
+        a=rtpmap:99 rtx/90000\r\n\
+        a=fmtp:99 apt=98\r\n\
+        a=ssrc-group:FID 2001 2002\r\n";
+
+    // Firefox groups the attributes by kind
+    const FIREFOX_OFFER: &str = "v=0\r\n\
+        o=mozilla...THIS_IS_SDPARTA-99.0 1234 0 IN IP4 0.0.0.0\r\n\
+        s=-\r\n\
+        t=0 0\r\n\
+        a=fingerprint:sha-256 AB:CD\r\n\
+        a=group:BUNDLE 0\r\n\
+        a=ice-options:trickle\r\n\
+        m=video 9 UDP/TLS/RTP/SAVPF 120 124\r\n\
+        c=IN IP4 0.0.0.0\r\n\
+        a=sendrecv\r\n\
+        a=extmap:3 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+        a=fmtp:120 max-fs=12288;max-fr=60\r\n\
+        a=fmtp:124 apt=120\r\n\
+        a=mid:0\r\n\
+        a=rtcp-fb:120 nack\r\n\
+        a=rtcp-fb:120 nack pli\r\n\
+        a=rtcp-fb:* transport-cc\r\n\
+        a=rtpmap:120 VP8/90000\r\n\
+        a=rtpmap:124 rtx/90000\r\n\
+        a=ssrc:3001 cname:{abcd}\r\n\
+        a=ssrc-group:FID 3001 3002\r\n";
+
+    #[test]
+    fn serialize_keeps_line_order() {
+        for offer in [OFFER, FIREFOX_OFFER] {
+            let sdp: Sdp = offer.parse().unwrap();
+            assert_eq!(sdp.to_string(), offer);
+        }
+
+        // Modified lines stay in place, the added ones are written at the end
+        let mut sdp: Sdp = FIREFOX_OFFER.parse().unwrap();
+        let video = sdp.media_by_mid_mut("0").unwrap();
+        for vp8 in video.codecs_by_name_mut("vp8") {
+            vp8.set_fmtp_parameter("max-fr", "30");
+            vp8.rtcp_fb.push("ccm fir".to_owned());
+        }
+        video.direction = Some(Direction::RecvOnly);
+        video.attributes.push(Attribute {
+            name: "rtcp-mux".to_owned(),
+            value: None,
+        });
+
+        let expected = FIREFOX_OFFER
+            .replace("a=sendrecv", "a=recvonly")
+            .replace("max-fr=60", "max-fr=30")
+            + "a=rtcp-mux\r\na=rtcp-fb:120 ccm fir\r\n";
+        assert_eq!(sdp.to_string(), expected);
+    }
+
+    #[test]
+    fn parse_roundtrip() {
+        let sdp: Sdp = OFFER.parse().unwrap();
+        assert_eq!(sdp.bundle_groups().next().unwrap(), ["0", "1"]);
+        assert_eq!(sdp.media.len(), 2);
+
+        let audio = sdp.media_by_mid("0").unwrap();
+        let opus = audio.codecs_by_name("opus").next().unwrap();
+        assert_eq!(opus.channels, Some(2));
+        assert_eq!(opus.fmtp_parameter("useinbandfec"), Some("1"));
+        assert_eq!(audio.extmaps[0].id, 1);
+        assert_eq!(audio.ssrcs[0].value.as_deref(), Some("abcd"));
+
+        let video = sdp.media_by_mid("1").unwrap();
+        assert_eq!(video.direction, Some(Direction::RecvOnly));
+        assert_eq!(video.ssrc_groups[0].ssrcs, vec![2001, 2002]);
+
+        let reparsed: Sdp = sdp.to_string().parse().unwrap();
+        assert_eq!(sdp, reparsed);
+    }
+
+    #[test]
+    fn munge_codecs() {
+        let mut sdp: Sdp = OFFER.parse().unwrap();
+
+        let audio = sdp.media_by_mid_mut("0").unwrap();
+        for opus in audio.codecs_by_name_mut("opus") {
+            opus.set_fmtp_parameter("stereo", "1");
+        }
+        audio.set_bandwidth(128);
+
+        let video = sdp.media_by_mid_mut("1").unwrap();
+        video.prioritize_codec("h264");
+        let order: Vec<u8> = video.codecs.iter().map(|c| c.payload_type).collect();
+        assert_eq!(order, vec![98, 99, 96, 97]);
+
+        video.remove_codec("vp8");
+        assert_eq!(video.codecs.len(), 2);
+
+        let sdp = sdp.to_string();
+        assert!(sdp.contains("a=fmtp:111 minptime=10;useinbandfec=1;stereo=1\r\n"));
+        assert!(sdp.contains("b=AS:128\r\n"));
+        assert!(sdp.contains("m=video 9 UDP/TLS/RTP/SAVPF 98 99\r\n"));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::imp::session_description as sd_imp;
+use crate::sdp::Sdp;
 use std::{fmt::Debug, str::FromStr};
 use thiserror::Error;
 
@@ -44,6 +45,15 @@ impl SessionDescription {
     pub fn sdp_type(&self) -> SdpType {
         self.handle.sdp_type()
     }
+
+    /// Parse the description into an editable Sdp
+    pub fn sdp(&self) -> Result<Sdp, SdpParseError> {
+        self.to_string().parse()
+    }
+
+    pub fn from_sdp(sdp: &Sdp, sdp_type: SdpType) -> Result<Self, SdpParseError> {
+        Self::parse(&sdp.to_string(), sdp_type)
+    }
 }
 
 impl ToString for SessionDescription {
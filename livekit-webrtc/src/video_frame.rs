@@ -43,14 +43,40 @@ where
     T: AsRef<dyn VideoFrameBuffer>,
 {
     pub rotation: VideoRotation,
-    pub timestamp: i64, // When the frame was captured
+    /// When the frame was captured, in microseconds. On received frames, this is the
+    /// render time in the local clock (the same clock as `receive_time_us`).
+    /// Sources use the current time when this is 0.
+    pub timestamp: i64,
+    /// RTP timestamp of the frame (90kHz clock)
+    pub rtp_timestamp: Option<u32>,
+    /// Capture time estimated in the sender's NTP clock, in milliseconds
+    pub ntp_time_ms: Option<i64>,
+    /// When the last packet of the frame was received, in microseconds
+    pub receive_time_us: Option<i64>,
     pub buffer: T,
 }
 
+impl<T> VideoFrame<T>
+where
+    T: AsRef<dyn VideoFrameBuffer>,
+{
+    /// Size of the frame once the pending rotation is applied
+    pub fn rotated_size(&self) -> (u32, u32) {
+        let buffer = self.buffer.as_ref();
+        match self.rotation {
+            VideoRotation::VideoRotation90 | VideoRotation::VideoRotation270 => {
+                (buffer.height(), buffer.width())
+            }
+            _ => (buffer.width(), buffer.height()),
+        }
+    }
+}
+
 pub type BoxVideoFrameBuffer = Box<dyn VideoFrameBuffer>;
 pub type BoxVideoFrame = VideoFrame<BoxVideoFrameBuffer>;
 
 pub(crate) mod internal {
+    #[cfg(not(target_arch = "wasm32"))]
     use super::{I420Buffer, VideoFormatType};
 
     pub trait BufferSealed: Send + Sync {
@@ -105,6 +131,11 @@ pub trait VideoFrameBuffer: internal::BufferSealed + Debug {
     fn as_nv12(&self) -> Option<&NV12Buffer> {
         None
     }
+
+    #[cfg(target_arch = "wasm32")]
+    fn as_webgl(&self) -> Option<&web::WebGlBuffer> {
+        None
+    }
 }
 
 macro_rules! new_buffer_type {
@@ -380,21 +411,150 @@ impl NV12Buffer {
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
-    use super::{vf_imp, I420Buffer, VideoFormatType, VideoFrameBuffer, VideoFrameBufferType};
+    use super::{
+        vf_imp, I010Buffer, I420ABuffer, I420Buffer, I422Buffer, I444Buffer, NV12Buffer,
+        VideoFormatType, VideoFrame, VideoFrameBuffer, VideoFrameBufferType, VideoRotation,
+    };
     use std::fmt::Debug;
 
     pub use crate::imp::yuv_helper::ConvertError;
 
     new_buffer_type!(NativeBuffer, Native, as_native);
 
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+    pub enum ScaleFilter {
+        None, // Point sample, fastest
+        Linear,
+        Bilinear,
+        #[default]
+        Box, // Highest quality
+    }
+
     pub trait I420BufferExt {
         fn new(width: u32, height: u32) -> I420Buffer;
+        /// None if the size is empty or too large
+        fn scale(&self, width: u32, height: u32, filter: ScaleFilter) -> Option<I420Buffer>;
+        /// x and y are rounded down to even values, the size is clamped to the buffer bounds.
+        /// None if the origin is out of bounds or the size is empty
+        fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Option<I420Buffer>;
+        fn rotate(&self, rotation: VideoRotation) -> I420Buffer;
+        /// Flip horizontally
+        fn mirror(&self) -> I420Buffer;
     }
 
     impl I420BufferExt for I420Buffer {
         fn new(width: u32, height: u32) -> I420Buffer {
             vf_imp::I420Buffer::new(width, height)
         }
+
+        fn scale(&self, width: u32, height: u32, filter: ScaleFilter) -> Option<I420Buffer> {
+            self.handle.scale(width, height, filter)
+        }
+
+        fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Option<I420Buffer> {
+            self.handle.crop(x, y, width, height)
+        }
+
+        fn rotate(&self, rotation: VideoRotation) -> I420Buffer {
+            self.handle.rotate(rotation)
+        }
+
+        fn mirror(&self) -> I420Buffer {
+            self.handle.mirror()
+        }
+    }
+
+    macro_rules! buffer_ext {
+        ($ext:ident, $type:ident) => {
+            pub trait $ext: Sized {
+                /// Fails when a dimension is 0 or when the buffer would be too large
+                fn new(width: u32, height: u32) -> Result<Self, ConvertError>;
+            }
+
+            impl $ext for $type {
+                fn new(width: u32, height: u32) -> Result<Self, ConvertError> {
+                    vf_imp::$type::new(width, height)
+                }
+            }
+        };
+    }
+
+    buffer_ext!(I420ABufferExt, I420ABuffer);
+    buffer_ext!(I422BufferExt, I422Buffer);
+    buffer_ext!(I444BufferExt, I444Buffer);
+    buffer_ext!(I010BufferExt, I010Buffer);
+    buffer_ext!(NV12BufferExt, NV12Buffer);
+
+    macro_rules! new_buffer_pool {
+        ($pool:ident, $type:ident) => {
+            /// Recycle the buffers once they're released by their consumers (e.g the encoder)
+            /// instead of allocating a new buffer for each frame.
+            /// The reused buffers aren't cleared.
+            #[derive(Clone)]
+            pub struct $pool {
+                handle: vf_imp::$pool,
+            }
+
+            impl $pool {
+                pub fn new(max_number_of_buffers: usize) -> Self {
+                    Self {
+                        handle: vf_imp::$pool::new(max_number_of_buffers),
+                    }
+                }
+
+                /// Returns None if all the buffers are in use
+                pub fn get(&self, width: u32, height: u32) -> Option<$type> {
+                    self.handle.create(width, height)
+                }
+
+                /// Drop the buffers that aren't in use
+                pub fn release(&self) {
+                    self.handle.release()
+                }
+
+                /// Returns false if more buffers than the new maximum are currently in use
+                pub fn resize(&self, max_number_of_buffers: usize) -> bool {
+                    self.handle.resize(max_number_of_buffers)
+                }
+            }
+
+            impl Debug for $pool {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_struct(stringify!($pool)).finish()
+                }
+            }
+        };
+    }
+
+    new_buffer_pool!(I420BufferPool, I420Buffer);
+    new_buffer_pool!(I422BufferPool, I422Buffer);
+    new_buffer_pool!(I444BufferPool, I444Buffer);
+    new_buffer_pool!(I010BufferPool, I010Buffer);
+    new_buffer_pool!(NV12BufferPool, NV12Buffer);
+
+    pub trait VideoFrameExt {
+        /// Convert the buffer to I420 and apply the pending rotation, the returned frame has
+        /// a rotation of VideoRotation0
+        fn to_upright_i420(&self) -> VideoFrame<I420Buffer>;
+    }
+
+    impl<T: AsRef<dyn VideoFrameBuffer>> VideoFrameExt for VideoFrame<T> {
+        fn to_upright_i420(&self) -> VideoFrame<I420Buffer> {
+            let i420 = self.buffer.as_ref().to_i420();
+            let buffer = match self.rotation {
+                VideoRotation::VideoRotation0 => i420,
+                rotation => i420.rotate(rotation),
+            };
+
+            VideoFrame {
+                rotation: VideoRotation::VideoRotation0,
+                timestamp: self.timestamp,
+                rtp_timestamp: self.rtp_timestamp,
+                ntp_time_ms: self.ntp_time_ms,
+                receive_time_us: self.receive_time_us,
+                buffer,
+            }
+        }
     }
 
     pub trait VideoFrameBufferExt: VideoFrameBuffer {
@@ -429,10 +589,46 @@ pub mod native {
 
 #[cfg(target_arch = "wasm32")]
 pub mod web {
-    use super::VideoFrameBuffer;
+    use super::{
+        vf_imp, I010Buffer, I420Buffer, I422Buffer, I444Buffer, NV12Buffer, VideoFrameBuffer,
+        VideoFrameBufferType,
+    };
+    use std::fmt::Debug;
+    use web_sys::{WebGlRenderingContext, WebGlTexture};
+
+    new_buffer_type!(WebGlBuffer, WebGl, as_webgl);
+
+    impl WebGlBuffer {
+        /// Context the texture belongs to (the one given to WebGlVideoStream)
+        pub fn context(&self) -> WebGlRenderingContext {
+            self.handle.context()
+        }
+
+        /// RGBA texture holding the frame, it is overwritten by the next frame
+        pub fn texture(&self) -> WebGlTexture {
+            self.handle.texture()
+        }
+    }
+
+    macro_rules! buffer_ext {
+        ($ext:ident, $type:ident) => {
+            pub trait $ext {
+                fn new(width: u32, height: u32) -> $type;
+            }
 
-    #[derive(Debug)]
-    pub struct WebGlBuffer {}
+            impl $ext for $type {
+                fn new(width: u32, height: u32) -> $type {
+                    $type {
+                        handle: vf_imp::$type::new(width, height),
+                    }
+                }
+            }
+        };
+    }
 
-    impl VideoFrameBuffer for WebGlBuffer {}
+    buffer_ext!(I420BufferExt, I420Buffer);
+    buffer_ext!(I422BufferExt, I422Buffer);
+    buffer_ext!(I444BufferExt, I444Buffer);
+    buffer_ext!(I010BufferExt, I010Buffer);
+    buffer_ext!(NV12BufferExt, NV12Buffer);
 }
@@ -5,6 +5,7 @@ pub mod native {
     use super::vs_imp;
     use crate::video_frame::{VideoFrame, VideoFrameBuffer};
     use std::fmt::{Debug, Formatter};
+    use tokio::sync::watch;
 
     #[derive(Default, Clone)]
     pub struct NativeVideoSource {
@@ -18,11 +19,120 @@ pub mod native {
     }
 
     impl NativeVideoSource {
+        /// Aggregated wants of the sinks consuming this source (e.g the encoders)
+        pub fn sink_wants(&self) -> VideoSinkWants {
+            self.handle.sink_wants()
+        }
+
+        /// Get notified when the sink wants change, e.g when the encoder is limited by the CPU
+        /// or the bandwidth. The frames can then be downscaled or dropped before being captured.
+        pub fn subscribe_sink_wants(&self) -> watch::Receiver<VideoSinkWants> {
+            self.handle.subscribe_sink_wants()
+        }
+
+        /// Frames exceeding the sink wants are still adapted by libwebrtc
         pub fn capture_frame<T: AsRef<dyn VideoFrameBuffer>>(&self, frame: &VideoFrame<T>) {
             self.handle.capture_frame(frame)
         }
     }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct VideoSinkWants {
+        pub max_pixel_count: i32, // i32::MAX when unconstrained
+        pub target_pixel_count: Option<i32>,
+        pub max_framerate_fps: i32, // i32::MAX when unconstrained
+        pub resolution_alignment: i32,
+        pub rotation_applied: bool,
+        pub black_frames: bool,
+    }
+
+    impl Default for VideoSinkWants {
+        fn default() -> Self {
+            Self {
+                max_pixel_count: i32::MAX,
+                target_pixel_count: None,
+                max_framerate_fps: i32::MAX,
+                resolution_alignment: 1,
+                rotation_applied: false,
+                black_frames: false,
+            }
+        }
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum EncodedVideoCodec {
+        VP8,
+        VP9,
+        H264,
+        AV1,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct EncodedVideoFrameInfo {
+        pub codec: EncodedVideoCodec,
+        pub width: u32,
+        pub height: u32,
+        pub keyframe: bool,
+        pub timestamp_us: i64, // When the frame was captured
+    }
+
+    pub type OnKeyframeRequest = Box<dyn FnMut() + Send + Sync>;
+
+    /// Publish already encoded frames, they are packetized without being re-encoded.
+    /// The codec of the frames must match the negotiated codec of the track (see
+    /// TrackPublishOptions::video_codec) and simulcast must be disabled.
+    #[derive(Default, Clone)]
+    pub struct EncodedVideoSource {
+        pub(crate) handle: vs_imp::EncodedVideoSource,
+    }
+
+    impl Debug for EncodedVideoSource {
+        fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+            f.debug_struct("EncodedVideoSource").finish()
+        }
+    }
+
+    impl EncodedVideoSource {
+        /// Returns false if the frame was rejected (e.g unsupported codec)
+        pub fn capture_frame(&self, data: &[u8], info: &EncodedVideoFrameInfo) -> bool {
+            self.handle.capture_frame(data, info)
+        }
+
+        /// Called when the encoder needs a keyframe (e.g a subscriber lost packets), the next
+        /// captured frame should be a keyframe
+        pub fn on_keyframe_request(&self, callback: Option<OnKeyframeRequest>) {
+            self.handle.on_keyframe_request(callback)
+        }
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
-pub mod web {}
+pub mod web {
+    use super::vs_imp;
+    use std::fmt::{Debug, Formatter};
+
+    /// Video track captured by the browser (e.g getUserMedia, getDisplayMedia or
+    /// HTMLCanvasElement.captureStream)
+    #[derive(Clone)]
+    pub struct WebVideoSource {
+        pub(crate) handle: vs_imp::WebVideoSource,
+    }
+
+    impl Debug for WebVideoSource {
+        fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+            f.debug_struct("WebVideoSource").finish()
+        }
+    }
+
+    impl WebVideoSource {
+        pub fn new(track: web_sys::MediaStreamTrack) -> Self {
+            Self {
+                handle: vs_imp::WebVideoSource::new(track),
+            }
+        }
+
+        pub fn track(&self) -> web_sys::MediaStreamTrack {
+            self.handle.sys_handle()
+        }
+    }
+}
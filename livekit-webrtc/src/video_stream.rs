@@ -6,6 +6,7 @@ use crate::imp::video_stream as stream_imp;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native {
     use super::stream_imp;
+    use crate::rtp_receiver::RtpReceiver;
     use crate::video_frame::BoxVideoFrame;
     use crate::video_track::RtcVideoTrack;
     use futures::stream::Stream;
@@ -48,7 +49,103 @@ pub mod native {
             Pin::new(&mut self.get_mut().handle).poll_next(cx)
         }
     }
+
+    #[derive(Debug, Clone)]
+    pub struct EncodedVideoFrame {
+        pub data: Vec<u8>,
+        pub mime_type: String, // e.g "video/H264"
+        pub rtp_timestamp: u32,
+        pub ssrc: u32,
+        pub keyframe: bool,
+        pub width: u32,
+        pub height: u32,
+    }
+
+    /// Yield a copy of the depacketized frames of a receiver. The frames are still
+    /// decoded, a NativeVideoStream on the same track keeps receiving them.
+    pub struct NativeEncodedVideoStream {
+        pub(crate) handle: stream_imp::NativeEncodedVideoStream,
+    }
+
+    impl Debug for NativeEncodedVideoStream {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_struct("NativeEncodedVideoStream")
+                .field("receiver", &self.receiver())
+                .finish()
+        }
+    }
+
+    impl NativeEncodedVideoStream {
+        pub fn new(receiver: RtpReceiver) -> Self {
+            Self {
+                handle: stream_imp::NativeEncodedVideoStream::new(receiver),
+            }
+        }
+
+        pub fn receiver(&self) -> RtpReceiver {
+            self.handle.receiver()
+        }
+
+        pub fn close(&mut self) {
+            self.handle.close();
+        }
+    }
+
+    impl Stream for NativeEncodedVideoStream {
+        type Item = EncodedVideoFrame;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.get_mut().handle).poll_next(cx)
+        }
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
-pub mod web {}
+pub mod web {
+    use super::stream_imp;
+    use crate::video_frame::web::WebGlBuffer;
+    use crate::video_frame::VideoFrame;
+    use crate::video_track::RtcVideoTrack;
+    use futures::stream::Stream;
+    use std::fmt::Debug;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use web_sys::WebGlRenderingContext;
+
+    /// Render the frames of a track to a texture of the given WebGL context
+    pub struct WebGlVideoStream {
+        pub(crate) handle: stream_imp::WebGlVideoStream,
+    }
+
+    impl Debug for WebGlVideoStream {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_struct("WebGlVideoStream")
+                .field("track", &self.track())
+                .finish()
+        }
+    }
+
+    impl WebGlVideoStream {
+        pub fn new(video_track: RtcVideoTrack, gl: WebGlRenderingContext) -> Self {
+            Self {
+                handle: stream_imp::WebGlVideoStream::new(video_track, gl),
+            }
+        }
+
+        pub fn track(&self) -> RtcVideoTrack {
+            self.handle.track()
+        }
+
+        pub fn close(&mut self) {
+            self.handle.close();
+        }
+    }
+
+    impl Stream for WebGlVideoStream {
+        type Item = VideoFrame<WebGlBuffer>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.get_mut().handle).poll_next(cx)
+        }
+    }
+}
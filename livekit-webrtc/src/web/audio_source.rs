@@ -0,0 +1,49 @@
+use super::{call, get, impl_thread_safety, set};
+use crate::audio_source::AudioSourceOptions;
+use js_sys::Object;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+
+/// Track captured by the browser (e.g getUserMedia), the AudioSourceOptions are applied
+/// by the browser using the track constraints
+#[derive(Clone)]
+pub struct WebAudioSource {
+    sys_handle: web_sys::MediaStreamTrack,
+}
+
+impl_thread_safety!(WebAudioSource);
+
+impl WebAudioSource {
+    pub fn new(sys_handle: web_sys::MediaStreamTrack) -> Self {
+        Self { sys_handle }
+    }
+
+    pub fn sys_handle(&self) -> web_sys::MediaStreamTrack {
+        self.sys_handle.clone()
+    }
+
+    pub fn audio_options(&self) -> AudioSourceOptions {
+        let settings = call(&self.sys_handle, "getSettings", &[]).unwrap_or(JsValue::UNDEFINED);
+        AudioSourceOptions {
+            echo_cancellation: get(&settings, "echoCancellation").is_truthy(),
+            noise_suppression: get(&settings, "noiseSuppression").is_truthy(),
+            auto_gain_control: get(&settings, "autoGainControl").is_truthy(),
+        }
+    }
+
+    pub fn set_audio_options(&self, options: AudioSourceOptions) {
+        let constraints = Object::new();
+        set(&constraints, "echoCancellation", options.echo_cancellation);
+        set(&constraints, "noiseSuppression", options.noise_suppression);
+        set(&constraints, "autoGainControl", options.auto_gain_control);
+
+        match call(&self.sys_handle, "applyConstraints", &[constraints.into()]) {
+            Ok(promise) => wasm_bindgen_futures::spawn_local(async move {
+                if let Err(err) = JsFuture::from(js_sys::Promise::from(promise)).await {
+                    log::error!("failed to apply the audio options: {:?}", err);
+                }
+            }),
+            Err(err) => log::error!("failed to apply the audio options: {:?}", err),
+        }
+    }
+}
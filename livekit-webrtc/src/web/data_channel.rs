@@ -1,12 +1,12 @@
-use core::str;
-use std::cell::RefCell;
-use std::rc::Rc;
-use wasm_bindgen::prelude::*;
-use web_sys::{MessageEvent, RtcDataChannelEvent, RtcDataChannelState};
-
+use super::{impl_thread_safety, set_handler};
 use crate::data_channel::{
-    DataChannelError, DataChannelTrait, DataState, OnBufferedAmountChange, OnMessage, OnStateChange,
+    DataBuffer, DataChannelError, DataChannelInit, DataState, OnBufferedAmountChange, OnMessage,
+    OnStateChange,
 };
+use js_sys::{ArrayBuffer, Uint8Array};
+use std::str;
+use wasm_bindgen::prelude::*;
+use web_sys::{MessageEvent, RtcDataChannelInit, RtcDataChannelState, RtcDataChannelType};
 
 impl From<RtcDataChannelState> for DataState {
     fn from(value: RtcDataChannelState) -> Self {
@@ -20,14 +20,41 @@ impl From<RtcDataChannelState> for DataState {
     }
 }
 
+impl From<DataChannelInit> for RtcDataChannelInit {
+    fn from(value: DataChannelInit) -> Self {
+        let init = RtcDataChannelInit::new();
+        init.set_ordered(value.ordered);
+        if let Some(max_retransmit_time) = value.max_retransmit_time {
+            init.set_max_packet_life_time(max_retransmit_time as u16);
+        }
+        if let Some(max_retransmits) = value.max_retransmits {
+            init.set_max_retransmits(max_retransmits as u16);
+        }
+        init.set_protocol(&value.protocol);
+        init.set_negotiated(value.negotiated);
+        if value.id >= 0 {
+            init.set_id(value.id as u16);
+        }
+        init
+    }
+}
+
 #[derive(Clone)]
 pub struct DataChannel {
-    sys_handle: web_sys::RtcDataChannel,
-    on_closing: Rc<RefCell<Option<JsValue>>>,
+    pub(crate) sys_handle: web_sys::RtcDataChannel,
 }
 
-impl DataChannelTrait for DataChannel {
-    fn send(&self, data: &[u8], binary: bool) -> Result<(), DataChannelError> {
+impl_thread_safety!(DataChannel);
+
+impl DataChannel {
+    pub fn configure(sys_handle: web_sys::RtcDataChannel) -> Self {
+        // Receive the binary messages as ArrayBuffer instead of Blob (which can only be read
+        // asynchronously)
+        sys_handle.set_binary_type(RtcDataChannelType::Arraybuffer);
+        Self { sys_handle }
+    }
+
+    pub fn send(&self, data: &[u8], binary: bool) -> Result<(), DataChannelError> {
         if binary {
             self.sys_handle
                 .send_with_u8_array(data)
@@ -40,64 +67,221 @@ impl DataChannelTrait for DataChannel {
         }
     }
 
-    fn label(&self) -> String {
+    pub fn label(&self) -> String {
         self.sys_handle.label()
     }
 
-    fn state(&self) -> DataState {
+    pub fn state(&self) -> DataState {
         self.sys_handle.ready_state().into()
     }
 
-    fn close(&self) {
+    pub fn buffered_amount(&self) -> u64 {
+        self.sys_handle.buffered_amount() as u64
+    }
+
+    pub fn close(&self) {
         self.sys_handle.close();
     }
 
-    fn on_state_change(&self, callback: Option<OnStateChange>) {
-        if let Some(mut callback) = callback {
-            let dc = self.clone();
-            let js_callback = Closure::new(move |_: RtcDataChannelEvent| {
-                callback(dc.state());
-            });
-            let js_callback = js_callback.into_js_value();
-            self.sys_handle
-                .set_onopen(Some(js_callback.unchecked_ref()));
-            self.sys_handle
-                .set_onclose(Some(js_callback.unchecked_ref()));
-            self.sys_handle
-                .add_event_listener_with_callback("closing", js_callback.unchecked_ref())
-                .unwrap();
+    pub fn on_state_change(&self, handler: Option<OnStateChange>) {
+        let js_handler = handler.map(|mut handler| {
+            let sys_handle = self.sys_handle.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                handler(sys_handle.ready_state().into());
+            })
+            .into_js_value()
+        });
 
-            self.on_closing.replace(Some(js_callback));
-        } else {
-            self.sys_handle.set_onopen(None);
-            self.sys_handle.set_onclose(None);
-            if let Some(on_closing) = self.on_closing.take() {
-                self.sys_handle
-                    .remove_event_listener_with_callback("closing", on_closing.unchecked_ref())
-                    .unwrap();
-            }
-            self.on_closing.replace(None);
+        for name in ["onopen", "onclosing", "onclose"] {
+            set_handler(&self.sys_handle, name, js_handler.clone());
         }
     }
 
-    fn on_message(&self, callback: Option<OnMessage>) {
-        let js_callback = callback.map(|mut callback| {
-            Closure::new(move |event: MessageEvent| {
-                if let Some(str) = event.as_string() {
-                    callback(str.as_bytes(), false);
+    pub fn on_message(&self, handler: Option<OnMessage>) {
+        let js_handler = handler.map(|mut handler| {
+            Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                let data = event.data();
+                if let Some(text) = data.as_string() {
+                    handler(DataBuffer {
+                        data: text.as_bytes(),
+                        binary: false,
+                    });
+                } else if let Some(buffer) = data.dyn_ref::<ArrayBuffer>() {
+                    handler(DataBuffer {
+                        data: &Uint8Array::new(buffer).to_vec(),
+                        binary: true,
+                    });
                 }
             })
             .into_js_value()
         });
 
-        self.sys_handle.set_onmessage(
-            js_callback
-                .as_ref()
-                .map(|callback| callback.unchecked_ref()),
+        set_handler(&self.sys_handle, "onmessage", js_handler);
+    }
+
+    /// Browsers only notify when the buffered amount drops to bufferedAmountLowThreshold,
+    /// the threshold is set to 0 so the handler is called once the queue is drained
+    pub fn on_buffered_amount_change(&self, handler: Option<OnBufferedAmountChange>) {
+        let js_handler = handler.map(|mut handler| {
+            self.sys_handle.set_buffered_amount_low_threshold(0);
+            let sys_handle = self.sys_handle.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                handler(sys_handle.buffered_amount() as u64);
+            })
+            .into_js_value()
+        });
+
+        set_handler(&self.sys_handle, "onbufferedamountlow", js_handler);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use futures::channel::mpsc;
+    use futures::StreamExt;
+    use wasm_bindgen_futures::spawn_local;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Connect two local PeerConnections and wait for the DataChannel to be open on both sides
+    async fn connect(
+        factory: &PeerConnectionFactory,
+    ) -> (PeerConnection, PeerConnection, DataChannel, DataChannel) {
+        let bob = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+        let alice = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+
+        let (bob_ice_tx, mut bob_ice_rx) = mpsc::unbounded::<IceCandidate>();
+        let (alice_ice_tx, mut alice_ice_rx) = mpsc::unbounded::<IceCandidate>();
+        let (alice_dc_tx, mut alice_dc_rx) = mpsc::unbounded::<DataChannel>();
+        bob.on_ice_candidate(Some(Box::new(move |candidate| {
+            let _ = bob_ice_tx.unbounded_send(candidate);
+        })));
+        alice.on_ice_candidate(Some(Box::new(move |candidate| {
+            let _ = alice_ice_tx.unbounded_send(candidate);
+        })));
+        alice.on_data_channel(Some(Box::new(move |dc| {
+            let _ = alice_dc_tx.unbounded_send(dc);
+        })));
+
+        let bob_dc = bob
+            .create_data_channel("test_dc", DataChannelInit::default())
+            .unwrap();
+        let (open_tx, mut open_rx) = mpsc::unbounded::<DataState>();
+        bob_dc.on_state_change(Some(Box::new(move |state| {
+            let _ = open_tx.unbounded_send(state);
+        })));
+
+        let offer = bob.create_offer(OfferOptions::default()).await.unwrap();
+        bob.set_local_description(offer.clone()).await.unwrap();
+        alice.set_remote_description(offer).await.unwrap();
+        let answer = alice.create_answer(AnswerOptions::default()).await.unwrap();
+        alice.set_local_description(answer.clone()).await.unwrap();
+        bob.set_remote_description(answer).await.unwrap();
+
+        spawn_local({
+            let alice = alice.clone();
+            async move {
+                while let Some(candidate) = bob_ice_rx.next().await {
+                    let _ = alice.add_ice_candidate(candidate).await;
+                }
+            }
+        });
+        spawn_local({
+            let bob = bob.clone();
+            async move {
+                while let Some(candidate) = alice_ice_rx.next().await {
+                    let _ = bob.add_ice_candidate(candidate).await;
+                }
+            }
+        });
+
+        let alice_dc = alice_dc_rx.next().await.unwrap();
+        while bob_dc.state() != DataState::Open {
+            open_rx.next().await.unwrap();
+        }
+        bob_dc.on_state_change(None);
+
+        (bob, alice, bob_dc, alice_dc)
+    }
+
+    #[wasm_bindgen_test]
+    async fn send_and_receive() {
+        let factory = PeerConnectionFactory::default();
+        let (bob, alice, bob_dc, alice_dc) = connect(&factory).await;
+        assert_eq!(alice_dc.label(), "test_dc");
+        assert_eq!(alice_dc.state(), DataState::Open);
+
+        let (data_tx, mut data_rx) = mpsc::unbounded::<(Vec<u8>, bool)>();
+        alice_dc.on_message(Some(Box::new(move |buffer| {
+            let _ = data_tx.unbounded_send((buffer.data.to_vec(), buffer.binary));
+        })));
+
+        bob_dc.send(b"binary", true).unwrap();
+        bob_dc.send("text é".as_bytes(), false).unwrap();
+        assert!(matches!(
+            bob_dc.send(&[0xff], false),
+            Err(DataChannelError::Utf8(_))
+        ));
+
+        assert_eq!(data_rx.next().await.unwrap(), (b"binary".to_vec(), true));
+        assert_eq!(
+            data_rx.next().await.unwrap(),
+            ("text é".as_bytes().to_vec(), false)
         );
+
+        alice.close();
+        bob.close();
     }
 
-    fn on_buffered_amount_change(&self, _callback: Option<OnBufferedAmountChange>) {
-        todo!("onbufferedamountlow instead?")
+    #[wasm_bindgen_test]
+    async fn buffered_amount_drained() {
+        let factory = PeerConnectionFactory::default();
+        let (bob, alice, bob_dc, _alice_dc) = connect(&factory).await;
+
+        let (amount_tx, mut amount_rx) = mpsc::unbounded::<u64>();
+        bob_dc.on_buffered_amount_change(Some(Box::new(move |amount| {
+            let _ = amount_tx.unbounded_send(amount);
+        })));
+
+        bob_dc.send(&vec![0; 64 * 1024], true).unwrap();
+        assert_eq!(amount_rx.next().await.unwrap(), 0);
+        assert_eq!(bob_dc.buffered_amount(), 0);
+
+        alice.close();
+        bob.close();
+    }
+
+    #[wasm_bindgen_test]
+    async fn close() {
+        let factory = PeerConnectionFactory::default();
+        let (bob, alice, bob_dc, alice_dc) = connect(&factory).await;
+
+        let (state_tx, mut state_rx) = mpsc::unbounded::<DataState>();
+        alice_dc.on_state_change(Some(Box::new(move |state| {
+            let _ = state_tx.unbounded_send(state);
+        })));
+
+        bob_dc.close();
+        assert!(matches!(
+            bob_dc.state(),
+            DataState::Closing | DataState::Closed
+        ));
+        assert!(matches!(
+            bob_dc.send(b"test", true),
+            Err(DataChannelError::Send)
+        ));
+
+        // The remote side is notified
+        while state_rx.next().await.unwrap() != DataState::Closed {}
+        assert_eq!(alice_dc.state(), DataState::Closed);
+
+        alice.close();
+        bob.close();
     }
 }
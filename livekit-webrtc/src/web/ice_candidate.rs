@@ -0,0 +1,252 @@
+use super::impl_thread_safety;
+use crate::ice_candidate::{self as ic, Candidate, CandidateType, TcpType};
+use crate::session_description::SdpParseError;
+use web_sys::{RtcIceCandidate, RtcIceCandidateInit};
+
+#[derive(Clone)]
+pub struct IceCandidate {
+    sdp_mid: String,
+    sdp_mline_index: i32,
+    sdp: String,
+    candidate: Candidate,
+}
+
+impl_thread_safety!(IceCandidate);
+
+impl IceCandidate {
+    pub fn parse(
+        sdp_mid: &str,
+        sdp_mline_index: i32,
+        sdp: &str,
+    ) -> Result<ic::IceCandidate, SdpParseError> {
+        Ok(ic::IceCandidate {
+            handle: IceCandidate {
+                sdp_mid: sdp_mid.to_owned(),
+                sdp_mline_index,
+                sdp: sdp.to_owned(),
+                candidate: parse_candidate(sdp)?,
+            },
+        })
+    }
+
+    pub fn from_js(candidate: &RtcIceCandidate) -> Result<ic::IceCandidate, SdpParseError> {
+        Self::parse(
+            &candidate.sdp_mid().unwrap_or_default(),
+            candidate.sdp_m_line_index().map_or(-1, i32::from),
+            &candidate.candidate(),
+        )
+    }
+
+    pub fn sys_handle(&self) -> RtcIceCandidateInit {
+        let init = RtcIceCandidateInit::new(&self.sdp);
+        init.set_sdp_mid(Some(&self.sdp_mid));
+        if self.sdp_mline_index >= 0 {
+            init.set_sdp_m_line_index(Some(self.sdp_mline_index as u16));
+        }
+        init
+    }
+
+    pub fn sdp_mid(&self) -> String {
+        self.sdp_mid.clone()
+    }
+
+    pub fn sdp_mline_index(&self) -> i32 {
+        self.sdp_mline_index
+    }
+
+    pub fn candidate(&self) -> Candidate {
+        self.candidate.clone()
+    }
+}
+
+impl ToString for IceCandidate {
+    fn to_string(&self) -> String {
+        self.sdp.clone()
+    }
+}
+
+/// candidate:<foundation> <component> <protocol> <priority> <address> <port> typ <type>
+/// [raddr <address>] [rport <port>] [tcptype <type>] *(<name> <value>)
+fn parse_candidate(sdp: &str) -> Result<Candidate, SdpParseError> {
+    let parse_error = |description: &str| SdpParseError {
+        line: sdp.to_owned(),
+        description: description.to_owned(),
+    };
+
+    let value = sdp.trim_start_matches("a=");
+    let value = match value.strip_prefix("candidate:") {
+        Some(value) => value,
+        None => return Err(parse_error("expected candidate:")),
+    };
+
+    let fields: Vec<&str> = value.split_whitespace().collect();
+    if fields.len() < 8 || fields[6] != "typ" {
+        return Err(parse_error("expected at least 8 fields"));
+    }
+
+    let mut candidate = Candidate {
+        foundation: fields[0].to_owned(),
+        component: fields[1]
+            .parse()
+            .map_err(|_| parse_error("invalid component"))?,
+        protocol: fields[2].to_lowercase(),
+        priority: fields[3]
+            .parse()
+            .map_err(|_| parse_error("invalid priority"))?,
+        address: fields[4].to_owned(),
+        port: fields[5].parse().map_err(|_| parse_error("invalid port"))?,
+        candidate_type: match fields[7] {
+            "host" => Some(CandidateType::Host),
+            "srflx" => Some(CandidateType::Srflx),
+            "prflx" => Some(CandidateType::Prflx),
+            "relay" => Some(CandidateType::Relay),
+            _ => None,
+        },
+        related_address: None,
+        related_port: None,
+        tcp_type: None,
+    };
+
+    for pair in fields[8..].chunks(2) {
+        let (name, value) = match pair {
+            [name, value] => (*name, *value),
+            _ => break,
+        };
+
+        match name {
+            "raddr" => candidate.related_address = Some(value.to_owned()),
+            "rport" => {
+                candidate.related_port =
+                    Some(value.parse().map_err(|_| parse_error("invalid rport"))?)
+            }
+            "tcptype" => {
+                candidate.tcp_type = match value {
+                    "active" => Some(TcpType::Active),
+                    "passive" => Some(TcpType::Passive),
+                    "so" => Some(TcpType::SimultaneousOpen),
+                    _ => return Err(parse_error("unknown tcptype")),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IceCandidate;
+    use crate::prelude::*;
+    use futures::channel::mpsc;
+    use futures::StreamExt;
+    use wasm_bindgen_test::*;
+    use web_sys::RtcIceCandidate;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn parse_host() {
+        let candidate = IceCandidate::parse(
+            "0",
+            0,
+            "candidate:842163049 1 UDP 2122260223 192.168.1.2 54400 typ host generation 0",
+        )
+        .unwrap()
+        .candidate();
+
+        assert_eq!(candidate.foundation, "842163049");
+        assert_eq!(candidate.component, 1);
+        assert_eq!(candidate.protocol, "udp");
+        assert_eq!(candidate.priority, 2122260223);
+        assert_eq!(candidate.address, "192.168.1.2");
+        assert_eq!(candidate.port, 54400);
+        assert_eq!(candidate.candidate_type, Some(CandidateType::Host));
+        assert_eq!(candidate.related_address, None);
+        assert_eq!(candidate.tcp_type, None);
+    }
+
+    #[wasm_bindgen_test]
+    fn parse_attributes() {
+        let candidate = IceCandidate::parse(
+            "0",
+            0,
+            "a=candidate:1 1 udp 1686052607 203.0.113.4 61665 typ srflx raddr 10.0.0.2 rport 54400",
+        )
+        .unwrap()
+        .candidate();
+        assert_eq!(candidate.candidate_type, Some(CandidateType::Srflx));
+        assert_eq!(candidate.related_address.as_deref(), Some("10.0.0.2"));
+        assert_eq!(candidate.related_port, Some(54400));
+
+        let candidate = IceCandidate::parse(
+            "0",
+            0,
+            "candidate:2 1 tcp 1518280447 192.168.1.2 9 typ host tcptype active",
+        )
+        .unwrap()
+        .candidate();
+        assert_eq!(candidate.protocol, "tcp");
+        assert_eq!(candidate.tcp_type, Some(TcpType::Active));
+
+        let candidate = IceCandidate::parse(
+            "0",
+            0,
+            "candidate:3 1 udp 41885439 198.51.100.7 3478 typ unknown",
+        )
+        .unwrap()
+        .candidate();
+        assert_eq!(candidate.candidate_type, None);
+    }
+
+    #[wasm_bindgen_test]
+    fn parse_invalid() {
+        for sdp in [
+            "a=ice-ufrag:abcd",
+            "candidate:1 1 udp 2122260223 192.168.1.2 54400",
+            "candidate:1 1 udp 2122260223 192.168.1.2 54400 host",
+            "candidate:1 1 udp 2122260223 192.168.1.2 port typ host",
+            "candidate:1 1 tcp 1518280447 192.168.1.2 9 typ host tcptype foo",
+        ] {
+            assert!(IceCandidate::parse("0", 0, sdp).is_err(), "{}", sdp);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn gathered_candidates() {
+        let factory = PeerConnectionFactory::default();
+        let pc = factory
+            .create_peer_connection(RtcConfiguration::default())
+            .unwrap();
+
+        let (ice_tx, mut ice_rx) = mpsc::unbounded::<crate::ice_candidate::IceCandidate>();
+        pc.on_ice_candidate(Some(Box::new(move |candidate| {
+            let _ = ice_tx.unbounded_send(candidate);
+        })));
+
+        let _dc = pc
+            .create_data_channel("test_dc", DataChannelInit::default())
+            .unwrap();
+        let offer = pc.create_offer(OfferOptions::default()).await.unwrap();
+        pc.set_local_description(offer).await.unwrap();
+
+        // The browser only gathers host candidates without ICE servers, their address may be
+        // a mDNS hostname
+        let ice_candidate = ice_rx.next().await.unwrap();
+        let candidate = ice_candidate.candidate();
+        assert_eq!(ice_candidate.sdp_mid(), "0");
+        assert_eq!(ice_candidate.sdp_mline_index(), 0);
+        assert_eq!(candidate.component, 1);
+        assert_eq!(candidate.candidate_type, Some(CandidateType::Host));
+        assert!(!candidate.address.is_empty());
+
+        // Round trip through the browser
+        let js_candidate = RtcIceCandidate::new(&ice_candidate.handle.sys_handle()).unwrap();
+        let parsed = IceCandidate::from_js(&js_candidate).unwrap();
+        assert_eq!(parsed.sdp_mid(), ice_candidate.sdp_mid());
+        assert_eq!(parsed.sdp_mline_index(), ice_candidate.sdp_mline_index());
+        assert_eq!(parsed.candidate(), candidate);
+
+        pc.close();
+    }
+}
@@ -0,0 +1,56 @@
+use crate::audio_track;
+use crate::imp::audio_track::RtcAudioTrack;
+use crate::imp::video_track::RtcVideoTrack;
+use crate::media_stream_track::MediaStreamTrack;
+use crate::media_stream_track::RtcTrackState;
+use crate::video_track;
+use web_sys::MediaStreamTrackState;
+
+impl From<MediaStreamTrackState> for RtcTrackState {
+    fn from(state: MediaStreamTrackState) -> Self {
+        match state {
+            MediaStreamTrackState::Live => RtcTrackState::Live,
+            MediaStreamTrackState::Ended => RtcTrackState::Ended,
+            _ => panic!("unknown TrackState"),
+        }
+    }
+}
+
+pub fn new_media_stream_track(sys_handle: web_sys::MediaStreamTrack) -> MediaStreamTrack {
+    match sys_handle.kind().as_str() {
+        "audio" => MediaStreamTrack::Audio(audio_track::RtcAudioTrack {
+            handle: RtcAudioTrack { sys_handle },
+        }),
+        "video" => MediaStreamTrack::Video(video_track::RtcVideoTrack {
+            handle: RtcVideoTrack { sys_handle },
+        }),
+        _ => panic!("unknown track kind"),
+    }
+}
+
+macro_rules! impl_media_stream_track {
+    () => {
+        pub fn id(&self) -> String {
+            self.sys_handle.id()
+        }
+
+        pub fn enabled(&self) -> bool {
+            self.sys_handle.enabled()
+        }
+
+        pub fn set_enabled(&self, enabled: bool) -> bool {
+            self.sys_handle.set_enabled(enabled);
+            true
+        }
+
+        pub fn state(&self) -> RtcTrackState {
+            self.sys_handle.ready_state().into()
+        }
+
+        pub fn sys_handle(&self) -> web_sys::MediaStreamTrack {
+            self.sys_handle.clone()
+        }
+    };
+}
+
+pub(super) use impl_media_stream_track;
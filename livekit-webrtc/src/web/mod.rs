@@ -1 +1,90 @@
-unimplemented!()
+pub mod audio_source;
+pub mod audio_track;
+pub mod data_channel;
+pub mod ice_candidate;
+pub mod media_stream;
+pub mod media_stream_track;
+pub mod peer_connection;
+pub mod peer_connection_factory;
+pub mod rtp_parameters;
+pub mod rtp_receiver;
+pub mod rtp_sender;
+pub mod rtp_transceiver;
+pub mod session_description;
+pub mod video_frame;
+pub mod video_source;
+pub mod video_stream;
+pub mod video_track;
+
+use crate::{MediaType, RtcError, RtcErrorType};
+use js_sys::{Array, Function, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// The JS handles are only used from the thread they were created on (wasm32 is
+/// single-threaded), this allows the public types to be Send + Sync like on native.
+macro_rules! impl_thread_safety {
+    ($obj:ty) => {
+        unsafe impl Send for $obj {}
+        unsafe impl Sync for $obj {}
+    };
+}
+
+pub(crate) use impl_thread_safety;
+
+impl From<JsValue> for RtcError {
+    fn from(value: JsValue) -> Self {
+        // Promises are rejected with a DOMException (e.g InvalidStateError)
+        let name = get(&value, "name").as_string().unwrap_or_default();
+        let message = get(&value, "message")
+            .as_string()
+            .or_else(|| value.as_string())
+            .unwrap_or_else(|| format!("{:?}", value));
+
+        Self {
+            error_type: match name.as_str() {
+                "InvalidStateError" => RtcErrorType::InvalidState,
+                _ => RtcErrorType::Internal,
+            },
+            message,
+        }
+    }
+}
+
+impl MediaType {
+    pub(crate) fn as_kind(&self) -> &'static str {
+        match self {
+            MediaType::Audio => "audio",
+            MediaType::Video => "video",
+            MediaType::Data => "data",
+            MediaType::Unsupported => "unsupported",
+        }
+    }
+}
+
+/// Property of a JS object, undefined if missing
+pub(crate) fn get(target: &JsValue, key: &str) -> JsValue {
+    Reflect::get(target, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED)
+}
+
+pub(crate) fn set(target: &JsValue, key: &str, value: impl Into<JsValue>) {
+    let _ = Reflect::set(target, &JsValue::from_str(key), &value.into());
+}
+
+/// Call a method of a JS object, used for the APIs not exposed by every browser (so
+/// missing from web-sys)
+pub(crate) fn call(target: &JsValue, method: &str, args: &[JsValue]) -> Result<JsValue, JsValue> {
+    let function = get(target, method)
+        .dyn_into::<Function>()
+        .map_err(|_| JsValue::from_str(&format!("{} is not supported", method)))?;
+
+    function.apply(target, &args.iter().collect::<Array>())
+}
+
+/// Replace the "on<event>" handler of a JS object, the closure is then owned by the JS GC
+pub(crate) fn set_handler(target: &JsValue, name: &str, handler: Option<JsValue>) {
+    set(target, name, handler.unwrap_or(JsValue::NULL));
+}
+
+pub(crate) fn array_to_vec<T: JsCast>(array: Array) -> Vec<T> {
+    array.iter().map(JsCast::unchecked_into).collect()
+}
@@ -0,0 +1,401 @@
+use super::{array_to_vec, get, impl_thread_safety, set_handler};
+use crate::data_channel::{DataChannel, DataChannelInit};
+use crate::ice_candidate::IceCandidate;
+use crate::imp::data_channel as imp_dc;
+use crate::imp::ice_candidate as imp_ic;
+use crate::imp::media_stream as imp_ms;
+use crate::imp::media_stream_track::new_media_stream_track;
+use crate::imp::rtp_receiver as imp_rr;
+use crate::imp::rtp_sender as imp_rs;
+use crate::imp::rtp_transceiver as imp_rt;
+use crate::imp::session_description as imp_sdp;
+use crate::media_stream::MediaStream;
+use crate::media_stream_track::MediaStreamTrack;
+use crate::peer_connection::{
+    AnswerOptions, IceCandidateError, IceConnectionState, IceGatheringState, OfferOptions,
+    OnConnectionChange, OnDataChannel, OnIceCandidate, OnIceCandidateError, OnIceConnectionChange,
+    OnIceGatheringChange, OnIceSelectedCandidatePairChange, OnNegotiationNeeded, OnSignalingChange,
+    OnTrack, PeerConnectionState, SignalingState, TrackEvent,
+};
+use crate::rtp_receiver::RtpReceiver;
+use crate::rtp_sender::RtpSender;
+use crate::rtp_transceiver::{RtpTransceiver, RtpTransceiverInit};
+use crate::session_description::SessionDescription;
+use crate::{MediaType, RtcError};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    RtcDataChannelEvent, RtcIceConnectionState, RtcIceGatheringState, RtcOfferOptions,
+    RtcPeerConnectionIceEvent, RtcSignalingState, RtcTrackEvent,
+};
+
+impl From<OfferOptions> for RtcOfferOptions {
+    fn from(options: OfferOptions) -> Self {
+        let init = RtcOfferOptions::new();
+        init.set_ice_restart(options.ice_restart);
+        init.set_offer_to_receive_audio(options.offer_to_receive_audio);
+        init.set_offer_to_receive_video(options.offer_to_receive_video);
+        init
+    }
+}
+
+/// RTCPeerConnectionState isn't exposed by web-sys, it is read as a string
+fn peer_connection_state(state: &str) -> PeerConnectionState {
+    match state {
+        "new" => PeerConnectionState::New,
+        "connecting" => PeerConnectionState::Connecting,
+        "connected" => PeerConnectionState::Connected,
+        "disconnected" => PeerConnectionState::Disconnected,
+        "failed" => PeerConnectionState::Failed,
+        "closed" => PeerConnectionState::Closed,
+        _ => panic!("unknown PeerConnectionState"),
+    }
+}
+
+impl From<RtcIceConnectionState> for IceConnectionState {
+    fn from(state: RtcIceConnectionState) -> Self {
+        match state {
+            RtcIceConnectionState::New => IceConnectionState::New,
+            RtcIceConnectionState::Checking => IceConnectionState::Checking,
+            RtcIceConnectionState::Connected => IceConnectionState::Connected,
+            RtcIceConnectionState::Completed => IceConnectionState::Completed,
+            RtcIceConnectionState::Failed => IceConnectionState::Failed,
+            RtcIceConnectionState::Disconnected => IceConnectionState::Disconnected,
+            RtcIceConnectionState::Closed => IceConnectionState::Closed,
+            _ => panic!("unknown IceConnectionState"),
+        }
+    }
+}
+
+impl From<RtcIceGatheringState> for IceGatheringState {
+    fn from(state: RtcIceGatheringState) -> Self {
+        match state {
+            RtcIceGatheringState::New => IceGatheringState::New,
+            RtcIceGatheringState::Gathering => IceGatheringState::Gathering,
+            RtcIceGatheringState::Complete => IceGatheringState::Complete,
+            _ => panic!("unknown IceGatheringState"),
+        }
+    }
+}
+
+impl From<RtcSignalingState> for SignalingState {
+    fn from(state: RtcSignalingState) -> Self {
+        match state {
+            RtcSignalingState::Stable => SignalingState::Stable,
+            RtcSignalingState::HaveLocalOffer => SignalingState::HaveLocalOffer,
+            RtcSignalingState::HaveRemoteOffer => SignalingState::HaveRemoteOffer,
+            RtcSignalingState::HaveLocalPranswer => SignalingState::HaveLocalPrAnswer,
+            RtcSignalingState::HaveRemotePranswer => SignalingState::HaveRemotePrAnswer,
+            RtcSignalingState::Closed => SignalingState::Closed,
+            _ => panic!("unknown SignalingState"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PeerConnection {
+    pub(crate) sys_handle: web_sys::RtcPeerConnection,
+}
+
+impl_thread_safety!(PeerConnection);
+
+impl PeerConnection {
+    pub fn configure(sys_handle: web_sys::RtcPeerConnection) -> Self {
+        Self { sys_handle }
+    }
+
+    pub async fn create_offer(
+        &self,
+        options: OfferOptions,
+    ) -> Result<SessionDescription, RtcError> {
+        let promise = self
+            .sys_handle
+            .create_offer_with_rtc_offer_options(&options.into());
+        let desc = JsFuture::from(promise).await?;
+        imp_sdp::SessionDescription::from_js(&desc)
+    }
+
+    pub async fn create_answer(
+        &self,
+        _options: AnswerOptions,
+    ) -> Result<SessionDescription, RtcError> {
+        let desc = JsFuture::from(self.sys_handle.create_answer()).await?;
+        imp_sdp::SessionDescription::from_js(&desc)
+    }
+
+    pub async fn set_local_description(&self, desc: SessionDescription) -> Result<(), RtcError> {
+        let promise = self
+            .sys_handle
+            .set_local_description(&desc.handle.sys_handle());
+        JsFuture::from(promise).await?;
+        Ok(())
+    }
+
+    pub async fn set_remote_description(&self, desc: SessionDescription) -> Result<(), RtcError> {
+        let promise = self
+            .sys_handle
+            .set_remote_description(&desc.handle.sys_handle());
+        JsFuture::from(promise).await?;
+        Ok(())
+    }
+
+    pub async fn add_ice_candidate(&self, candidate: IceCandidate) -> Result<(), RtcError> {
+        let promise = self
+            .sys_handle
+            .add_ice_candidate_with_opt_rtc_ice_candidate_init(Some(
+                &candidate.handle.sys_handle(),
+            ));
+        JsFuture::from(promise).await?;
+        Ok(())
+    }
+
+    pub fn create_data_channel(
+        &self,
+        label: &str,
+        init: DataChannelInit,
+    ) -> Result<DataChannel, RtcError> {
+        let sys_handle = self
+            .sys_handle
+            .create_data_channel_with_data_channel_dict(label, &init.into());
+
+        Ok(DataChannel {
+            handle: imp_dc::DataChannel::configure(sys_handle),
+        })
+    }
+
+    /// The stream ids can't be chosen in the browsers, the track is added to a new stream
+    pub fn add_track<T: AsRef<str>>(
+        &self,
+        track: MediaStreamTrack,
+        _streams_ids: &[T],
+    ) -> Result<RtpSender, RtcError> {
+        let track = track.sys_handle();
+        let stream = web_sys::MediaStream::new()?;
+        stream.add_track(&track);
+
+        Ok(RtpSender {
+            handle: imp_rs::RtpSender {
+                sys_handle: self.sys_handle.add_track_0(&track, &stream),
+            },
+        })
+    }
+
+    pub fn remove_track(&self, sender: RtpSender) -> Result<(), RtcError> {
+        self.sys_handle.remove_track(&sender.handle.sys_handle);
+        Ok(())
+    }
+
+    pub fn add_transceiver(
+        &self,
+        track: MediaStreamTrack,
+        init: RtpTransceiverInit,
+    ) -> Result<RtpTransceiver, RtcError> {
+        let sys_handle = self
+            .sys_handle
+            .add_transceiver_with_media_stream_track_and_init(&track.sys_handle(), &init.into());
+
+        Ok(RtpTransceiver {
+            handle: imp_rt::RtpTransceiver { sys_handle },
+        })
+    }
+
+    pub fn add_transceiver_for_media(
+        &self,
+        media_type: MediaType,
+        init: RtpTransceiverInit,
+    ) -> Result<RtpTransceiver, RtcError> {
+        let sys_handle = self
+            .sys_handle
+            .add_transceiver_with_str_and_init(media_type.as_kind(), &init.into());
+
+        Ok(RtpTransceiver {
+            handle: imp_rt::RtpTransceiver { sys_handle },
+        })
+    }
+
+    pub fn close(&self) {
+        self.sys_handle.close();
+    }
+
+    pub fn connection_state(&self) -> PeerConnectionState {
+        peer_connection_state(
+            &get(&self.sys_handle, "connectionState")
+                .as_string()
+                .unwrap_or_default(),
+        )
+    }
+
+    pub fn ice_connection_state(&self) -> IceConnectionState {
+        self.sys_handle.ice_connection_state().into()
+    }
+
+    pub fn ice_gathering_state(&self) -> IceGatheringState {
+        self.sys_handle.ice_gathering_state().into()
+    }
+
+    pub fn signaling_state(&self) -> SignalingState {
+        self.sys_handle.signaling_state().into()
+    }
+
+    pub fn current_local_description(&self) -> Option<SessionDescription> {
+        let desc = self.sys_handle.current_local_description()?;
+        imp_sdp::SessionDescription::from_js(&desc).ok()
+    }
+
+    pub fn current_remote_description(&self) -> Option<SessionDescription> {
+        let desc = self.sys_handle.current_remote_description()?;
+        imp_sdp::SessionDescription::from_js(&desc).ok()
+    }
+
+    pub fn senders(&self) -> Vec<RtpSender> {
+        array_to_vec(self.sys_handle.get_senders())
+            .into_iter()
+            .map(|sys_handle| RtpSender {
+                handle: imp_rs::RtpSender { sys_handle },
+            })
+            .collect()
+    }
+
+    pub fn receivers(&self) -> Vec<RtpReceiver> {
+        array_to_vec(self.sys_handle.get_receivers())
+            .into_iter()
+            .map(|sys_handle| RtpReceiver {
+                handle: imp_rr::RtpReceiver { sys_handle },
+            })
+            .collect()
+    }
+
+    pub fn transceivers(&self) -> Vec<RtpTransceiver> {
+        array_to_vec(self.sys_handle.get_transceivers())
+            .into_iter()
+            .map(|sys_handle| RtpTransceiver {
+                handle: imp_rt::RtpTransceiver { sys_handle },
+            })
+            .collect()
+    }
+
+    pub fn on_connection_state_change(&self, f: Option<OnConnectionChange>) {
+        let handler = f.map(|mut f| {
+            let pc = self.clone();
+            Closure::<dyn FnMut()>::new(move || f(pc.connection_state())).into_js_value()
+        });
+        set_handler(&self.sys_handle, "onconnectionstatechange", handler);
+    }
+
+    pub fn on_data_channel(&self, f: Option<OnDataChannel>) {
+        let handler = f.map(|mut f| {
+            Closure::<dyn FnMut(RtcDataChannelEvent)>::new(move |event: RtcDataChannelEvent| {
+                f(DataChannel {
+                    handle: imp_dc::DataChannel::configure(event.channel()),
+                })
+            })
+            .into_js_value()
+        });
+        set_handler(&self.sys_handle, "ondatachannel", handler);
+    }
+
+    pub fn on_ice_candidate(&self, f: Option<OnIceCandidate>) {
+        let handler = f.map(|mut f| {
+            Closure::<dyn FnMut(RtcPeerConnectionIceEvent)>::new(
+                move |event: RtcPeerConnectionIceEvent| {
+                    // A null candidate signals the end of the gathering
+                    let candidate = match event.candidate() {
+                        Some(candidate) => candidate,
+                        None => return,
+                    };
+
+                    match imp_ic::IceCandidate::from_js(&candidate) {
+                        Ok(candidate) => f(candidate),
+                        Err(err) => log::warn!("failed to parse ice candidate: {}", err),
+                    }
+                },
+            )
+            .into_js_value()
+        });
+        set_handler(&self.sys_handle, "onicecandidate", handler);
+    }
+
+    pub fn on_ice_candidate_error(&self, f: Option<OnIceCandidateError>) {
+        let handler = f.map(|mut f| {
+            Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+                f(IceCandidateError {
+                    address: get(&event, "address").as_string().unwrap_or_default(),
+                    port: get(&event, "port").as_f64().unwrap_or_default() as i32,
+                    url: get(&event, "url").as_string().unwrap_or_default(),
+                    error_code: get(&event, "errorCode").as_f64().unwrap_or_default() as i32,
+                    error_text: get(&event, "errorText").as_string().unwrap_or_default(),
+                })
+            })
+            .into_js_value()
+        });
+        set_handler(&self.sys_handle, "onicecandidateerror", handler);
+    }
+
+    pub fn on_ice_connection_state_change(&self, f: Option<OnIceConnectionChange>) {
+        let handler = f.map(|mut f| {
+            let pc = self.clone();
+            Closure::<dyn FnMut()>::new(move || f(pc.ice_connection_state())).into_js_value()
+        });
+        set_handler(&self.sys_handle, "oniceconnectionstatechange", handler);
+    }
+
+    pub fn on_ice_gathering_state_change(&self, f: Option<OnIceGatheringChange>) {
+        let handler = f.map(|mut f| {
+            let pc = self.clone();
+            Closure::<dyn FnMut()>::new(move || f(pc.ice_gathering_state())).into_js_value()
+        });
+        set_handler(&self.sys_handle, "onicegatheringstatechange", handler);
+    }
+
+    /// Browsers only expose the selected pair on the RTCIceTransport of each sender/receiver,
+    /// the handler is never called
+    pub fn on_ice_selected_candidate_pair_change(
+        &self,
+        _f: Option<OnIceSelectedCandidatePairChange>,
+    ) {
+    }
+
+    pub fn on_negotiation_needed(&self, f: Option<OnNegotiationNeeded>) {
+        let handler = f.map(|mut f| {
+            // There is no event id in the browsers, the event is always valid
+            Closure::<dyn FnMut()>::new(move || f(0)).into_js_value()
+        });
+        set_handler(&self.sys_handle, "onnegotiationneeded", handler);
+    }
+
+    pub fn on_signaling_state_change(&self, f: Option<OnSignalingChange>) {
+        let handler = f.map(|mut f| {
+            let pc = self.clone();
+            Closure::<dyn FnMut()>::new(move || f(pc.signaling_state())).into_js_value()
+        });
+        set_handler(&self.sys_handle, "onsignalingstatechange", handler);
+    }
+
+    pub fn on_track(&self, f: Option<OnTrack>) {
+        let handler = f.map(|mut f| {
+            Closure::<dyn FnMut(RtcTrackEvent)>::new(move |event: RtcTrackEvent| {
+                f(TrackEvent {
+                    receiver: RtpReceiver {
+                        handle: imp_rr::RtpReceiver {
+                            sys_handle: event.receiver(),
+                        },
+                    },
+                    streams: array_to_vec(event.streams())
+                        .into_iter()
+                        .map(|sys_handle| MediaStream {
+                            handle: imp_ms::MediaStream { sys_handle },
+                        })
+                        .collect(),
+                    track: new_media_stream_track(event.track()),
+                    transceiver: RtpTransceiver {
+                        handle: imp_rt::RtpTransceiver {
+                            sys_handle: event.transceiver(),
+                        },
+                    },
+                })
+            })
+            .into_js_value()
+        });
+        set_handler(&self.sys_handle, "ontrack", handler);
+    }
+}
@@ -0,0 +1,114 @@
+use super::audio_source::WebAudioSource;
+use super::rtp_parameters::rtp_capabilities_from_js;
+use super::video_source::WebVideoSource;
+use super::{call, get, set};
+use crate::audio_track::RtcAudioTrack;
+use crate::imp::audio_track as imp_at;
+use crate::imp::peer_connection as imp_pc;
+use crate::imp::video_track as imp_vt;
+use crate::peer_connection::PeerConnection;
+use crate::peer_connection_factory::{
+    IceTransportsType, PeerConnectionFactoryOptions, RtcConfiguration,
+};
+use crate::rtp_parameters::RtpCapabilities;
+use crate::video_track::RtcVideoTrack;
+use crate::{MediaType, RtcError};
+use js_sys::{Array, Object};
+use wasm_bindgen::{JsCast, JsValue};
+
+impl From<RtcConfiguration> for web_sys::RtcConfiguration {
+    fn from(config: RtcConfiguration) -> Self {
+        // The port range and the network filtering can't be configured in the browsers
+        let ice_servers = config
+            .ice_servers
+            .iter()
+            .map(|ice_server| {
+                let server = Object::new();
+                set(
+                    &server,
+                    "urls",
+                    ice_server
+                        .urls
+                        .iter()
+                        .map(|url| JsValue::from_str(url))
+                        .collect::<Array>(),
+                );
+                set(&server, "username", ice_server.username.as_str());
+                set(&server, "credential", ice_server.password.as_str());
+                JsValue::from(server)
+            })
+            .collect::<Array>();
+
+        let ice_transport_policy = match config.ice_transport_type {
+            IceTransportsType::Relay => "relay",
+            _ => "all",
+        };
+
+        let rtc_config = Object::new();
+        set(&rtc_config, "iceServers", ice_servers);
+        set(&rtc_config, "iceTransportPolicy", ice_transport_policy);
+        rtc_config.unchecked_into()
+    }
+}
+
+/// The browser owns the WebRTC stack, there is nothing to create
+#[derive(Clone, Default)]
+pub struct PeerConnectionFactory {}
+
+impl PeerConnectionFactory {
+    /// The options only apply to libwebrtc and are ignored
+    pub fn with_options(_options: PeerConnectionFactoryOptions) -> Self {
+        Self {}
+    }
+
+    pub fn create_peer_connection(
+        &self,
+        config: RtcConfiguration,
+    ) -> Result<PeerConnection, RtcError> {
+        let sys_handle = web_sys::RtcPeerConnection::new_with_configuration(&config.into())?;
+
+        Ok(PeerConnection {
+            handle: imp_pc::PeerConnection::configure(sys_handle),
+        })
+    }
+
+    /// Label is ignored, the track is a clone of the one captured by the browser
+    pub fn create_video_track(&self, _label: &str, source: WebVideoSource) -> RtcVideoTrack {
+        RtcVideoTrack {
+            handle: imp_vt::RtcVideoTrack {
+                sys_handle: source.sys_handle().clone_track(),
+            },
+        }
+    }
+
+    /// Label is ignored, the track is a clone of the one captured by the browser
+    pub fn create_audio_track(&self, _label: &str, source: WebAudioSource) -> RtcAudioTrack {
+        RtcAudioTrack {
+            handle: imp_at::RtcAudioTrack {
+                sys_handle: source.sys_handle().clone_track(),
+            },
+        }
+    }
+
+    pub fn get_rtp_sender_capabilities(&self, media_type: MediaType) -> RtpCapabilities {
+        rtp_capabilities("RTCRtpSender", media_type)
+    }
+
+    pub fn get_rtp_receiver_capabilities(&self, media_type: MediaType) -> RtpCapabilities {
+        rtp_capabilities("RTCRtpReceiver", media_type)
+    }
+}
+
+/// getCapabilities is a static method of RTCRtpSender/RTCRtpReceiver, it returns null
+/// for the unsupported kinds
+fn rtp_capabilities(class: &str, media_type: MediaType) -> RtpCapabilities {
+    let class = get(&js_sys::global(), class);
+    let capabilities = call(
+        &class,
+        "getCapabilities",
+        &[JsValue::from_str(media_type.as_kind())],
+    )
+    .unwrap_or(JsValue::NULL);
+
+    rtp_capabilities_from_js(&capabilities)
+}
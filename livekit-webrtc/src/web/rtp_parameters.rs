@@ -0,0 +1,186 @@
+use super::{get, set};
+use crate::rtp_parameters::*;
+use crate::rtp_transceiver::RtpTransceiverDirection;
+use js_sys::{Array, Object};
+use wasm_bindgen::{JsCast, JsValue};
+
+// The parameters are plain JS dictionaries, they're read and written by name since
+// browsers don't implement the same subset of fields
+
+impl Priority {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Priority::VeryLow => "very-low",
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        }
+    }
+
+    pub(crate) fn from_js(value: &JsValue) -> Option<Self> {
+        match value.as_string()?.as_str() {
+            "very-low" => Some(Priority::VeryLow),
+            "low" => Some(Priority::Low),
+            "medium" => Some(Priority::Medium),
+            "high" => Some(Priority::High),
+            _ => None,
+        }
+    }
+}
+
+impl RtpTransceiverDirection {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            RtpTransceiverDirection::SendRecv => "sendrecv",
+            RtpTransceiverDirection::SendOnly => "sendonly",
+            RtpTransceiverDirection::RecvOnly => "recvonly",
+            RtpTransceiverDirection::Inactive => "inactive",
+            RtpTransceiverDirection::Stopped => "stopped",
+        }
+    }
+
+    pub(crate) fn from_js(value: &JsValue) -> Option<Self> {
+        match value.as_string()?.as_str() {
+            "sendrecv" => Some(RtpTransceiverDirection::SendRecv),
+            "sendonly" => Some(RtpTransceiverDirection::SendOnly),
+            "recvonly" => Some(RtpTransceiverDirection::RecvOnly),
+            "inactive" => Some(RtpTransceiverDirection::Inactive),
+            "stopped" => Some(RtpTransceiverDirection::Stopped),
+            _ => None,
+        }
+    }
+}
+
+fn number(value: &JsValue, key: &str) -> Option<f64> {
+    get(value, key).as_f64()
+}
+
+fn string(value: &JsValue, key: &str) -> String {
+    get(value, key).as_string().unwrap_or_default()
+}
+
+fn array(value: &JsValue, key: &str) -> Vec<JsValue> {
+    get(value, key)
+        .dyn_into::<Array>()
+        .map(|array| array.iter().collect())
+        .unwrap_or_default()
+}
+
+pub fn rtp_parameters_from_js(value: &JsValue) -> RtpParameters {
+    let rtcp = get(value, "rtcp");
+    RtpParameters {
+        codecs: array(value, "codecs")
+            .iter()
+            .map(|codec| RtpCodecParameters {
+                payload_type: number(codec, "payloadType").unwrap_or_default() as u8,
+                mime_type: string(codec, "mimeType"),
+                clock_rate: number(codec, "clockRate").map(|c| c as u64),
+                channels: number(codec, "channels").map(|c| c as u16),
+            })
+            .collect(),
+        header_extensions: array(value, "headerExtensions")
+            .iter()
+            .map(|extension| RtpHeaderExtensionParameters {
+                uri: string(extension, "uri"),
+                id: number(extension, "id").unwrap_or_default() as i32,
+                encrypted: get(extension, "encrypted").is_truthy(),
+            })
+            .collect(),
+        rtcp: RtcpParameters {
+            cname: string(&rtcp, "cname"),
+            reduced_size: get(&rtcp, "reducedSize").is_truthy(),
+        },
+        encodings: array(value, "encodings")
+            .iter()
+            .map(rtp_encoding_parameters_from_js)
+            .collect(),
+    }
+}
+
+pub fn rtp_capabilities_from_js(value: &JsValue) -> RtpCapabilities {
+    RtpCapabilities {
+        codecs: array(value, "codecs")
+            .iter()
+            .map(rtp_codec_capability_from_js)
+            .collect(),
+        header_extensions: array(value, "headerExtensions")
+            .iter()
+            .map(rtp_header_extension_capability_from_js)
+            .collect(),
+    }
+}
+
+pub fn rtp_codec_capability_from_js(value: &JsValue) -> RtpCodecCapability {
+    RtpCodecCapability {
+        channels: number(value, "channels").map(|c| c as u16),
+        clock_rate: number(value, "clockRate").map(|c| c as u64),
+        mime_type: string(value, "mimeType"),
+        sdp_fmtp_line: get(value, "sdpFmtpLine").as_string(),
+    }
+}
+
+pub fn rtp_codec_capability_to_js(codec: &RtpCodecCapability) -> JsValue {
+    let value = Object::new().into();
+    set(&value, "mimeType", codec.mime_type.as_str());
+    if let Some(clock_rate) = codec.clock_rate {
+        set(&value, "clockRate", clock_rate as f64);
+    }
+    if let Some(channels) = codec.channels {
+        set(&value, "channels", channels);
+    }
+    if let Some(sdp_fmtp_line) = &codec.sdp_fmtp_line {
+        set(&value, "sdpFmtpLine", sdp_fmtp_line.as_str());
+    }
+    value
+}
+
+pub fn rtp_header_extension_capability_from_js(value: &JsValue) -> RtpHeaderExtensionCapability {
+    RtpHeaderExtensionCapability {
+        uri: string(value, "uri"),
+        // getCapabilities doesn't return a direction
+        direction: RtpTransceiverDirection::from_js(&get(value, "direction"))
+            .unwrap_or(RtpTransceiverDirection::SendRecv),
+    }
+}
+
+pub fn rtp_header_extension_capability_to_js(extension: &RtpHeaderExtensionCapability) -> JsValue {
+    let value = Object::new().into();
+    set(&value, "uri", extension.uri.as_str());
+    set(&value, "direction", extension.direction.as_str());
+    value
+}
+
+pub fn rtp_encoding_parameters_from_js(value: &JsValue) -> RtpEncodingParameters {
+    RtpEncodingParameters {
+        // Defaults to true when missing
+        active: get(value, "active").as_bool().unwrap_or(true),
+        max_bitrate: number(value, "maxBitrate").map(|b| b as u64),
+        max_framerate: number(value, "maxFramerate"),
+        priority: Priority::from_js(&get(value, "priority")).unwrap_or(Priority::Low),
+        rid: string(value, "rid"),
+        scale_resolution_down_by: number(value, "scaleResolutionDownBy"),
+        scalability_mode: get(value, "scalabilityMode").as_string(),
+    }
+}
+
+pub fn rtp_encoding_parameters_to_js(encoding: &RtpEncodingParameters) -> JsValue {
+    let value = Object::new().into();
+    set(&value, "active", encoding.active);
+    if let Some(max_bitrate) = encoding.max_bitrate {
+        set(&value, "maxBitrate", max_bitrate as f64);
+    }
+    if let Some(max_framerate) = encoding.max_framerate {
+        set(&value, "maxFramerate", max_framerate);
+    }
+    set(&value, "priority", encoding.priority.as_str());
+    if !encoding.rid.is_empty() {
+        set(&value, "rid", encoding.rid.as_str());
+    }
+    if let Some(scale_resolution_down_by) = encoding.scale_resolution_down_by {
+        set(&value, "scaleResolutionDownBy", scale_resolution_down_by);
+    }
+    if let Some(scalability_mode) = &encoding.scalability_mode {
+        set(&value, "scalabilityMode", scalability_mode.as_str());
+    }
+    value
+}
@@ -0,0 +1,73 @@
+use super::media_stream_track::new_media_stream_track;
+use super::rtp_parameters::rtp_parameters_from_js;
+use super::{call, get, impl_thread_safety, set};
+use crate::media_stream_track::MediaStreamTrack;
+use crate::rtp_parameters::RtpParameters;
+use crate::rtp_receiver::{RtpSource, RtpSourceType};
+use js_sys::Array;
+use std::time::Duration;
+use wasm_bindgen::JsValue;
+
+#[derive(Clone)]
+pub struct RtpReceiver {
+    pub(crate) sys_handle: web_sys::RtcRtpReceiver,
+}
+
+impl_thread_safety!(RtpReceiver);
+
+impl RtpReceiver {
+    pub fn track(&self) -> Option<MediaStreamTrack> {
+        Some(new_media_stream_track(self.sys_handle.track()))
+    }
+
+    pub fn parameters(&self) -> RtpParameters {
+        call(&self.sys_handle, "getParameters", &[])
+            .map(|parameters| rtp_parameters_from_js(&parameters))
+            .unwrap_or_default()
+    }
+
+    pub fn set_jitter_buffer_minimum_delay(&self, delay: Option<Duration>) {
+        let target = match delay {
+            Some(delay) => JsValue::from_f64(delay.as_secs_f64() * 1000.0),
+            None => JsValue::NULL,
+        };
+        set(&self.sys_handle, "jitterBufferTarget", target);
+    }
+
+    pub fn sources(&self) -> Vec<RtpSource> {
+        let mut sources = Vec::new();
+        for (method, source_type) in [
+            ("getSynchronizationSources", RtpSourceType::Ssrc),
+            ("getContributingSources", RtpSourceType::Csrc),
+        ] {
+            let array = match call(&self.sys_handle, method, &[]) {
+                Ok(array) => Array::from(&array),
+                Err(_) => continue,
+            };
+
+            sources.extend(
+                array
+                    .iter()
+                    .map(|source| rtp_source_from_js(&source, source_type)),
+            );
+        }
+        sources
+    }
+}
+
+fn rtp_source_from_js(value: &JsValue, source_type: RtpSourceType) -> RtpSource {
+    RtpSource {
+        source_id: get(value, "source").as_f64().unwrap_or_default() as u32,
+        source_type,
+        timestamp_ms: get(value, "timestamp").as_f64().unwrap_or_default() as i64,
+        rtp_timestamp: get(value, "rtpTimestamp").as_f64().unwrap_or_default() as u32,
+        // Linear in the browsers, converted back to -dBov
+        audio_level: get(value, "audioLevel").as_f64().map(|level| {
+            if level <= 0.0 {
+                127
+            } else {
+                (-20.0 * level.log10()).clamp(0.0, 127.0).round() as u8
+            }
+        }),
+    }
+}
@@ -0,0 +1,68 @@
+use super::media_stream_track::new_media_stream_track;
+use super::rtp_parameters::rtp_parameters_from_js;
+use super::{call, get, impl_thread_safety, set};
+use crate::media_stream_track::MediaStreamTrack;
+use crate::{rtp_parameters::RtpParameters, RtcError};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+#[derive(Clone)]
+pub struct RtpSender {
+    pub(crate) sys_handle: web_sys::RtcRtpSender,
+}
+
+impl_thread_safety!(RtpSender);
+
+impl RtpSender {
+    pub fn track(&self) -> Option<MediaStreamTrack> {
+        self.sys_handle.track().map(new_media_stream_track)
+    }
+
+    /// replaceTrack is asynchronous in the browsers, the failures are only logged
+    pub fn set_track(&self, track: Option<MediaStreamTrack>) -> Result<(), RtcError> {
+        let track = track.map(|t| t.sys_handle());
+        let promise = self.sys_handle.replace_track(track.as_ref());
+        spawn_local(async move {
+            if let Err(err) = JsFuture::from(promise).await {
+                log::error!("failed to replace track: {:?}", RtcError::from(err));
+            }
+        });
+        Ok(())
+    }
+
+    pub fn parameters(&self) -> RtpParameters {
+        rtp_parameters_from_js(&self.sys_handle.get_parameters())
+    }
+
+    /// The codecs, header extensions and rtcp parameters are read-only in the browsers,
+    /// only the encodings are applied to the current parameters
+    pub fn set_parameters(&self, parameters: RtpParameters) -> Result<(), RtcError> {
+        let current = self.sys_handle.get_parameters();
+        let encodings = get(&current, "encodings");
+        for (i, encoding) in parameters.encodings.iter().enumerate() {
+            let value = get(&encodings, &i.to_string());
+            if value.is_undefined() {
+                break; // The number of encodings can't be changed
+            }
+
+            set(&value, "active", encoding.active);
+            if let Some(max_bitrate) = encoding.max_bitrate {
+                set(&value, "maxBitrate", max_bitrate as f64);
+            }
+            if let Some(max_framerate) = encoding.max_framerate {
+                set(&value, "maxFramerate", max_framerate);
+            }
+            set(&value, "priority", encoding.priority.as_str());
+            if let Some(scale_resolution_down_by) = encoding.scale_resolution_down_by {
+                set(&value, "scaleResolutionDownBy", scale_resolution_down_by);
+            }
+        }
+
+        let promise = call(&self.sys_handle, "setParameters", &[current.into()])?;
+        spawn_local(async move {
+            if let Err(err) = JsFuture::from(js_sys::Promise::from(promise)).await {
+                log::error!("failed to set parameters: {:?}", RtcError::from(err));
+            }
+        });
+        Ok(())
+    }
+}
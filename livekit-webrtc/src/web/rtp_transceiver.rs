@@ -0,0 +1,141 @@
+use super::rtp_parameters::{
+    rtp_codec_capability_to_js, rtp_encoding_parameters_to_js,
+    rtp_header_extension_capability_from_js, rtp_header_extension_capability_to_js,
+};
+use super::{call, get, impl_thread_safety, set};
+use crate::imp::rtp_receiver::RtpReceiver;
+use crate::imp::rtp_sender::RtpSender;
+use crate::rtp_parameters::{RtpCodecCapability, RtpHeaderExtensionCapability};
+use crate::rtp_receiver;
+use crate::rtp_sender;
+use crate::rtp_transceiver::{RtpTransceiverDirection, RtpTransceiverInit};
+use crate::{RtcError, RtcErrorType};
+use js_sys::{Array, Object};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{RtcRtpTransceiverDirection, RtcRtpTransceiverInit};
+
+/// The stream ids can't be chosen in the browsers, the tracks are added to new streams
+impl From<RtpTransceiverInit> for RtcRtpTransceiverInit {
+    fn from(value: RtpTransceiverInit) -> Self {
+        let init: JsValue = Object::new().into();
+        set(&init, "direction", value.direction.as_str());
+        set(
+            &init,
+            "sendEncodings",
+            value
+                .send_encodings
+                .iter()
+                .map(rtp_encoding_parameters_to_js)
+                .collect::<Array>(),
+        );
+        init.unchecked_into()
+    }
+}
+
+#[derive(Clone)]
+pub struct RtpTransceiver {
+    pub(crate) sys_handle: web_sys::RtcRtpTransceiver,
+}
+
+impl_thread_safety!(RtpTransceiver);
+
+impl RtpTransceiver {
+    pub fn mid(&self) -> Option<String> {
+        self.sys_handle.mid()
+    }
+
+    pub fn current_direction(&self) -> Option<RtpTransceiverDirection> {
+        RtpTransceiverDirection::from_js(&get(&self.sys_handle, "currentDirection"))
+    }
+
+    pub fn direction(&self) -> RtpTransceiverDirection {
+        RtpTransceiverDirection::from_js(&get(&self.sys_handle, "direction"))
+            .expect("unknown RtpTransceiverDirection")
+    }
+
+    /// The transceiver is stopped with stop(), not by setting its direction
+    pub fn set_direction(&self, direction: RtpTransceiverDirection) -> Result<(), RtcError> {
+        let direction = match direction {
+            RtpTransceiverDirection::SendRecv => RtcRtpTransceiverDirection::Sendrecv,
+            RtpTransceiverDirection::SendOnly => RtcRtpTransceiverDirection::Sendonly,
+            RtpTransceiverDirection::RecvOnly => RtcRtpTransceiverDirection::Recvonly,
+            RtpTransceiverDirection::Inactive => RtcRtpTransceiverDirection::Inactive,
+            RtpTransceiverDirection::Stopped => {
+                return Err(RtcError {
+                    error_type: RtcErrorType::InvalidState,
+                    message: "stopped isn't a valid direction".to_owned(),
+                })
+            }
+        };
+
+        self.sys_handle.set_direction(direction);
+        Ok(())
+    }
+
+    pub fn sender(&self) -> rtp_sender::RtpSender {
+        rtp_sender::RtpSender {
+            handle: RtpSender {
+                sys_handle: self.sys_handle.sender(),
+            },
+        }
+    }
+
+    pub fn receiver(&self) -> rtp_receiver::RtpReceiver {
+        rtp_receiver::RtpReceiver {
+            handle: RtpReceiver {
+                sys_handle: self.sys_handle.receiver(),
+            },
+        }
+    }
+
+    pub fn set_codec_preferences(&self, codecs: Vec<RtpCodecCapability>) -> Result<(), RtcError> {
+        let codecs = codecs
+            .iter()
+            .map(rtp_codec_capability_to_js)
+            .collect::<Array>();
+
+        call(&self.sys_handle, "setCodecPreferences", &[codecs.into()])?;
+        Ok(())
+    }
+
+    /// Empty if the browser doesn't support the header extensions control API
+    pub fn header_extensions_to_offer(&self) -> Vec<RtpHeaderExtensionCapability> {
+        self.header_extensions("getHeaderExtensionsToNegotiate")
+    }
+
+    pub fn header_extensions_negotiated(&self) -> Vec<RtpHeaderExtensionCapability> {
+        self.header_extensions("getNegotiatedHeaderExtensions")
+    }
+
+    pub fn set_header_extensions_to_offer(
+        &self,
+        extensions: Vec<RtpHeaderExtensionCapability>,
+    ) -> Result<(), RtcError> {
+        let extensions = extensions
+            .iter()
+            .map(rtp_header_extension_capability_to_js)
+            .collect::<Array>();
+
+        call(
+            &self.sys_handle,
+            "setHeaderExtensionsToNegotiate",
+            &[extensions.into()],
+        )?;
+        Ok(())
+    }
+
+    pub fn stop(&self) -> Result<(), RtcError> {
+        call(&self.sys_handle, "stop", &[])?;
+        Ok(())
+    }
+
+    fn header_extensions(&self, method: &str) -> Vec<RtpHeaderExtensionCapability> {
+        match call(&self.sys_handle, method, &[]) {
+            Ok(extensions) => Array::from(&extensions)
+                .iter()
+                .map(|extension| rtp_header_extension_capability_from_js(&extension))
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use super::get;
+use crate::sdp::Sdp;
+use crate::session_description::{self as sd, SdpParseError, SdpType};
+use crate::{RtcError, RtcErrorType};
+use wasm_bindgen::JsValue;
+use web_sys::{RtcSdpType, RtcSessionDescriptionInit};
+
+impl From<SdpType> for RtcSdpType {
+    fn from(sdp_type: SdpType) -> Self {
+        match sdp_type {
+            SdpType::Offer => RtcSdpType::Offer,
+            SdpType::PrAnswer => RtcSdpType::Pranswer,
+            SdpType::Answer => RtcSdpType::Answer,
+            SdpType::Rollback => RtcSdpType::Rollback,
+        }
+    }
+}
+
+impl From<RtcSdpType> for SdpType {
+    fn from(sdp_type: RtcSdpType) -> Self {
+        match sdp_type {
+            RtcSdpType::Offer => SdpType::Offer,
+            RtcSdpType::Pranswer => SdpType::PrAnswer,
+            RtcSdpType::Answer => SdpType::Answer,
+            RtcSdpType::Rollback => SdpType::Rollback,
+            _ => panic!("unknown SdpType"),
+        }
+    }
+}
+
+/// The browsers don't expose their SDP parser, the description is validated with
+/// crate::sdp and parsed again by the PeerConnection when it is applied
+#[derive(Clone)]
+pub struct SessionDescription {
+    sdp_type: SdpType,
+    sdp: String,
+}
+
+impl SessionDescription {
+    pub fn parse(sdp: &str, sdp_type: SdpType) -> Result<sd::SessionDescription, SdpParseError> {
+        sdp.parse::<Sdp>()?;
+        Ok(sd::SessionDescription {
+            handle: SessionDescription {
+                sdp_type,
+                sdp: sdp.to_owned(),
+            },
+        })
+    }
+
+    /// RTCSessionDescriptionInit returned by createOffer/createAnswer
+    pub fn from_js(value: &JsValue) -> Result<sd::SessionDescription, RtcError> {
+        let sdp_type = get(value, "type")
+            .as_string()
+            .and_then(|sdp_type| sdp_type.parse().ok());
+
+        match (sdp_type, get(value, "sdp").as_string()) {
+            (Some(sdp_type), Some(sdp)) => Ok(sd::SessionDescription {
+                handle: SessionDescription { sdp_type, sdp },
+            }),
+            _ => Err(RtcError {
+                error_type: RtcErrorType::InvalidSdp,
+                message: "invalid session description".to_owned(),
+            }),
+        }
+    }
+
+    pub fn sys_handle(&self) -> RtcSessionDescriptionInit {
+        let init = RtcSessionDescriptionInit::new(self.sdp_type.into());
+        init.set_sdp(&self.sdp);
+        init
+    }
+
+    pub fn sdp_type(&self) -> SdpType {
+        self.sdp_type
+    }
+}
+
+impl ToString for SessionDescription {
+    fn to_string(&self) -> String {
+        self.sdp.clone()
+    }
+}
@@ -0,0 +1,173 @@
+use super::impl_thread_safety;
+use web_sys::{WebGlRenderingContext, WebGlTexture};
+
+// The planar buffers are only used to exchange raw frames with the user on wasm32, the
+// browser never reads nor produces them. The planes are tightly packed (stride == width).
+
+macro_rules! planar_buffer {
+    ($type:ident, $sample:ty, [$($plane:ident),+], $chroma_width:expr, $chroma_height:expr) => {
+        pub struct $type {
+            width: u32,
+            height: u32,
+            $($plane: Vec<$sample>,)+
+        }
+
+        impl $type {
+            pub fn width(&self) -> u32 {
+                self.width
+            }
+
+            pub fn height(&self) -> u32 {
+                self.height
+            }
+
+            pub fn chroma_width(&self) -> u32 {
+                $chroma_width(self.width)
+            }
+
+            pub fn chroma_height(&self) -> u32 {
+                $chroma_height(self.height)
+            }
+
+            pub fn stride_y(&self) -> u32 {
+                self.width
+            }
+        }
+    };
+}
+
+fn half(size: u32) -> u32 {
+    (size + 1) / 2
+}
+
+fn full(size: u32) -> u32 {
+    size
+}
+
+fn plane<T: Clone + Default>(width: u32, height: u32) -> Vec<T> {
+    vec![T::default(); (width * height) as usize]
+}
+
+planar_buffer!(I420Buffer, u8, [data_y, data_u, data_v], half, half);
+planar_buffer!(
+    I420ABuffer,
+    u8,
+    [data_y, data_u, data_v, data_a],
+    half,
+    half
+);
+planar_buffer!(I422Buffer, u8, [data_y, data_u, data_v], half, full);
+planar_buffer!(I444Buffer, u8, [data_y, data_u, data_v], full, full);
+planar_buffer!(I010Buffer, u16, [data_y, data_u, data_v], half, half);
+planar_buffer!(NV12Buffer, u8, [data_y, data_uv], half, half);
+
+macro_rules! yuv_buffer {
+    ($type:ident, $sample:ty, $chroma_width:expr, $chroma_height:expr) => {
+        impl $type {
+            pub fn new(width: u32, height: u32) -> Self {
+                let (chroma_width, chroma_height) = ($chroma_width(width), $chroma_height(height));
+                Self {
+                    width,
+                    height,
+                    data_y: plane(width, height),
+                    data_u: plane(chroma_width, chroma_height),
+                    data_v: plane(chroma_width, chroma_height),
+                }
+            }
+
+            pub fn stride_u(&self) -> u32 {
+                self.chroma_width()
+            }
+
+            pub fn stride_v(&self) -> u32 {
+                self.chroma_width()
+            }
+
+            pub fn data(&self) -> (&[$sample], &[$sample], &[$sample]) {
+                (&self.data_y, &self.data_u, &self.data_v)
+            }
+        }
+    };
+}
+
+yuv_buffer!(I420Buffer, u8, half, half);
+yuv_buffer!(I422Buffer, u8, half, full);
+yuv_buffer!(I444Buffer, u8, full, full);
+yuv_buffer!(I010Buffer, u16, half, half);
+
+impl I420ABuffer {
+    pub fn new(width: u32, height: u32) -> Self {
+        let (chroma_width, chroma_height) = (half(width), half(height));
+        Self {
+            width,
+            height,
+            data_y: plane(width, height),
+            data_u: plane(chroma_width, chroma_height),
+            data_v: plane(chroma_width, chroma_height),
+            data_a: plane(width, height),
+        }
+    }
+
+    pub fn stride_u(&self) -> u32 {
+        self.chroma_width()
+    }
+
+    pub fn stride_v(&self) -> u32 {
+        self.chroma_width()
+    }
+
+    pub fn stride_a(&self) -> u32 {
+        self.width
+    }
+
+    pub fn data(&self) -> (&[u8], &[u8], &[u8], Option<&[u8]>) {
+        (&self.data_y, &self.data_u, &self.data_v, Some(&self.data_a))
+    }
+}
+
+impl NV12Buffer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data_y: plane(width, height),
+            data_uv: plane(half(width) * 2, half(height)),
+        }
+    }
+
+    pub fn stride_uv(&self) -> u32 {
+        self.chroma_width() * 2
+    }
+
+    pub fn data(&self) -> (&[u8], &[u8]) {
+        (&self.data_y, &self.data_uv)
+    }
+}
+
+/// Frame uploaded to a texture of a WebGL context, see WebGlVideoStream
+pub struct WebGlBuffer {
+    pub(crate) gl: WebGlRenderingContext,
+    pub(crate) texture: WebGlTexture,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl_thread_safety!(WebGlBuffer);
+
+impl WebGlBuffer {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn context(&self) -> WebGlRenderingContext {
+        self.gl.clone()
+    }
+
+    pub fn texture(&self) -> WebGlTexture {
+        self.texture.clone()
+    }
+}
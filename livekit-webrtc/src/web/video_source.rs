@@ -0,0 +1,20 @@
+use super::impl_thread_safety;
+
+/// Track captured by the browser (e.g getUserMedia, getDisplayMedia or
+/// HTMLCanvasElement.captureStream)
+#[derive(Clone)]
+pub struct WebVideoSource {
+    sys_handle: web_sys::MediaStreamTrack,
+}
+
+impl_thread_safety!(WebVideoSource);
+
+impl WebVideoSource {
+    pub fn new(sys_handle: web_sys::MediaStreamTrack) -> Self {
+        Self { sys_handle }
+    }
+
+    pub fn sys_handle(&self) -> web_sys::MediaStreamTrack {
+        self.sys_handle.clone()
+    }
+}
@@ -0,0 +1,139 @@
+use super::video_frame as vf_imp;
+use super::{impl_thread_safety, set};
+use crate::video_frame::web::WebGlBuffer;
+use crate::video_frame::{VideoFrame, VideoRotation};
+use crate::video_track::RtcVideoTrack;
+use futures::channel::mpsc;
+use futures::stream::Stream;
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use wasm_bindgen::prelude::*;
+use web_sys::{HtmlMediaElement, HtmlVideoElement, WebGlRenderingContext as Gl};
+
+/// Browsers don't give access to the decoded frames of a track: the track is played by a
+/// detached <video> element and each new frame is uploaded to a texture of the WebGL context
+pub struct WebGlVideoStream {
+    video_track: RtcVideoTrack,
+    element: HtmlVideoElement,
+    frame_rx: mpsc::UnboundedReceiver<VideoFrame<WebGlBuffer>>,
+}
+
+impl_thread_safety!(WebGlVideoStream);
+
+impl WebGlVideoStream {
+    pub fn new(video_track: RtcVideoTrack, gl: Gl) -> Self {
+        let window = web_sys::window().expect("no global window");
+        let element: HtmlVideoElement = window
+            .document()
+            .expect("no document")
+            .create_element("video")
+            .expect("failed to create the video element")
+            .unchecked_into();
+
+        element.set_muted(true);
+        element.set_autoplay(true);
+        set(&element, "playsInline", true);
+
+        let stream = web_sys::MediaStream::new().expect("failed to create the MediaStream");
+        stream.add_track(&video_track.sys_handle());
+        element.set_src_object(Some(&stream));
+        let _ = element.play();
+
+        let texture = gl.create_texture().expect("failed to create the texture");
+        let (frame_tx, frame_rx) = mpsc::unbounded();
+
+        // The animation frame callback reschedules itself until the stream is closed
+        let on_frame = Rc::new(RefCell::new(None::<Closure<dyn FnMut()>>));
+        let next_frame = on_frame.clone();
+        let video = element.clone();
+        let mut last_time = -1.0;
+        *on_frame.borrow_mut() = Some(Closure::new(move || {
+            if frame_tx.is_closed() {
+                next_frame.borrow_mut().take();
+                return;
+            }
+
+            let time = video.current_time();
+            if video.ready_state() >= HtmlMediaElement::HAVE_CURRENT_DATA
+                && video.video_width() > 0
+                && time != last_time
+            {
+                last_time = time;
+
+                gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+                gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, Gl::LINEAR as i32);
+                gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, Gl::CLAMP_TO_EDGE as i32);
+                gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, Gl::CLAMP_TO_EDGE as i32);
+
+                match gl.tex_image_2d_with_u32_and_u32_and_video(
+                    Gl::TEXTURE_2D,
+                    0,
+                    Gl::RGBA as i32,
+                    Gl::RGBA,
+                    Gl::UNSIGNED_BYTE,
+                    &video,
+                ) {
+                    Ok(()) => {
+                        let _ = frame_tx.unbounded_send(VideoFrame {
+                            rotation: VideoRotation::VideoRotation0,
+                            timestamp: (time * 1_000_000.0) as i64,
+                            rtp_timestamp: None,
+                            ntp_time_ms: None,
+                            receive_time_us: None,
+                            buffer: WebGlBuffer {
+                                handle: vf_imp::WebGlBuffer {
+                                    gl: gl.clone(),
+                                    texture: texture.clone(),
+                                    width: video.video_width(),
+                                    height: video.video_height(),
+                                },
+                            },
+                        });
+                    }
+                    Err(err) => log::error!("failed to upload the video frame: {:?}", err),
+                }
+            }
+
+            if let Some(next_frame) = next_frame.borrow().as_ref() {
+                let _ = web_sys::window()
+                    .expect("no global window")
+                    .request_animation_frame(next_frame.as_ref().unchecked_ref());
+            }
+        }));
+
+        if let Some(on_frame) = on_frame.borrow().as_ref() {
+            let _ = window.request_animation_frame(on_frame.as_ref().unchecked_ref());
+        }
+
+        Self {
+            video_track,
+            element,
+            frame_rx,
+        }
+    }
+
+    pub fn track(&self) -> RtcVideoTrack {
+        self.video_track.clone()
+    }
+
+    pub fn close(&mut self) {
+        self.element.set_src_object(None);
+        self.frame_rx.close();
+    }
+}
+
+impl Drop for WebGlVideoStream {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Stream for WebGlVideoStream {
+    type Item = VideoFrame<WebGlBuffer>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.frame_rx).poll_next(cx)
+    }
+}
@@ -0,0 +1,14 @@
+use super::impl_thread_safety;
+use super::media_stream_track::impl_media_stream_track;
+use crate::media_stream_track::RtcTrackState;
+
+#[derive(Clone)]
+pub struct RtcVideoTrack {
+    pub(crate) sys_handle: web_sys::MediaStreamTrack,
+}
+
+impl_thread_safety!(RtcVideoTrack);
+
+impl RtcVideoTrack {
+    impl_media_stream_track!();
+}
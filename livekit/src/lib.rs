@@ -2,6 +2,7 @@ extern crate core;
 
 pub mod proto;
 mod room;
+mod rt;
 mod rtc_engine;
 mod signal_client;
 
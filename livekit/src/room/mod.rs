@@ -1,11 +1,13 @@
 use self::track::RemoteTrack;
 use crate::participant::ConnectionQuality;
 use crate::prelude::*;
+use crate::rt::{self, JoinHandle};
 use crate::rtc_engine::EngineError;
-use crate::rtc_engine::{EngineEvent, EngineEvents, EngineResult, RtcEngine};
+use crate::rtc_engine::{EngineEvent, EngineEvents, EngineOptions, EngineResult, RtcEngine};
 use crate::signal_client::SignalOptions;
 use livekit_protocol as proto;
 use livekit_protocol::observer::Dispatcher;
+use livekit_webrtc::prelude::RtcConfiguration;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use std::collections::HashMap;
 use std::fmt::Debug;
@@ -13,10 +15,10 @@ use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
-use tokio::task::JoinHandle;
 use tracing::{error, info, instrument, trace, Level};
 
-pub use crate::rtc_engine::SimulateScenario;
+pub use crate::rtc_engine::lk_runtime::LkRuntime;
+pub use crate::rtc_engine::{IceDiagnostics, SimulateScenario};
 
 pub mod id;
 pub mod options;
@@ -86,6 +88,7 @@ pub enum RoomEvent {
         participant: RemoteParticipant,
     },
     ConnectionStateChanged(ConnectionState),
+    IceDiagnostics(IceDiagnostics),
     Connected,
     Disconnected,
     Reconnecting,
@@ -100,6 +103,31 @@ pub enum ConnectionState {
     Unknown,
 }
 
+#[derive(Debug, Clone)]
+pub struct RoomOptions {
+    pub auto_subscribe: bool,
+    pub adaptive_stream: bool,
+    /// ICE restrictions (port range, relay only, ...).
+    /// The ICE servers sent by the server are used when rtc_config.ice_servers is empty
+    pub rtc_config: RtcConfiguration,
+    /// Use a dedicated runtime (see LkRuntime::new) instead of the global one, e.g to
+    /// ignore some network types (PeerConnectionFactoryOptions::ignored_network_types).
+    /// The local tracks published to this room must be created from the same
+    /// PeerConnectionFactory
+    pub runtime: Option<Arc<LkRuntime>>,
+}
+
+impl Default for RoomOptions {
+    fn default() -> Self {
+        Self {
+            auto_subscribe: true,
+            adaptive_stream: false,
+            rtc_config: RtcConfiguration::default(),
+            runtime: None,
+        }
+    }
+}
+
 struct RoomHandle {
     session_task: JoinHandle<()>,
     close_emitter: oneshot::Sender<()>,
@@ -121,14 +149,31 @@ impl Debug for Room {
 }
 
 impl Room {
+    /// Breaking change: the options argument is new, `RoomOptions::default()` keeps the
+    /// previous behavior of `Room::connect(url, token)`
     pub async fn connect(
         url: &str,
         token: &str,
+        options: RoomOptions,
     ) -> RoomResult<(Self, mpsc::UnboundedReceiver<RoomEvent>)> {
-        let (rtc_engine, engine_events) = RtcEngine::new();
+        let lk_runtime = options.runtime.unwrap_or_else(LkRuntime::instance);
+        let (rtc_engine, engine_events) = RtcEngine::new(lk_runtime);
         let rtc_engine = Arc::new(rtc_engine);
+
+        let signal_options = SignalOptions {
+            auto_subscribe: options.auto_subscribe,
+            adaptive_stream: options.adaptive_stream,
+            ..Default::default()
+        };
         rtc_engine
-            .connect(url, token, SignalOptions::default())
+            .connect(
+                url,
+                token,
+                EngineOptions {
+                    signal_options,
+                    rtc_config: options.rtc_config,
+                },
+            )
             .await?;
 
         let join_response = rtc_engine.join_response().unwrap();
@@ -164,7 +209,7 @@ impl Room {
         }
 
         let (close_emitter, close_receiver) = oneshot::channel();
-        let session_task = tokio::spawn(inner.clone().room_task(engine_events, close_receiver));
+        let session_task = rt::spawn(inner.clone().room_task(engine_events, close_receiver));
 
         inner.update_connection_state(ConnectionState::Connected);
 
@@ -211,6 +256,11 @@ impl Room {
         self.inner.local_participant.clone()
     }
 
+    /// Runtime of the room, the local tracks must be created from it
+    pub fn runtime(&self) -> Arc<LkRuntime> {
+        self.inner.rtc_engine.lk_runtime()
+    }
+
     pub fn connection_state(&self) -> ConnectionState {
         self.inner.state.load(Ordering::Acquire).try_into().unwrap()
     }
@@ -344,7 +394,8 @@ impl SessionInner {
             EngineEvent::MediaTrack {
                 track,
                 stream,
-                receiver: _,
+                receiver,
+                transceiver,
             } => {
                 let stream_id = stream.id();
                 let lk_stream_id = unpack_stream_id(&stream_id);
@@ -360,9 +411,9 @@ impl SessionInner {
                 let remote_participant = self.get_participant(&participant_sid.to_string().into());
 
                 if let Some(remote_participant) = remote_participant {
-                    tokio::spawn(async move {
+                    rt::spawn(async move {
                         remote_participant
-                            .add_subscribed_media_track(track_sid, track)
+                            .add_subscribed_media_track(track_sid, track, receiver, transceiver)
                             .await;
                     });
                 } else {
@@ -409,6 +460,13 @@ impl SessionInner {
             EngineEvent::ConnectionQuality { updates } => {
                 self.handle_connection_quality_update(updates)
             }
+            EngineEvent::SubscribedQualityUpdate { update } => {
+                self.local_participant.on_subscribed_quality_update(update)
+            }
+            EngineEvent::IceDiagnostics(diagnostics) => {
+                self.dispatcher
+                    .dispatch(&RoomEvent::IceDiagnostics(diagnostics));
+            }
         }
 
         Ok(())
@@ -596,7 +654,7 @@ impl SessionInner {
 
         // Create the participant task
         let (close_tx, close_rx) = oneshot::channel();
-        let participant_task = tokio::spawn(self.clone().participant_task(
+        let participant_task = rt::spawn(self.clone().participant_task(
             Participant::Remote(participant.clone()),
             participant.register_observer(),
             close_rx,
@@ -613,7 +671,7 @@ impl SessionInner {
     /// Cleanup the participant and emit an event
     #[instrument(level = Level::DEBUG)]
     fn handle_participant_disconnect(self: Arc<Self>, remote_participant: RemoteParticipant) {
-        tokio::spawn(async move {
+        rt::spawn(async move {
             for (sid, _) in &*remote_participant.tracks() {
                 remote_participant.unpublish_track(&sid);
             }
@@ -1,11 +1,13 @@
 use crate::prelude::*;
 use livekit_protocol as proto;
+use livekit_webrtc::audio_source::AudioSourceOptions;
 use livekit_webrtc::prelude::*;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum VideoCodec {
     VP8,
     H264,
+    VP9,
     AV1,
 }
 
@@ -14,9 +16,38 @@ impl VideoCodec {
         match self {
             VideoCodec::VP8 => "vp8",
             VideoCodec::H264 => "h264",
+            VideoCodec::VP9 => "vp9",
             VideoCodec::AV1 => "av1",
         }
     }
+
+    /// The SVC codecs encode all the layers in a single stream instead of simulcasting
+    pub fn is_svc(&self) -> bool {
+        matches!(self, VideoCodec::VP9 | VideoCodec::AV1)
+    }
+}
+
+/// Spatial (L) and temporal (T) layers of an SVC encoding, the _KEY modes only make the
+/// spatial layers depend on each other on the key frames
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScalabilityMode {
+    L1T3,
+    L2T3,
+    L2T3Key,
+    L3T3,
+    L3T3Key,
+}
+
+impl ScalabilityMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScalabilityMode::L1T3 => "L1T3",
+            ScalabilityMode::L2T3 => "L2T3",
+            ScalabilityMode::L2T3Key => "L2T3_KEY",
+            ScalabilityMode::L3T3 => "L3T3",
+            ScalabilityMode::L3T3Key => "L3T3_KEY",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -58,19 +89,21 @@ impl AudioPreset {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Processing applied to the captured audio. It is opt-in: by default the audio is sent
+/// as captured
+#[derive(Debug, Clone, Default)]
 pub struct AudioCaptureOptions {
     pub echo_cancellation: bool,
     pub noise_suppression: bool,
     pub auto_gain_control: bool,
 }
 
-impl Default for AudioCaptureOptions {
-    fn default() -> Self {
+impl From<AudioCaptureOptions> for AudioSourceOptions {
+    fn from(options: AudioCaptureOptions) -> Self {
         Self {
-            echo_cancellation: true,
-            noise_suppression: true,
-            auto_gain_control: true,
+            echo_cancellation: options.echo_cancellation,
+            noise_suppression: options.noise_suppression,
+            auto_gain_control: options.auto_gain_control,
         }
     }
 }
@@ -90,10 +123,14 @@ impl Default for VideoCaptureOptions {
 
 #[derive(Clone, Debug)]
 pub struct TrackPublishOptions {
-    // If the encodings aren't set, LiveKit will compute the most appropriate ones
+    /// If the encodings aren't set, LiveKit will compute the most appropriate ones
     pub video_encoding: Option<VideoEncoding>,
     pub audio_encoding: Option<AudioEncoding>,
     pub video_codec: VideoCodec,
+    /// Only used by the SVC codecs (VP9 & AV1), defaults to L3T3_KEY
+    pub scalability_mode: Option<ScalabilityMode>,
+    /// Published on demand when a subscriber can't decode the SVC codec (VP8 or H264)
+    pub backup_codec: Option<VideoCodec>,
     pub dtx: bool,
     pub red: bool,
     pub simulcast: bool,
@@ -107,6 +144,8 @@ impl Default for TrackPublishOptions {
             video_encoding: None,
             audio_encoding: None,
             video_codec: VideoCodec::VP8,
+            scalability_mode: None,
+            backup_codec: None,
             dtx: true,
             red: true,
             simulcast: true,
@@ -157,6 +196,17 @@ pub fn compute_video_encodings(
         },
     };
 
+    if options.video_codec.is_svc() {
+        // A single encoding, the layers are described by the scalability mode
+        let scalability_mode = options.scalability_mode.unwrap_or(ScalabilityMode::L3T3Key);
+        return vec![RtpEncodingParameters {
+            max_bitrate: Some(initial_preset.encoding.max_bitrate),
+            max_framerate: Some(initial_preset.encoding.max_framerate),
+            scalability_mode: Some(scalability_mode.as_str().to_owned()),
+            ..Default::default()
+        }];
+    }
+
     if !options.simulcast {
         return into_rtp_encodings(width, height, &[initial_preset]);
     }
@@ -290,6 +340,31 @@ pub fn video_layers_from_encodings(
         }];
     }
 
+    if let Some(scalability_mode) = encodings[0].scalability_mode.as_ref() {
+        // SVC: each spatial layer halves the resolution of the one above it
+        let spatial_layers = spatial_layers_from_scalability_mode(scalability_mode);
+        let bitrate = encodings[0].max_bitrate.unwrap_or(0);
+        let qualities = [
+            proto::VideoQuality::High,
+            proto::VideoQuality::Medium,
+            proto::VideoQuality::Low,
+        ];
+
+        let mut layers = Vec::with_capacity(spatial_layers);
+        for (i, quality) in qualities.iter().take(spatial_layers).enumerate() {
+            let scale = 2u32.pow(i as u32);
+            layers.push(proto::VideoLayer {
+                quality: *quality as i32,
+                width: (width + scale - 1) / scale,
+                height: (height + scale - 1) / scale,
+                bitrate: (bitrate / 3u64.pow(i as u32)) as u32,
+                ssrc: 0,
+            });
+        }
+
+        return layers;
+    }
+
     let mut layers = Vec::with_capacity(encodings.len());
     for encoding in encodings {
         let scale = encoding.scale_resolution_down_by.unwrap_or(1.0);
@@ -307,6 +382,16 @@ pub fn video_layers_from_encodings(
     layers
 }
 
+/// Number of spatial layers of a scalability mode (the digit following the L)
+fn spatial_layers_from_scalability_mode(scalability_mode: &str) -> usize {
+    scalability_mode
+        .strip_prefix('L')
+        .and_then(|mode| mode.chars().next())
+        .and_then(|spatial| spatial.to_digit(10))
+        .map(|spatial| spatial.clamp(1, 3) as usize)
+        .unwrap_or(1)
+}
+
 const VIDEO_RIDS: &[char] = &['q', 'h', 'f'];
 
 pub mod audio {
@@ -395,3 +480,143 @@ pub mod screenshare {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(
+        quality: proto::VideoQuality,
+        width: u32,
+        height: u32,
+        bitrate: u32,
+    ) -> proto::VideoLayer {
+        proto::VideoLayer {
+            quality: quality as i32,
+            width,
+            height,
+            bitrate,
+            ssrc: 0,
+        }
+    }
+
+    fn svc_options(
+        video_codec: VideoCodec,
+        scalability_mode: Option<ScalabilityMode>,
+    ) -> TrackPublishOptions {
+        TrackPublishOptions {
+            video_codec,
+            scalability_mode,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn svc_encodings() {
+        // A single encoding even when simulcast is enabled, L3T3_KEY by default
+        for video_codec in [VideoCodec::VP9, VideoCodec::AV1] {
+            let encodings = compute_video_encodings(1280, 720, &svc_options(video_codec, None));
+            assert_eq!(encodings.len(), 1);
+            assert_eq!(encodings[0].scalability_mode.as_deref(), Some("L3T3_KEY"));
+            assert_eq!(
+                encodings[0].max_bitrate,
+                Some(video::H720.encoding.max_bitrate)
+            );
+            assert_eq!(
+                encodings[0].max_framerate,
+                Some(video::H720.encoding.max_framerate)
+            );
+            assert_eq!(encodings[0].rid, "");
+            assert_eq!(encodings[0].scale_resolution_down_by, None);
+        }
+
+        for (mode, expected) in [
+            (ScalabilityMode::L1T3, "L1T3"),
+            (ScalabilityMode::L2T3, "L2T3"),
+            (ScalabilityMode::L2T3Key, "L2T3_KEY"),
+            (ScalabilityMode::L3T3, "L3T3"),
+        ] {
+            let encodings =
+                compute_video_encodings(1280, 720, &svc_options(VideoCodec::VP9, Some(mode)));
+            assert_eq!(encodings.len(), 1);
+            assert_eq!(encodings[0].scalability_mode.as_deref(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn svc_layers() {
+        use proto::VideoQuality::{High, Low, Medium};
+
+        let layers = |mode: ScalabilityMode, width: u32, height: u32| {
+            let options = svc_options(VideoCodec::VP9, Some(mode));
+            let encodings = compute_video_encodings(width, height, &options);
+            video_layers_from_encodings(width, height, &encodings)
+        };
+
+        // Each spatial layer halves the resolution and divides the bitrate by 3
+        assert_eq!(
+            layers(ScalabilityMode::L3T3Key, 1280, 720),
+            vec![
+                layer(High, 1280, 720, 1_700_000),
+                layer(Medium, 640, 360, 566_666),
+                layer(Low, 320, 180, 188_888),
+            ]
+        );
+        assert_eq!(
+            layers(ScalabilityMode::L2T3, 1280, 720),
+            vec![
+                layer(High, 1280, 720, 1_700_000),
+                layer(Medium, 640, 360, 566_666),
+            ]
+        );
+        assert_eq!(
+            layers(ScalabilityMode::L1T3, 1280, 720),
+            vec![layer(High, 1280, 720, 1_700_000)]
+        );
+
+        // The sizes of the lower layers are rounded up
+        assert_eq!(
+            layers(ScalabilityMode::L3T3, 1279, 719),
+            vec![
+                layer(High, 1279, 719, 1_700_000),
+                layer(Medium, 640, 360, 566_666),
+                layer(Low, 320, 180, 188_888),
+            ]
+        );
+    }
+
+    #[test]
+    fn simulcast_layers() {
+        use proto::VideoQuality::{High, Low, Medium};
+
+        let encodings = compute_video_encodings(1280, 720, &TrackPublishOptions::default());
+        assert_eq!(
+            video_layers_from_encodings(1280, 720, &encodings),
+            vec![
+                layer(Low, 320, 180, 120_000),
+                layer(Medium, 640, 360, 300_000),
+                layer(High, 1280, 720, 1_700_000),
+            ]
+        );
+
+        assert_eq!(
+            video_layers_from_encodings(1280, 720, &[]),
+            vec![layer(High, 1280, 720, 0)]
+        );
+    }
+
+    #[test]
+    fn spatial_layers() {
+        assert_eq!(spatial_layers_from_scalability_mode("L1T3"), 1);
+        assert_eq!(spatial_layers_from_scalability_mode("L2T3"), 2);
+        assert_eq!(spatial_layers_from_scalability_mode("L2T3_KEY"), 2);
+        assert_eq!(spatial_layers_from_scalability_mode("L3T3"), 3);
+        assert_eq!(spatial_layers_from_scalability_mode("L3T3_KEY"), 3);
+
+        // Clamped to the 3 qualities, invalid modes have a single layer
+        assert_eq!(spatial_layers_from_scalability_mode("L4T3"), 3);
+        assert_eq!(spatial_layers_from_scalability_mode("L0T1"), 1);
+        assert_eq!(spatial_layers_from_scalability_mode("S2T1"), 1);
+        assert_eq!(spatial_layers_from_scalability_mode(""), 1);
+    }
+}
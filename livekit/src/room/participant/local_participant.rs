@@ -3,7 +3,9 @@ use crate::options;
 use crate::options::compute_video_encodings;
 use crate::options::video_layers_from_encodings;
 use crate::options::TrackPublishOptions;
+use crate::options::VideoCodec;
 use crate::prelude::*;
+use crate::rt;
 use crate::rtc_engine::RtcEngine;
 use livekit_protocol as proto;
 use livekit_webrtc::rtp_parameters::RtpEncodingParameters;
@@ -12,7 +14,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 use tokio::sync::mpsc;
-use tracing::debug;
+use tracing::{debug, error};
 
 #[derive(Clone)]
 pub struct LocalParticipant {
@@ -71,6 +73,22 @@ impl LocalParticipant {
 
                 encodings = compute_video_encodings(req.width, req.height, &options);
                 req.layers = video_layers_from_encodings(req.width, req.height, &encodings);
+
+                // Without it, the server assumes the track uses its preferred codec
+                req.simulcast_codecs = vec![proto::SimulcastCodec {
+                    codec: options.video_codec.as_str().to_owned(),
+                    cid: track.rtc_track().id(),
+                    ..Default::default()
+                }];
+
+                // The backup codec gets its own track (and cid) when the server requests it
+                if let Some(backup_codec) = backup_codec(&options) {
+                    req.simulcast_codecs.push(proto::SimulcastCodec {
+                        codec: backup_codec.as_str().to_owned(),
+                        cid: String::default(),
+                        ..Default::default()
+                    });
+                }
             }
             LocalTrack::Audio(_audio_track) => {
                 // Setup audio encoding
@@ -99,7 +117,7 @@ impl LocalParticipant {
         track.update_transceiver(Some(transceiver));
         track.start();
 
-        tokio::spawn({
+        rt::spawn({
             let rtc_engine = self.rtc_engine.clone();
             async move {
                 let _ = rtc_engine.negotiate_publisher().await;
@@ -130,6 +148,13 @@ impl LocalParticipant {
             self.rtc_engine.remove_track(sender).await?;
             track.update_transceiver(None);
 
+            if let LocalTrack::Video(video_track) = &track {
+                if let Some(transceiver) = video_track.backup_transceiver() {
+                    self.rtc_engine.remove_track(transceiver.sender()).await?;
+                    video_track.update_backup_transceiver(None);
+                }
+            }
+
             self.inner
                 .dispatcher
                 .dispatch(&ParticipantEvent::LocalTrackUnpublished {
@@ -137,7 +162,7 @@ impl LocalParticipant {
                 });
             publication.update_track(None);
 
-            tokio::spawn({
+            rt::spawn({
                 let rtc_engine = self.rtc_engine.clone();
                 async move {
                     let _ = rtc_engine.negotiate_publisher().await;
@@ -171,6 +196,90 @@ impl LocalParticipant {
             .map_err(Into::into)
     }
 
+    /// The server asks for the backup codec once a subscriber can't decode the SVC codec
+    pub(crate) fn on_subscribed_quality_update(&self, update: proto::SubscribedQualityUpdate) {
+        let publication = match self.get_track_publication(&update.track_sid.into()) {
+            Some(publication) => publication,
+            None => return,
+        };
+
+        let options = publication.publish_options();
+        let backup_codec = match backup_codec(&options) {
+            Some(backup_codec) => backup_codec,
+            None => return,
+        };
+
+        let video_track = match publication.track() {
+            Some(LocalTrack::Video(video_track)) => video_track,
+            _ => return,
+        };
+
+        let qualities = backup_qualities(&update.subscribed_codecs, backup_codec);
+        if let Some(transceiver) = video_track.backup_transceiver() {
+            // Already published, pause the layers no subscriber requested
+            let sender = transceiver.sender();
+            let mut parameters = sender.parameters();
+            set_active_layers(&mut parameters.encodings, &qualities);
+            if let Err(err) = sender.set_parameters(parameters) {
+                error!("failed to update the backup codec layers: {:?}", err);
+            }
+            return;
+        }
+
+        if qualities.is_empty() {
+            return;
+        }
+
+        rt::spawn({
+            let local_participant = self.clone();
+            async move {
+                if let Err(err) = local_participant
+                    .publish_backup_codec(video_track, backup_codec, options)
+                    .await
+                {
+                    error!("failed to publish the backup codec: {:?}", err);
+                }
+            }
+        });
+    }
+
+    /// Publish the same source a second time, using the backup codec. The server forwards
+    /// it to the subscribers that can't decode the primary codec
+    async fn publish_backup_codec(
+        &self,
+        track: LocalVideoTrack,
+        backup_codec: VideoCodec,
+        options: TrackPublishOptions,
+    ) -> RoomResult<()> {
+        let simulcast_track = match track.create_backup_track(&self.rtc_engine.lk_runtime()) {
+            Some(simulcast_track) => simulcast_track,
+            None => return Ok(()), // Already published, or the source is encoded
+        };
+
+        let (req, encodings) =
+            backup_track_request(&track, simulcast_track.id(), backup_codec, options);
+
+        let res = async {
+            self.rtc_engine.add_track(req).await?;
+            self.rtc_engine
+                .create_simulcast_sender(simulcast_track, backup_codec, encodings)
+                .await
+        }
+        .await;
+
+        match res {
+            Ok(transceiver) => track.update_backup_transceiver(Some(transceiver)),
+            Err(err) => {
+                // Release the backup track so the next request can retry
+                track.update_backup_transceiver(None);
+                return Err(err.into());
+            }
+        }
+
+        self.rtc_engine.negotiate_publisher().await?;
+        Ok(())
+    }
+
     #[inline]
     pub fn get_track_publication(&self, sid: &TrackSid) -> Option<LocalTrackPublication> {
         self.inner.tracks.read().get(sid).map(|track| {
@@ -247,3 +356,261 @@ impl LocalParticipant {
         self.inner.set_connection_quality(quality);
     }
 }
+
+/// AddTrackRequest publishing the backup codec under the sid of the primary track, and the
+/// encodings of its sender
+fn backup_track_request(
+    track: &LocalVideoTrack,
+    cid: String,
+    backup_codec: VideoCodec,
+    options: TrackPublishOptions,
+) -> (proto::AddTrackRequest, Vec<RtpEncodingParameters>) {
+    let capture_options = track.capture_options();
+    let width = capture_options.resolution.width;
+    let height = capture_options.resolution.height;
+    let options = TrackPublishOptions {
+        video_codec: backup_codec,
+        ..options
+    };
+    let encodings = compute_video_encodings(width, height, &options);
+
+    let req = proto::AddTrackRequest {
+        cid: cid.clone(),
+        name: options.name.clone(),
+        r#type: proto::TrackType::Video as i32,
+        width,
+        height,
+        muted: track.is_muted(),
+        source: proto::TrackSource::from(options.source) as i32,
+        layers: video_layers_from_encodings(width, height, &encodings),
+        simulcast_codecs: vec![proto::SimulcastCodec {
+            codec: backup_codec.as_str().to_owned(),
+            cid,
+            ..Default::default()
+        }],
+        sid: track.sid().to_string(),
+        ..Default::default()
+    };
+
+    (req, encodings)
+}
+
+/// Qualities of the backup codec requested by the subscribers
+fn backup_qualities(
+    subscribed_codecs: &[proto::SubscribedCodec],
+    backup_codec: VideoCodec,
+) -> Vec<proto::VideoQuality> {
+    subscribed_codecs
+        .iter()
+        .filter(|subscribed| subscribed.codec.eq_ignore_ascii_case(backup_codec.as_str()))
+        .flat_map(|subscribed| subscribed.qualities.iter())
+        .filter(|quality| quality.enabled)
+        .filter_map(|quality| proto::VideoQuality::from_i32(quality.quality))
+        .collect()
+}
+
+/// Only the encodings of the requested qualities are active, a single encoding is active
+/// as long as any quality is requested
+fn set_active_layers(encodings: &mut [RtpEncodingParameters], qualities: &[proto::VideoQuality]) {
+    let single = encodings.len() == 1;
+    for encoding in encodings {
+        encoding.active = if single {
+            !qualities.is_empty()
+        } else {
+            matches!(
+                options::video_quality_for_rid(&encoding.rid),
+                Some(quality) if qualities.contains(&quality)
+            )
+        };
+    }
+}
+
+/// Only the SVC codecs have a backup, and it must be a codec every subscriber can decode
+fn backup_codec(options: &TrackPublishOptions) -> Option<VideoCodec> {
+    match options.backup_codec {
+        Some(backup_codec) if options.video_codec.is_svc() && !backup_codec.is_svc() => {
+            Some(backup_codec)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::options::{video, ScalabilityMode, VideoCaptureOptions};
+    use crate::LkRuntime;
+    use livekit_webrtc::video_source::native::{EncodedVideoSource, NativeVideoSource};
+
+    fn svc_options(backup_codec: Option<VideoCodec>) -> TrackPublishOptions {
+        TrackPublishOptions {
+            video_codec: VideoCodec::VP9,
+            scalability_mode: Some(ScalabilityMode::L3T3Key),
+            backup_codec,
+            name: "camera".to_owned(),
+            source: TrackSource::Camera,
+            ..Default::default()
+        }
+    }
+
+    fn subscribed_codec(
+        codec: &str,
+        qualities: &[(proto::VideoQuality, bool)],
+    ) -> proto::SubscribedCodec {
+        proto::SubscribedCodec {
+            codec: codec.to_owned(),
+            qualities: qualities
+                .iter()
+                .map(|(quality, enabled)| proto::SubscribedQuality {
+                    quality: *quality as i32,
+                    enabled: *enabled,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn backup_codec_requires_svc() {
+        assert_eq!(
+            backup_codec(&svc_options(Some(VideoCodec::VP8))),
+            Some(VideoCodec::VP8)
+        );
+        assert_eq!(
+            backup_codec(&svc_options(Some(VideoCodec::H264))),
+            Some(VideoCodec::H264)
+        );
+        assert_eq!(backup_codec(&svc_options(None)), None);
+
+        // The backup must be decodable by every subscriber
+        assert_eq!(backup_codec(&svc_options(Some(VideoCodec::AV1))), None);
+
+        // The simulcast codecs don't have a backup
+        let options = TrackPublishOptions {
+            video_codec: VideoCodec::VP8,
+            backup_codec: Some(VideoCodec::H264),
+            ..Default::default()
+        };
+        assert_eq!(backup_codec(&options), None);
+    }
+
+    #[test]
+    fn requested_backup_qualities() {
+        use proto::VideoQuality::{High, Low, Medium};
+
+        let subscribed_codecs = [
+            subscribed_codec("vp9", &[(High, true), (Medium, true), (Low, true)]),
+            subscribed_codec("VP8", &[(High, false), (Medium, true), (Low, true)]),
+        ];
+        assert_eq!(
+            backup_qualities(&subscribed_codecs, VideoCodec::VP8),
+            vec![Medium, Low]
+        );
+        assert!(backup_qualities(&subscribed_codecs, VideoCodec::H264).is_empty());
+
+        let subscribed_codecs = [subscribed_codec("vp8", &[(High, false), (Low, false)])];
+        assert!(backup_qualities(&subscribed_codecs, VideoCodec::VP8).is_empty());
+    }
+
+    #[test]
+    fn active_backup_layers() {
+        use proto::VideoQuality::{High, Low};
+
+        let mut encodings = compute_video_encodings(
+            1280,
+            720,
+            &TrackPublishOptions {
+                video_codec: VideoCodec::VP8,
+                ..Default::default()
+            },
+        );
+        let active = |encodings: &[RtpEncodingParameters]| -> Vec<bool> {
+            encodings.iter().map(|encoding| encoding.active).collect()
+        };
+
+        // q, h, f
+        set_active_layers(&mut encodings, &[Low, High]);
+        assert_eq!(active(&encodings), vec![true, false, true]);
+        set_active_layers(&mut encodings, &[]);
+        assert_eq!(active(&encodings), vec![false, false, false]);
+
+        let mut encodings = vec![RtpEncodingParameters::default()];
+        set_active_layers(&mut encodings, &[Low]);
+        assert_eq!(active(&encodings), vec![true]);
+        set_active_layers(&mut encodings, &[]);
+        assert_eq!(active(&encodings), vec![false]);
+    }
+
+    #[test]
+    fn backup_track() {
+        let runtime = LkRuntime::instance();
+        let track = LocalVideoTrack::create_video_track(
+            "camera",
+            VideoCaptureOptions::default(),
+            NativeVideoSource::default(),
+            &runtime,
+        );
+
+        // Only one backup track at a time, released with the backup transceiver
+        let backup_track = track.create_backup_track(&runtime).unwrap();
+        assert_ne!(backup_track.id(), track.rtc_track().id());
+        assert!(track.create_backup_track(&runtime).is_none());
+        track.update_backup_transceiver(None);
+        assert!(track.create_backup_track(&runtime).is_some());
+
+        // The encoded sources can't be encoded a second time
+        let encoded_track = LocalVideoTrack::create_encoded_video_track(
+            "camera",
+            VideoCaptureOptions::default(),
+            EncodedVideoSource::default(),
+            &runtime,
+        );
+        assert!(encoded_track.create_backup_track(&runtime).is_none());
+    }
+
+    #[test]
+    fn backup_request() {
+        let runtime = LkRuntime::instance();
+        let track = LocalVideoTrack::create_video_track(
+            "camera",
+            VideoCaptureOptions::default(),
+            NativeVideoSource::default(),
+            &runtime,
+        );
+        track.set_muted(true);
+
+        let (req, encodings) = backup_track_request(
+            &track,
+            "backup_cid".to_owned(),
+            VideoCodec::VP8,
+            svc_options(Some(VideoCodec::VP8)),
+        );
+
+        // Published under the sid of the primary track, simulcasted with the backup codec
+        assert_eq!(req.sid, track.sid().to_string());
+        assert_eq!(req.cid, "backup_cid");
+        assert_eq!(req.name, "camera");
+        assert_eq!(req.r#type, proto::TrackType::Video as i32);
+        assert_eq!(req.source, proto::TrackSource::Camera as i32);
+        assert!(req.muted);
+        assert_eq!((req.width, req.height), (1280, 720));
+        assert_eq!(
+            req.simulcast_codecs,
+            vec![proto::SimulcastCodec {
+                codec: "vp8".to_owned(),
+                cid: "backup_cid".to_owned(),
+                ..Default::default()
+            }]
+        );
+
+        assert_eq!(encodings.len(), 3);
+        assert!(encodings.iter().all(|e| e.scalability_mode.is_none()));
+        assert_eq!(
+            encodings[2].max_bitrate,
+            Some(video::H720.encoding.max_bitrate)
+        );
+        assert_eq!(
+            req.layers,
+            video_layers_from_encodings(1280, 720, &encodings)
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use super::{ConnectionQuality, ParticipantInner};
 use crate::prelude::*;
+use crate::rt::{self, timeout};
 use crate::track::TrackError;
 use livekit_protocol as proto;
 use livekit_webrtc::prelude::*;
@@ -9,7 +10,6 @@ use std::fmt::Debug;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
-use tokio::time::timeout;
 use tracing::{debug, error, instrument, Level};
 
 const ADD_TRACK_TIMEOUT: Duration = Duration::from_secs(5);
@@ -67,6 +67,8 @@ impl RemoteParticipant {
         &self,
         sid: TrackSid,
         media_track: MediaStreamTrack,
+        receiver: RtpReceiver,
+        transceiver: RtpTransceiver,
     ) {
         let wait_publication = {
             let participant = self.clone();
@@ -78,7 +80,7 @@ impl RemoteParticipant {
                         return publication;
                     }
 
-                    tokio::task::yield_now().await; // Remove yield
+                    rt::yield_now().await; // Remove yield
                 }
             }
         };
@@ -91,6 +93,7 @@ impl RemoteParticipant {
                             remote_publication.sid().into(),
                             remote_publication.name(),
                             rtc_track,
+                            receiver,
                         );
                         RemoteTrack::Audio(audio_track)
                     } else {
@@ -103,6 +106,7 @@ impl RemoteParticipant {
                             remote_publication.sid().into(),
                             remote_publication.name(),
                             rtc_track,
+                            receiver,
                         );
                         RemoteTrack::Video(video_track)
                     } else {
@@ -113,6 +117,7 @@ impl RemoteParticipant {
 
             debug!("starting track: {:?}", sid);
 
+            track.update_transceiver(Some(transceiver));
             remote_publication.update_track(Some(track.clone().into()));
             track.set_muted(remote_publication.is_muted());
             track.update_info(proto::TrackInfo {
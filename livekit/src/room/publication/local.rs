@@ -9,8 +9,6 @@ use std::sync::Arc;
 #[derive(Debug)]
 struct LocalTrackPublicationInner {
     publication_inner: TrackPublicationInner,
-
-    #[allow(unused)] // TODO(theomonnom)
     options: Mutex<TrackPublishOptions>,
 }
 
@@ -86,6 +84,11 @@ impl LocalTrackPublication {
         false
     }
 
+    #[inline]
+    pub(crate) fn publish_options(&self) -> TrackPublishOptions {
+        self.inner.options.lock().clone()
+    }
+
     #[inline]
     pub(crate) fn update_track(&self, track: Option<Track>) {
         self.inner.publication_inner.update_track(track);
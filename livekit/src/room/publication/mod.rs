@@ -1,5 +1,6 @@
 use super::track::{TrackDimension, TrackEvent};
 use crate::prelude::*;
+use crate::rt;
 use crate::track::Track;
 use futures_util::stream::StreamExt;
 use livekit_protocol as proto;
@@ -62,7 +63,7 @@ impl TrackPublicationInner {
 
         if let Some(track) = track.as_ref() {
             let track_stream = UnboundedReceiverStream::new(track.register_observer());
-            tokio::spawn({
+            rt::spawn({
                 let dispatcher = self.dispatcher.clone();
                 let notifier = self.close_notifier.clone();
 
@@ -2,7 +2,10 @@ use super::TrackInner;
 use crate::options::AudioCaptureOptions;
 use crate::prelude::*;
 use crate::rtc_engine::lk_runtime::LkRuntime;
+#[cfg(not(target_arch = "wasm32"))]
 use crate::webrtc::peer_connection_factory::native::PeerConnectionFactoryExt;
+#[cfg(target_arch = "wasm32")]
+use crate::webrtc::peer_connection_factory::web::PeerConnectionFactoryExt;
 use livekit_protocol as proto;
 use livekit_webrtc::prelude::*;
 use parking_lot::Mutex;
@@ -32,7 +35,7 @@ impl Debug for LocalAudioTrack {
 }
 
 impl LocalAudioTrack {
-    pub(crate) fn new(
+    pub fn new(
         name: String,
         rtc_track: RtcAudioTrack,
         capture_options: AudioCaptureOptions,
@@ -134,16 +137,54 @@ impl LocalAudioTrack {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl LocalAudioTrack {
+    /// The capture options are applied to the source, the audio processing module is only
+    /// used when one of them is enabled.
+    /// The runtime must be the one of the room the track is published to (see Room::runtime).
+    /// Breaking change: the runtime argument is new, pass `&room.runtime()`
     pub fn create_audio_track(
         name: &str,
         options: AudioCaptureOptions,
         source: livekit_webrtc::audio_source::native::NativeAudioSource,
+        runtime: &LkRuntime,
     ) -> LocalAudioTrack {
-        let rtc_track = LkRuntime::instance()
+        source.set_audio_options(options.clone().into());
+        let rtc_track = runtime
             .pc_factory()
             .create_audio_track(&livekit_webrtc::native::create_random_uuid(), source);
 
         Self::new(name.to_string(), rtc_track, options)
     }
+
+    /// The track sends the encoded packets as is, the capture options are ignored
+    pub fn create_encoded_audio_track(
+        name: &str,
+        options: AudioCaptureOptions,
+        source: livekit_webrtc::audio_source::native::EncodedAudioSource,
+        runtime: &LkRuntime,
+    ) -> LocalAudioTrack {
+        let rtc_track = runtime
+            .pc_factory()
+            .create_encoded_audio_track(&livekit_webrtc::native::create_random_uuid(), source);
+
+        Self::new(name.to_string(), rtc_track, options)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalAudioTrack {
+    /// The source wraps a track captured by the browser (e.g getUserMedia), the capture
+    /// options are applied as constraints of the track
+    pub fn create_audio_track(
+        name: &str,
+        options: AudioCaptureOptions,
+        source: livekit_webrtc::audio_source::web::WebAudioSource,
+        runtime: &LkRuntime,
+    ) -> LocalAudioTrack {
+        source.set_audio_options(options.clone().into());
+        let rtc_track = runtime.pc_factory().create_audio_track(name, source);
+
+        Self::new(name.to_string(), rtc_track, options)
+    }
 }
@@ -2,17 +2,33 @@ use super::TrackInner;
 use crate::rtc_engine::lk_runtime::LkRuntime;
 use crate::{options::VideoCaptureOptions, prelude::*};
 use livekit_protocol as proto;
+#[cfg(not(target_arch = "wasm32"))]
 use livekit_webrtc::peer_connection_factory::native::PeerConnectionFactoryExt;
+#[cfg(target_arch = "wasm32")]
+use livekit_webrtc::peer_connection_factory::web::PeerConnectionFactoryExt;
 use livekit_webrtc::prelude::*;
 use parking_lot::Mutex;
 use std::fmt::Debug;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
+/// Source of the raw frames, a second track is created from it to publish the backup codec
+/// (the frames of an EncodedVideoSource can't be re-encoded)
+#[derive(Debug, Clone)]
+enum BackupSource {
+    #[cfg(not(target_arch = "wasm32"))]
+    Native(livekit_webrtc::video_source::native::NativeVideoSource),
+    #[cfg(target_arch = "wasm32")]
+    Web(livekit_webrtc::video_source::web::WebVideoSource),
+}
+
 #[derive(Debug)]
 struct LocalVideoTrackInner {
     track_inner: TrackInner,
     capture_options: Mutex<VideoCaptureOptions>,
+    backup_source: Option<BackupSource>,
+    backup_track: Mutex<Option<RtcVideoTrack>>,
+    backup_transceiver: Mutex<Option<RtpTransceiver>>,
 }
 
 #[derive(Clone)]
@@ -35,6 +51,15 @@ impl LocalVideoTrack {
         name: String,
         rtc_track: RtcVideoTrack,
         capture_options: VideoCaptureOptions,
+    ) -> Self {
+        Self::with_backup_source(name, rtc_track, capture_options, None)
+    }
+
+    fn with_backup_source(
+        name: String,
+        rtc_track: RtcVideoTrack,
+        capture_options: VideoCaptureOptions,
+        backup_source: Option<BackupSource>,
     ) -> Self {
         Self {
             inner: Arc::new(LocalVideoTrackInner {
@@ -45,6 +70,9 @@ impl LocalVideoTrack {
                     MediaStreamTrack::Video(rtc_track),
                 ),
                 capture_options: Mutex::new(capture_options),
+                backup_source,
+                backup_track: Default::default(),
+                backup_transceiver: Default::default(),
             }),
         }
     }
@@ -81,12 +109,14 @@ impl LocalVideoTrack {
 
     #[inline]
     pub fn start(&self) {
-        self.inner.track_inner.start()
+        self.inner.track_inner.start();
+        self.sync_backup_track();
     }
 
     #[inline]
     pub fn stop(&self) {
-        self.inner.track_inner.stop()
+        self.inner.track_inner.stop();
+        self.sync_backup_track();
     }
 
     #[inline]
@@ -96,7 +126,8 @@ impl LocalVideoTrack {
 
     #[inline]
     pub fn set_muted(&self, muted: bool) {
-        self.inner.track_inner.set_muted(muted)
+        self.inner.track_inner.set_muted(muted);
+        self.sync_backup_track();
     }
 
     #[inline]
@@ -131,18 +162,106 @@ impl LocalVideoTrack {
     pub(crate) fn update_info(&self, info: proto::TrackInfo) {
         self.inner.track_inner.update_info(info)
     }
+
+    /// Create the track used to publish the backup codec, None if the track was created
+    /// from an encoded source or if a backup track already exists
+    pub(crate) fn create_backup_track(&self, runtime: &LkRuntime) -> Option<RtcVideoTrack> {
+        let source = self.inner.backup_source.clone()?;
+        let mut backup_track = self.inner.backup_track.lock();
+        if backup_track.is_some() {
+            return None;
+        }
+
+        let pc_factory = runtime.pc_factory();
+        let rtc_track = match source {
+            #[cfg(not(target_arch = "wasm32"))]
+            BackupSource::Native(source) => {
+                pc_factory.create_video_track(&livekit_webrtc::native::create_random_uuid(), source)
+            }
+            #[cfg(target_arch = "wasm32")]
+            BackupSource::Web(source) => pc_factory.create_video_track(&self.name(), source),
+        };
+
+        rtc_track.set_enabled(self.rtc_track().enabled());
+        *backup_track = Some(rtc_track.clone());
+        Some(rtc_track)
+    }
+
+    #[inline]
+    pub(crate) fn backup_transceiver(&self) -> Option<RtpTransceiver> {
+        self.inner.backup_transceiver.lock().clone()
+    }
+
+    /// Removing the backup transceiver also releases the backup track
+    pub(crate) fn update_backup_transceiver(&self, transceiver: Option<RtpTransceiver>) {
+        if transceiver.is_none() {
+            self.inner.backup_track.lock().take();
+        }
+        *self.inner.backup_transceiver.lock() = transceiver;
+    }
+
+    fn sync_backup_track(&self) {
+        if let Some(backup_track) = self.inner.backup_track.lock().as_ref() {
+            backup_track.set_enabled(self.rtc_track().enabled());
+        }
+    }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl LocalVideoTrack {
+    /// The runtime must be the one of the room the track is published to (see Room::runtime).
+    /// Breaking change: the runtime argument is new, pass `&room.runtime()`
     pub fn create_video_track(
         name: &str,
         options: VideoCaptureOptions,
         source: livekit_webrtc::video_source::native::NativeVideoSource,
+        runtime: &LkRuntime,
+    ) -> LocalVideoTrack {
+        let rtc_track = runtime.pc_factory().create_video_track(
+            &livekit_webrtc::native::create_random_uuid(),
+            source.clone(),
+        );
+
+        Self::with_backup_source(
+            name.to_string(),
+            rtc_track,
+            options,
+            Some(BackupSource::Native(source)),
+        )
+    }
+
+    pub fn create_encoded_video_track(
+        name: &str,
+        options: VideoCaptureOptions,
+        source: livekit_webrtc::video_source::native::EncodedVideoSource,
+        runtime: &LkRuntime,
     ) -> LocalVideoTrack {
-        let rtc_track = LkRuntime::instance()
+        let rtc_track = runtime
             .pc_factory()
-            .create_video_track(&livekit_webrtc::native::create_random_uuid(), source);
+            .create_encoded_video_track(&livekit_webrtc::native::create_random_uuid(), source);
 
         Self::new(name.to_string(), rtc_track, options)
     }
 }
+
+#[cfg(target_arch = "wasm32")]
+impl LocalVideoTrack {
+    /// The source wraps a track captured by the browser (e.g getUserMedia)
+    pub fn create_video_track(
+        name: &str,
+        options: VideoCaptureOptions,
+        source: livekit_webrtc::video_source::web::WebVideoSource,
+        runtime: &LkRuntime,
+    ) -> LocalVideoTrack {
+        let rtc_track = runtime
+            .pc_factory()
+            .create_video_track(name, source.clone());
+
+        Self::with_backup_source(
+            name.to_string(),
+            rtc_track,
+            options,
+            Some(BackupSource::Web(source)),
+        )
+    }
+}
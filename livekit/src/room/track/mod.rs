@@ -22,6 +22,8 @@ pub use remote_video_track::*;
 pub enum TrackError {
     #[error("could not find published track with sid: {0}")]
     TrackNotFound(String),
+    #[error("failed to snapshot the track: {0}")]
+    Snapshot(String),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
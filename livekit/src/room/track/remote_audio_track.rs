@@ -4,11 +4,13 @@ use livekit_protocol as proto;
 use livekit_webrtc::prelude::*;
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 #[derive(Clone)]
 pub struct RemoteAudioTrack {
     pub(crate) inner: Arc<TrackInner>,
+    receiver: RtpReceiver,
 }
 
 impl Debug for RemoteAudioTrack {
@@ -22,7 +24,12 @@ impl Debug for RemoteAudioTrack {
 }
 
 impl RemoteAudioTrack {
-    pub(crate) fn new(sid: TrackSid, name: String, rtc_track: RtcAudioTrack) -> Self {
+    pub(crate) fn new(
+        sid: TrackSid,
+        name: String,
+        rtc_track: RtcAudioTrack,
+        receiver: RtpReceiver,
+    ) -> Self {
         Self {
             inner: Arc::new(TrackInner::new(
                 sid,
@@ -30,6 +37,7 @@ impl RemoteAudioTrack {
                 TrackKind::Audio,
                 MediaStreamTrack::Audio(rtc_track),
             )),
+            receiver,
         }
     }
 
@@ -96,6 +104,28 @@ impl RemoteAudioTrack {
         true
     }
 
+    #[inline]
+    pub fn receiver(&self) -> RtpReceiver {
+        self.receiver.clone()
+    }
+
+    /// Minimum delay before playing the received media. A higher delay gives a smoother
+    /// playback, None restores the default (lowest latency)
+    pub fn set_playout_delay(&self, delay: Option<Duration>) {
+        self.receiver.set_jitter_buffer_minimum_delay(delay);
+    }
+
+    /// Level of the last received packet, between 0.0 and 1.0. It is read from the
+    /// ssrc-audio-level RTP header extension, 0.0 when unavailable.
+    pub fn audio_level(&self) -> f32 {
+        self.receiver
+            .get_synchronization_sources()
+            .into_iter()
+            .max_by_key(|source| source.timestamp_ms)
+            .and_then(|source| source.audio_level_linear())
+            .unwrap_or(0.0)
+    }
+
     #[allow(dead_code)]
     #[inline]
     pub(crate) fn transceiver(&self) -> Option<RtpTransceiver> {
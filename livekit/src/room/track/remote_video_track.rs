@@ -1,14 +1,17 @@
 use super::TrackInner;
 use crate::prelude::*;
+use crate::track::TrackError;
 use livekit_protocol as proto;
 use livekit_webrtc::prelude::*;
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 #[derive(Clone)]
 pub struct RemoteVideoTrack {
     pub(crate) inner: Arc<TrackInner>,
+    receiver: RtpReceiver,
 }
 
 impl Debug for RemoteVideoTrack {
@@ -22,7 +25,12 @@ impl Debug for RemoteVideoTrack {
 }
 
 impl RemoteVideoTrack {
-    pub(crate) fn new(sid: TrackSid, name: String, rtc_track: RtcVideoTrack) -> Self {
+    pub(crate) fn new(
+        sid: TrackSid,
+        name: String,
+        rtc_track: RtcVideoTrack,
+        receiver: RtpReceiver,
+    ) -> Self {
         Self {
             inner: Arc::new(TrackInner::new(
                 sid,
@@ -30,6 +38,7 @@ impl RemoteVideoTrack {
                 TrackKind::Video,
                 MediaStreamTrack::Video(rtc_track),
             )),
+            receiver,
         }
     }
 
@@ -96,6 +105,47 @@ impl RemoteVideoTrack {
         true
     }
 
+    #[inline]
+    pub fn receiver(&self) -> RtpReceiver {
+        self.receiver.clone()
+    }
+
+    /// Minimum delay before playing the received media. A higher delay gives a smoother
+    /// playback, None restores the default (lowest latency).
+    /// The playout-delay RTP header extension is negotiated on the subscriber, so the
+    /// delay requested by the sender is also applied
+    pub fn set_playout_delay(&self, delay: Option<Duration>) {
+        self.receiver.set_jitter_buffer_minimum_delay(delay);
+    }
+
+    /// Wait for the next frame of the track and encode it to an image.
+    /// Fails if no frame is received before the timeout (e.g the track is muted)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn snapshot(
+        &self,
+        format: livekit_webrtc::native::snapshot::ImageFormat,
+        timeout: Duration,
+    ) -> Result<Vec<u8>, TrackError> {
+        use futures_util::StreamExt;
+        use livekit_webrtc::native::snapshot;
+        use livekit_webrtc::video_stream::native::NativeVideoStream;
+
+        let mut stream = NativeVideoStream::new(self.rtc_track());
+        let frame = crate::rt::timeout(timeout, stream.next()).await;
+        stream.close();
+
+        let frame = frame
+            .map_err(|_| TrackError::Snapshot("no frame received before the timeout".to_owned()))?
+            .ok_or_else(|| {
+                TrackError::Snapshot("the track ended before receiving a frame".to_owned())
+            })?;
+
+        tokio::task::spawn_blocking(move || snapshot::encode_frame(&frame, format))
+            .await
+            .map_err(|err| TrackError::Snapshot(err.to_string()))?
+            .map_err(|err| TrackError::Snapshot(err.to_string()))
+    }
+
     #[allow(dead_code)]
     #[inline]
     pub(crate) fn transceiver(&self) -> Option<RtpTransceiver> {
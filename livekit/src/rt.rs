@@ -0,0 +1,119 @@
+//! Tasks and timers used by the room, backed by tokio on native platforms and by the
+//! browser event loop on wasm32 (where the tokio runtime isn't available)
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use tokio::task::{spawn, yield_now, JoinHandle};
+#[cfg(not(target_arch = "wasm32"))]
+pub use tokio::time::{interval, sleep, timeout, Interval};
+
+#[cfg(target_arch = "wasm32")]
+pub use web::*;
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use futures_util::future::{self, Either};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+    use thiserror::Error;
+    use tokio::sync::oneshot;
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen_futures::JsFuture;
+
+    #[wasm_bindgen]
+    extern "C" {
+        // Available on both the window and the workers
+        #[wasm_bindgen(js_name = setTimeout)]
+        fn set_timeout(handler: &js_sys::Function, timeout: i32) -> JsValue;
+    }
+
+    #[derive(Debug, Error)]
+    #[error("task dropped before completion")]
+    pub struct JoinError;
+
+    #[derive(Debug, Error)]
+    #[error("deadline has elapsed")]
+    pub struct Elapsed;
+
+    /// The tasks run on the current thread, they don't need to be Send
+    pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = tx.send(future.await);
+        });
+        JoinHandle { rx }
+    }
+
+    #[derive(Debug)]
+    pub struct JoinHandle<T> {
+        rx: oneshot::Receiver<T>,
+    }
+
+    impl<T> Future for JoinHandle<T> {
+        type Output = Result<T, JoinError>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            Pin::new(&mut self.rx).poll(cx).map_err(|_| JoinError)
+        }
+    }
+
+    pub async fn sleep(duration: Duration) {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            set_timeout(&resolve, duration.as_millis().min(i32::MAX as u128) as i32);
+        });
+        let _ = JsFuture::from(promise).await;
+    }
+
+    pub async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output, Elapsed> {
+        let future = Box::pin(future);
+        let sleep = Box::pin(sleep(duration));
+        match future::select(future, sleep).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(Elapsed),
+        }
+    }
+
+    /// Yield to the browser event loop with setTimeout(0). Waking the task again would only
+    /// queue a microtask, so the loops polling a state (e.g the PeerConnection state set by
+    /// its callbacks) would never let the events run
+    pub async fn yield_now() {
+        sleep(Duration::ZERO).await
+    }
+
+    /// Like tokio's Interval, the first tick completes immediately
+    pub fn interval(period: Duration) -> Interval {
+        Interval {
+            period,
+            deadline: now(),
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Interval {
+        period: Duration,
+        deadline: f64, // ms, see now()
+    }
+
+    impl Interval {
+        pub async fn tick(&mut self) {
+            let remaining = self.deadline - now();
+            if remaining > 0.0 {
+                sleep(Duration::from_secs_f64(remaining / 1000.0)).await;
+            }
+            self.deadline = now().max(self.deadline) + self.period.as_secs_f64() * 1000.0;
+        }
+
+        pub fn reset(&mut self) {
+            self.deadline = now() + self.period.as_secs_f64() * 1000.0;
+        }
+    }
+
+    fn now() -> f64 {
+        js_sys::Date::now()
+    }
+}
@@ -20,6 +20,7 @@ impl Debug for LkRuntime {
 }
 
 impl LkRuntime {
+    /// Global runtime shared by the rooms that don't specify one
     pub fn instance() -> Arc<LkRuntime> {
         let mut lk_runtime_ref = LK_RUNTIME.lock();
         if let Some(lk_runtime) = lk_runtime_ref.upgrade() {
@@ -34,6 +35,14 @@ impl LkRuntime {
         }
     }
 
+    /// Runtime with its own PeerConnectionFactory, isolated from the global instance
+    pub fn new(options: PeerConnectionFactoryOptions) -> Arc<LkRuntime> {
+        trace!("LkRuntime::new()");
+        Arc::new(Self {
+            pc_factory: PeerConnectionFactory::with_options(options),
+        })
+    }
+
     pub fn pc_factory(&self) -> &PeerConnectionFactory {
         &self.pc_factory
     }
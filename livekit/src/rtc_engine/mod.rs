@@ -1,5 +1,6 @@
-use crate::options::TrackPublishOptions;
+use crate::options::{TrackPublishOptions, VideoCodec};
 use crate::prelude::LocalTrack;
+use crate::rt::{self, interval, Interval, JoinHandle};
 use crate::rtc_engine::lk_runtime::LkRuntime;
 use crate::rtc_engine::rtc_session::{RtcSession, SessionEvent, SessionEvents, SessionInfo};
 use crate::signal_client::{SignalError, SignalOptions};
@@ -14,8 +15,6 @@ use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::RwLock as AsyncRwLock;
 use tokio::sync::{mpsc, oneshot};
-use tokio::task::JoinHandle;
-use tokio::time::{interval, Interval};
 use tracing::{error, info, trace, warn};
 
 pub mod lk_runtime;
@@ -27,6 +26,40 @@ pub(crate) type EngineEmitter = mpsc::Sender<EngineEvent>;
 pub(crate) type EngineEvents = mpsc::Receiver<EngineEvent>;
 pub(crate) type EngineResult<T> = Result<T, EngineError>;
 
+/// ICE events of the publisher and subscriber PeerConnections, used to diagnose
+/// connectivity issues
+#[derive(Debug, Clone)]
+pub enum IceDiagnostics {
+    CandidateGathered {
+        candidate: Candidate,
+        target: proto::SignalTarget,
+    },
+    CandidateError {
+        error: IceCandidateError,
+        target: proto::SignalTarget,
+    },
+    ConnectionStateChanged {
+        state: IceConnectionState,
+        target: proto::SignalTarget,
+    },
+    GatheringStateChanged {
+        state: IceGatheringState,
+        target: proto::SignalTarget,
+    },
+    SelectedCandidatePairChanged {
+        pair: CandidatePair,
+        reason: String,
+        target: proto::SignalTarget,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EngineOptions {
+    pub signal_options: SignalOptions,
+    /// The ICE servers received from the server are used when ice_servers is empty
+    pub rtc_config: RtcConfiguration,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[repr(u8)]
 pub enum SimulateScenario {
@@ -68,6 +101,7 @@ pub enum EngineEvent {
         track: MediaStreamTrack,
         stream: MediaStream,
         receiver: RtpReceiver,
+        transceiver: RtpTransceiver,
     },
     Data {
         participant_sid: String,
@@ -80,6 +114,10 @@ pub enum EngineEvent {
     ConnectionQuality {
         updates: Vec<proto::ConnectionQualityInfo>,
     },
+    SubscribedQualityUpdate {
+        update: proto::SubscribedQualityUpdate,
+    },
+    IceDiagnostics(IceDiagnostics),
     Resuming,
     Resumed,
     Restarting,
@@ -130,10 +168,10 @@ pub struct RtcEngine {
 }
 
 impl RtcEngine {
-    pub fn new() -> (Self, EngineEvents) {
+    pub fn new(lk_runtime: Arc<LkRuntime>) -> (Self, EngineEvents) {
         let (engine_emitter, engine_events) = mpsc::channel(8);
         let inner = Arc::new(EngineInner {
-            lk_runtime: LkRuntime::instance(),
+            lk_runtime,
             session_info: Default::default(),
             running_handle: Default::default(),
             opened: Default::default(),
@@ -146,12 +184,16 @@ impl RtcEngine {
         (Self { inner }, engine_events)
     }
 
+    pub fn lk_runtime(&self) -> Arc<LkRuntime> {
+        self.inner.lk_runtime.clone()
+    }
+
     #[tracing::instrument]
     pub async fn connect(
         &self,
         url: &str,
         token: &str,
-        options: SignalOptions,
+        options: EngineOptions,
     ) -> EngineResult<()> {
         self.inner.connect(url, token, options).await
     }
@@ -237,6 +279,24 @@ impl RtcEngine {
             .await
     }
 
+    pub async fn create_simulcast_sender(
+        &self,
+        simulcast_track: RtcVideoTrack,
+        video_codec: VideoCodec,
+        encodings: Vec<RtpEncodingParameters>,
+    ) -> EngineResult<RtpTransceiver> {
+        self.inner.wait_reconnection().await?;
+        self.inner
+            .running_handle
+            .read()
+            .await
+            .as_ref()
+            .unwrap()
+            .session
+            .create_simulcast_sender(simulcast_track, video_codec, encodings)
+            .await
+    }
+
     pub async fn negotiate_publisher(&self) -> EngineResult<()> {
         // TODO(theomonnom): guard for reconnection
         self.inner.wait_reconnection().await?;
@@ -298,7 +358,7 @@ impl EngineInner {
                 } else {
                     // Spawning a new task because the close function wait for the engine_task to
                     // finish.
-                    tokio::spawn({
+                    rt::spawn({
                         let inner = self.clone();
                         async move {
                             inner.close().await;
@@ -324,6 +384,7 @@ impl EngineInner {
                 track,
                 stream,
                 receiver,
+                transceiver,
             } => {
                 let _ = self
                     .engine_emitter
@@ -331,6 +392,7 @@ impl EngineInner {
                         track,
                         stream,
                         receiver,
+                        transceiver,
                     })
                     .await;
             }
@@ -352,6 +414,18 @@ impl EngineInner {
                     .send(EngineEvent::ConnectionQuality { updates })
                     .await;
             }
+            SessionEvent::SubscribedQualityUpdate { update } => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::SubscribedQualityUpdate { update })
+                    .await;
+            }
+            SessionEvent::IceDiagnostics(diagnostics) => {
+                let _ = self
+                    .engine_emitter
+                    .send(EngineEvent::IceDiagnostics(diagnostics))
+                    .await;
+            }
             SessionEvent::Connected => {}
         }
         Ok(())
@@ -361,7 +435,7 @@ impl EngineInner {
         self: &Arc<Self>,
         url: &str,
         token: &str,
-        options: SignalOptions,
+        options: EngineOptions,
     ) -> EngineResult<()> {
         let (session_emitter, session_events) = mpsc::unbounded_channel();
         let session = RtcSession::connect(
@@ -374,7 +448,7 @@ impl EngineInner {
         .await?;
 
         let (close_sender, close_receiver) = oneshot::channel();
-        let engine_task = tokio::spawn(self.clone().engine_task(session_events, close_receiver));
+        let engine_task = rt::spawn(self.clone().engine_task(session_events, close_receiver));
         *self.session_info.lock() = Some(session.info().clone());
         *self.running_handle.write().await = Some(EngineHandle {
             session,
@@ -408,7 +482,7 @@ impl EngineInner {
         }
 
         while self.reconnecting.load(Ordering::Acquire) {
-            tokio::task::yield_now().await; // TODO(theomonnom): Remove yield
+            rt::yield_now().await; // TODO(theomonnom): Remove yield
         }
 
         if self.running_handle.read().await.is_none() {
@@ -437,7 +511,7 @@ impl EngineInner {
         self.reconnecting.store(true, Ordering::SeqCst);
         self.full_reconnect.store(full_reconnect, Ordering::SeqCst);
         self.reconnect_interval.lock().reset();
-        tokio::spawn({
+        rt::spawn({
             let inner = self.clone();
             async move {
                 let res = inner.reconnect_task().await;
@@ -5,6 +5,7 @@ use std::time::Duration;
 use tracing::{event, Level};
 
 const _NEGOTIATION_FREQUENCY: Duration = Duration::from_millis(150);
+const PLAYOUT_DELAY_URI: &str = "http://www.webrtc.org/experiments/rtp-hdrext/playout-delay";
 
 pub type OnOfferCreated = Box<dyn FnMut(SessionDescription) + Send + Sync>;
 
@@ -113,6 +114,7 @@ impl PeerTransport {
         options: AnswerOptions,
     ) -> Result<SessionDescription, RtcError> {
         self.set_remote_description(offer).await?;
+        self.enable_playout_delay();
         let answer = self.peer_connection().create_answer(options).await?;
         self.peer_connection()
             .set_local_description(answer.clone())
@@ -121,6 +123,27 @@ impl PeerTransport {
         Ok(answer)
     }
 
+    /// Accept the playout-delay RTP header extension in the answer, so the delay requested by
+    /// the sender is applied without having to renegotiate (only video transceivers have it)
+    fn enable_playout_delay(&self) {
+        for transceiver in self.peer_connection.transceivers() {
+            let mut extensions = transceiver.header_extensions_to_offer();
+            match extensions
+                .iter_mut()
+                .find(|extension| extension.uri == PLAYOUT_DELAY_URI)
+            {
+                Some(extension) if extension.direction == RtpTransceiverDirection::Stopped => {
+                    extension.direction = RtpTransceiverDirection::SendRecv;
+                }
+                _ => continue, // Unsupported or already enabled
+            }
+
+            if let Err(err) = transceiver.set_header_extensions_to_offer(extensions) {
+                event!(Level::WARN, "failed to enable the playout delay: {:?}", err);
+            }
+        }
+    }
+
     #[tracing::instrument(level = Level::DEBUG)]
     pub async fn create_and_send_offer(&mut self, options: OfferOptions) -> Result<(), RtcError> {
         if self.on_offer_handler.is_none() {
@@ -1,9 +1,9 @@
 use super::peer_transport::PeerTransport;
+use super::IceDiagnostics;
 use crate::rtc_engine::peer_transport::OnOfferCreated;
 use livekit_protocol as proto;
 use livekit_webrtc::{self as rtc, prelude::*};
 use tokio::sync::mpsc;
-use tracing::{error};
 
 pub type RtcEmitter = mpsc::UnboundedSender<RtcEvent>;
 pub type RtcEvents = mpsc::UnboundedReceiver<RtcEvent>;
@@ -14,6 +14,22 @@ pub enum RtcEvent {
         ice_candidate: IceCandidate,
         target: proto::SignalTarget,
     },
+    IceCandidateError {
+        error: IceCandidateError,
+        target: proto::SignalTarget,
+    },
+    IceConnectionChange {
+        state: IceConnectionState,
+        target: proto::SignalTarget,
+    },
+    IceGatheringChange {
+        state: IceGatheringState,
+        target: proto::SignalTarget,
+    },
+    IceSelectedCandidatePairChange {
+        event: CandidatePairChangeEvent,
+        target: proto::SignalTarget,
+    },
     ConnectionChange {
         state: PeerConnectionState,
         target: proto::SignalTarget,
@@ -40,6 +56,54 @@ pub enum RtcEvent {
     },
 }
 
+impl RtcEvent {
+    /// The ICE events are also reported to the user as diagnostics
+    pub fn ice_diagnostics(&self) -> Option<IceDiagnostics> {
+        match self {
+            Self::IceCandidate {
+                ice_candidate,
+                target,
+            } => Some(IceDiagnostics::CandidateGathered {
+                candidate: ice_candidate.candidate(),
+                target: *target,
+            }),
+            Self::IceCandidateError { error, target } => Some(IceDiagnostics::CandidateError {
+                error: error.clone(),
+                target: *target,
+            }),
+            Self::IceConnectionChange { state, target } => {
+                Some(IceDiagnostics::ConnectionStateChanged {
+                    state: *state,
+                    target: *target,
+                })
+            }
+            Self::IceGatheringChange { state, target } => {
+                Some(IceDiagnostics::GatheringStateChanged {
+                    state: *state,
+                    target: *target,
+                })
+            }
+            Self::IceSelectedCandidatePairChange { event, target } => {
+                Some(IceDiagnostics::SelectedCandidatePairChanged {
+                    pair: event.selected_candidate_pair.clone(),
+                    reason: event.reason.clone(),
+                    target: *target,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<DataMessage> for RtcEvent {
+    fn from(message: DataMessage) -> Self {
+        Self::Data {
+            data: message.data,
+            binary: message.binary,
+        }
+    }
+}
+
 /// Handlers used to forward events to a channel
 /// Every callback here is called on the signaling thread
 
@@ -74,8 +138,8 @@ fn on_data_channel(
     target: proto::SignalTarget,
     emitter: RtcEmitter,
 ) -> rtc::peer_connection::OnDataChannel {
-    Box::new(move |data_channel| {
-        data_channel.on_message(Some(on_message(emitter.clone())));
+    Box::new(move |mut data_channel| {
+        forward_dc_events(&mut data_channel, emitter.clone());
 
         let _ = emitter.send(RtcEvent::DataChannel {
             data_channel,
@@ -97,11 +161,38 @@ fn on_track(target: proto::SignalTarget, emitter: RtcEmitter) -> rtc::peer_conne
 }
 
 fn on_ice_candidate_error(
-    _target: proto::SignalTarget,
-    _emitter: RtcEmitter,
+    target: proto::SignalTarget,
+    emitter: RtcEmitter,
 ) -> rtc::peer_connection::OnIceCandidateError {
-    Box::new(move |ice_error| {
-        error!("{:?}", ice_error);
+    Box::new(move |error| {
+        let _ = emitter.send(RtcEvent::IceCandidateError { error, target });
+    })
+}
+
+fn on_ice_connection_state_change(
+    target: proto::SignalTarget,
+    emitter: RtcEmitter,
+) -> rtc::peer_connection::OnIceConnectionChange {
+    Box::new(move |state| {
+        let _ = emitter.send(RtcEvent::IceConnectionChange { state, target });
+    })
+}
+
+fn on_ice_gathering_state_change(
+    target: proto::SignalTarget,
+    emitter: RtcEmitter,
+) -> rtc::peer_connection::OnIceGatheringChange {
+    Box::new(move |state| {
+        let _ = emitter.send(RtcEvent::IceGatheringChange { state, target });
+    })
+}
+
+fn on_ice_selected_candidate_pair_change(
+    target: proto::SignalTarget,
+    emitter: RtcEmitter,
+) -> rtc::peer_connection::OnIceSelectedCandidatePairChange {
+    Box::new(move |event| {
+        let _ = emitter.send(RtcEvent::IceSelectedCandidatePairChange { event, target });
     })
 }
 
@@ -133,18 +224,123 @@ pub fn forward_pc_events(transport: &mut PeerTransport, rtc_emitter: RtcEmitter)
             rtc_emitter.clone(),
         )));
 
+    transport
+        .peer_connection()
+        .on_ice_connection_state_change(Some(on_ice_connection_state_change(
+            signal_target,
+            rtc_emitter.clone(),
+        )));
+
+    transport
+        .peer_connection()
+        .on_ice_gathering_state_change(Some(on_ice_gathering_state_change(
+            signal_target,
+            rtc_emitter.clone(),
+        )));
+
+    transport
+        .peer_connection()
+        .on_ice_selected_candidate_pair_change(Some(on_ice_selected_candidate_pair_change(
+            signal_target,
+            rtc_emitter.clone(),
+        )));
+
     transport.on_offer(Some(on_offer(signal_target, rtc_emitter.clone())));
 }
 
-fn on_message(emitter: RtcEmitter) -> rtc::data_channel::OnMessage {
-    Box::new(move |buffer| {
-        let _ = emitter.send(RtcEvent::Data {
-            data: buffer.data.to_vec(),
-            binary: buffer.binary,
-        });
-    })
+/// Forward the messages received on a DataChannel to any channel whose events can be
+/// built from a DataMessage
+pub fn forward_dc_events<E>(dc: &mut DataChannel, emitter: mpsc::UnboundedSender<E>)
+where
+    E: From<DataMessage> + Send + 'static,
+{
+    dc.on_message(Some(Box::new(move |buffer| {
+        let _ = emitter.send(DataMessage::from(buffer).into());
+    })));
 }
 
-pub fn forward_dc_events(dc: &mut DataChannel, rtc_emitter: RtcEmitter) {
-    dc.on_message(Some(on_message(rtc_emitter.clone())));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ice_events_are_diagnostics() {
+        let (emitter, mut events) = mpsc::unbounded_channel();
+        let target = proto::SignalTarget::Subscriber;
+
+        on_ice_gathering_state_change(target, emitter.clone())(IceGatheringState::Complete);
+        on_ice_connection_state_change(target, emitter.clone())(IceConnectionState::Checking);
+        on_ice_candidate_error(target, emitter.clone())(IceCandidateError {
+            address: "0.0.0.0".to_owned(),
+            port: 0,
+            url: "stun:stun.example.com:3478".to_owned(),
+            error_code: 701,
+            error_text: "STUN binding request timed out".to_owned(),
+        });
+
+        assert!(matches!(
+            events.try_recv().unwrap().ice_diagnostics(),
+            Some(IceDiagnostics::GatheringStateChanged {
+                state: IceGatheringState::Complete,
+                target: proto::SignalTarget::Subscriber,
+            })
+        ));
+        assert!(matches!(
+            events.try_recv().unwrap().ice_diagnostics(),
+            Some(IceDiagnostics::ConnectionStateChanged {
+                state: IceConnectionState::Checking,
+                target: proto::SignalTarget::Subscriber,
+            })
+        ));
+        assert!(matches!(
+            events.try_recv().unwrap().ice_diagnostics(),
+            Some(IceDiagnostics::CandidateError { error, .. }) if error.error_code == 701
+        ));
+
+        let data = RtcEvent::Data {
+            data: Vec::new(),
+            binary: true,
+        };
+        assert!(data.ice_diagnostics().is_none());
+    }
+
+    #[test]
+    fn selected_candidate_pair_is_diagnostics() {
+        let (emitter, mut events) = mpsc::unbounded_channel();
+        let candidate = |address: &str, port| {
+            IceCandidate::parse(
+                "0",
+                0,
+                &format!("candidate:1 1 udp 2122260223 {} {} typ host", address, port),
+            )
+            .unwrap()
+            .candidate()
+        };
+
+        on_ice_selected_candidate_pair_change(proto::SignalTarget::Publisher, emitter)(
+            CandidatePairChangeEvent {
+                selected_candidate_pair: CandidatePair {
+                    local: candidate("192.168.1.2", 50000),
+                    remote: candidate("203.0.113.5", 60000),
+                },
+                last_data_received_ms: 0,
+                reason: "Selected by controlling agent".to_owned(),
+                estimated_disconnected_time_ms: 0,
+            },
+        );
+
+        match events.try_recv().unwrap().ice_diagnostics() {
+            Some(IceDiagnostics::SelectedCandidatePairChanged {
+                pair,
+                reason,
+                target,
+            }) => {
+                assert_eq!(pair.local.address, "192.168.1.2");
+                assert_eq!(pair.remote.port, 60000);
+                assert_eq!(reason, "Selected by controlling agent");
+                assert_eq!(target, proto::SignalTarget::Publisher);
+            }
+            other => panic!("unexpected diagnostics: {:?}", other),
+        }
+    }
 }
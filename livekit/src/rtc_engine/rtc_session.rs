@@ -1,11 +1,14 @@
-use super::{rtc_events, EngineError, EngineResult, SimulateScenario};
-use crate::options::TrackPublishOptions;
+use super::{
+    rtc_events, EngineError, EngineOptions, EngineResult, IceDiagnostics, SimulateScenario,
+};
+use crate::options::{TrackPublishOptions, VideoCodec};
 use crate::prelude::TrackKind;
+use crate::rt::{self, sleep, JoinHandle};
 use crate::rtc_engine::lk_runtime::LkRuntime;
 use crate::rtc_engine::peer_transport::PeerTransport;
 use crate::rtc_engine::rtc_events::{RtcEvent, RtcEvents};
 use crate::signal_client;
-use crate::signal_client::{SignalClient, SignalEvent, SignalEvents, SignalOptions};
+use crate::signal_client::{SignalClient, SignalEvent, SignalEvents};
 use crate::track::LocalTrack;
 use livekit_protocol as proto;
 use livekit_webrtc::prelude::*;
@@ -20,8 +23,6 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex as AsyncMutex;
 use tokio::sync::{mpsc, oneshot, watch};
-use tokio::task::JoinHandle;
-use tokio::time::sleep;
 use tracing::{debug, error, trace, warn};
 
 pub const ICE_CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
@@ -46,6 +47,7 @@ pub enum SessionEvent {
         track: MediaStreamTrack,
         stream: MediaStream,
         receiver: RtpReceiver,
+        transceiver: RtpTransceiver,
     },
     SpeakersChanged {
         speakers: Vec<proto::SpeakerInfo>,
@@ -53,6 +55,10 @@ pub enum SessionEvent {
     ConnectionQuality {
         updates: Vec<proto::ConnectionQualityInfo>,
     },
+    SubscribedQualityUpdate {
+        update: proto::SubscribedQualityUpdate,
+    },
+    IceDiagnostics(IceDiagnostics),
     // TODO(theomonnom): Move entirely the reconnection logic on mod.rs
     Close {
         source: String,
@@ -100,12 +106,13 @@ struct IceCandidateJson {
 pub struct SessionInfo {
     pub url: String,
     pub token: String,
-    pub options: SignalOptions,
+    pub options: EngineOptions,
     pub join_response: proto::JoinResponse,
 }
 
 /// Fields shared with rtc_task and signal_task
 struct SessionInner {
+    lk_runtime: Arc<LkRuntime>,
     info: SessionInfo,
     signal_client: Arc<SignalClient>,
     pc_state: AtomicU8, // PCState
@@ -146,8 +153,6 @@ impl Debug for SessionInner {
 /// RTCSession is also responsable for the signaling and the negotation
 #[derive(Debug)]
 pub struct RtcSession {
-    #[allow(dead_code)]
-    lk_runtime: Arc<LkRuntime>,
     inner: Arc<SessionInner>,
     close_tx: watch::Sender<bool>, // false = is_running
     signal_task: JoinHandle<()>,
@@ -158,33 +163,31 @@ impl RtcSession {
     pub async fn connect(
         url: &str,
         token: &str,
-        options: SignalOptions,
+        options: EngineOptions,
         lk_runtime: Arc<LkRuntime>,
         session_emitter: SessionEmitter,
     ) -> EngineResult<Self> {
         // Connect to the SignalClient
         let (signal_client, mut signal_events) = SignalClient::new();
         let signal_client = Arc::new(signal_client);
-        signal_client.connect(url, token, options.clone()).await?;
+        signal_client
+            .connect(url, token, options.signal_options.clone())
+            .await?;
         let join_response = signal_client::utils::next_join_response(&mut signal_events).await?;
         debug!("received JoinResponse: {:?}", join_response);
 
         let (rtc_emitter, rtc_events) = mpsc::unbounded_channel();
-        let rtc_config = RtcConfiguration {
-            ice_servers: {
-                let mut servers = vec![];
-                for ice_server in join_response.ice_servers.clone() {
-                    servers.push(IceServer {
-                        urls: ice_server.urls,
-                        username: ice_server.username,
-                        password: ice_server.credential,
-                    })
-                }
-                servers
-            },
-            continual_gathering_policy: ContinualGatheringPolicy::GatherContinually,
-            ice_transport_type: IceTransportsType::All,
-        };
+        let mut rtc_config = options.rtc_config.clone();
+        rtc_config.continual_gathering_policy = ContinualGatheringPolicy::GatherContinually;
+        if rtc_config.ice_servers.is_empty() {
+            for ice_server in join_response.ice_servers.clone() {
+                rtc_config.ice_servers.push(IceServer {
+                    urls: ice_server.urls,
+                    username: ice_server.username,
+                    password: ice_server.credential,
+                })
+            }
+        }
 
         let mut publisher_pc = PeerTransport::new(
             lk_runtime
@@ -232,6 +235,7 @@ impl RtcSession {
 
         let (close_tx, close_rx) = watch::channel(false);
         let inner = Arc::new(SessionInner {
+            lk_runtime,
             info: session_info,
             pc_state: AtomicU8::new(PeerState::New as u8),
             has_published: Default::default(),
@@ -247,15 +251,14 @@ impl RtcSession {
         });
 
         // Start session tasks
-        let signal_task = tokio::spawn(inner.clone().signal_task(signal_events, close_rx.clone()));
-        let rtc_task = tokio::spawn(inner.clone().rtc_session_task(rtc_events, close_rx.clone()));
+        let signal_task = rt::spawn(inner.clone().signal_task(signal_events, close_rx.clone()));
+        let rtc_task = rt::spawn(inner.clone().rtc_session_task(rtc_events, close_rx.clone()));
 
         if !inner.info.join_response.subscriber_primary {
             inner.negotiate_publisher().await?;
         }
 
         let session = Self {
-            lk_runtime,
             inner: inner.clone(),
             close_tx,
             signal_task,
@@ -285,6 +288,18 @@ impl RtcSession {
         self.inner.create_sender(track, options, encodings).await
     }
 
+    #[inline]
+    pub async fn create_simulcast_sender(
+        &self,
+        simulcast_track: RtcVideoTrack,
+        video_codec: VideoCodec,
+        encodings: Vec<RtpEncodingParameters>,
+    ) -> EngineResult<RtpTransceiver> {
+        self.inner
+            .create_simulcast_sender(simulcast_track, video_codec, encodings)
+            .await
+    }
+
     #[inline]
     pub async fn negotiate_publisher(&self) -> EngineResult<()> {
         self.inner.negotiate_publisher().await
@@ -500,6 +515,11 @@ impl SessionInner {
                     updates: quality.updates,
                 });
             }
+            proto::signal_response::Message::SubscribedQualityUpdate(update) => {
+                let _ = self
+                    .emitter
+                    .send(SessionEvent::SubscribedQualityUpdate { update });
+            }
             proto::signal_response::Message::TrackPublished(publish_res) => {
                 let mut pending_tracks = self.pending_tracks.lock();
                 if let Some(tx) = pending_tracks.remove(&publish_res.cid) {
@@ -514,6 +534,10 @@ impl SessionInner {
     }
 
     async fn on_rtc_event(&self, event: RtcEvent) -> EngineResult<()> {
+        if let Some(diagnostics) = event.ice_diagnostics() {
+            let _ = self.emitter.send(SessionEvent::IceDiagnostics(diagnostics));
+        }
+
         match event {
             RtcEvent::IceCandidate {
                 ice_candidate,
@@ -525,7 +549,7 @@ impl SessionInner {
                             candidate_init: serde_json::to_string(&IceCandidateJson {
                                 sdp_mid: ice_candidate.sdp_mid(),
                                 sdp_m_line_index: ice_candidate.sdp_mline_index(),
-                                candidate: ice_candidate.candidate(),
+                                candidate: ice_candidate.to_string(),
                             })?,
                             target: target as i32,
                         },
@@ -557,6 +581,13 @@ impl SessionInner {
                     );
                 }
             }
+            RtcEvent::IceCandidateError { error, target } => {
+                warn!("ice candidate error: {:?} {:?}", error, target);
+            }
+            RtcEvent::IceConnectionChange { .. } | RtcEvent::IceGatheringChange { .. } => {}
+            RtcEvent::IceSelectedCandidatePairChange { event, target } => {
+                debug!("selected candidate pair changed: {:?} {:?}", event, target);
+            }
             RtcEvent::DataChannel {
                 data_channel,
                 target: _,
@@ -579,7 +610,7 @@ impl SessionInner {
                 receiver,
                 mut streams,
                 track,
-                transceiver: _,
+                transceiver,
                 target: _,
             } => {
                 if !streams.is_empty() {
@@ -587,6 +618,7 @@ impl SessionInner {
                         stream: streams.remove(0),
                         track,
                         receiver,
+                        transceiver,
                     });
                 } else {
                     warn!("Track event with no streams");
@@ -667,6 +699,36 @@ impl SessionInner {
         track: LocalTrack,
         options: TrackPublishOptions,
         encodings: Vec<RtpEncodingParameters>,
+    ) -> EngineResult<RtpTransceiver> {
+        let transceiver = self
+            .add_send_transceiver(track.rtc_track(), encodings)
+            .await?;
+        if track.kind() == TrackKind::Video {
+            self.set_codec_preferences(&transceiver, options.video_codec)?;
+        }
+
+        Ok(transceiver)
+    }
+
+    /// Sender of an additional codec of a published track (e.g the backup codec), the
+    /// simulcast track shares the source of the published one
+    async fn create_simulcast_sender(
+        &self,
+        simulcast_track: RtcVideoTrack,
+        video_codec: VideoCodec,
+        encodings: Vec<RtpEncodingParameters>,
+    ) -> EngineResult<RtpTransceiver> {
+        let transceiver = self
+            .add_send_transceiver(simulcast_track.into(), encodings)
+            .await?;
+        self.set_codec_preferences(&transceiver, video_codec)?;
+        Ok(transceiver)
+    }
+
+    async fn add_send_transceiver(
+        &self,
+        track: MediaStreamTrack,
+        encodings: Vec<RtpEncodingParameters>,
     ) -> EngineResult<RtpTransceiver> {
         let init = RtpTransceiverInit {
             direction: RtpTransceiverDirection::SendOnly,
@@ -679,43 +741,35 @@ impl SessionInner {
             .lock()
             .await
             .peer_connection()
-            .add_transceiver(track.rtc_track(), init)?;
-
-        if track.kind() == TrackKind::Video {
-            let capabilities = LkRuntime::instance()
-                .pc_factory()
-                .get_rtp_sender_capabilities(track.kind().into());
-
-            let mut matched = Vec::new();
-            let mut partial_matched = Vec::new();
-            let mut unmatched = Vec::new();
-
-            for codec in capabilities.codecs {
-                let mime_type = codec.mime_type.to_lowercase();
-                if mime_type == format!("video/{}", options.video_codec.as_str()) {
-                    if let Some(sdp_fmtp_line) = codec.sdp_fmtp_line.as_ref() {
-                        // for h264 codecs that have sdpFmtpLine available, use only if the
-                        // profile-level-id is 42e01f for cross-browser compatibility
-                        if sdp_fmtp_line.contains("profile-level-id=42e01f") {
-                            matched.push(codec);
-                            continue;
-                        }
-                    }
-                    partial_matched.push(codec);
-                } else {
-                    unmatched.push(codec);
-                }
-            }
-
-            matched.append(&mut partial_matched);
-            matched.append(&mut unmatched);
-
-            transceiver.set_codec_preferences(matched)?;
-        }
+            .add_transceiver(track, init)?;
 
         Ok(transceiver)
     }
 
+    /// Put the requested codec first so it is the one negotiated. libwebrtc only offers the
+    /// codecs of its encoder factory (e.g no H264 without rtc_use_h264), failing here avoids
+    /// silently publishing with VP8
+    fn set_codec_preferences(
+        &self,
+        transceiver: &RtpTransceiver,
+        video_codec: VideoCodec,
+    ) -> EngineResult<()> {
+        let capabilities = self
+            .lk_runtime
+            .pc_factory()
+            .get_rtp_sender_capabilities(TrackKind::Video.into());
+
+        let codecs = sort_codecs(capabilities.codecs, video_codec).ok_or_else(|| {
+            EngineError::Internal(format!(
+                "video codec {} isn't supported",
+                video_codec.as_str()
+            ))
+        })?;
+
+        transceiver.set_codec_preferences(codecs)?;
+        Ok(())
+    }
+
     /// Called when the SignalClient or one of the PeerConnection has lost the connection
     /// The RTCEngine may try a reconnect.
     fn on_session_disconnected(
@@ -831,7 +885,7 @@ impl SessionInner {
     async fn restart_session(&self) -> EngineResult<()> {
         self.signal_client.close().await;
 
-        let mut options = self.info.options.clone();
+        let mut options = self.info.options.signal_options.clone();
         options.sid = self.info.join_response.participant.clone().unwrap().sid;
         options.reconnect = true;
 
@@ -867,7 +921,7 @@ impl SessionInner {
                     return Err(EngineError::Connection("closed".to_string()));
                 }
 
-                tokio::task::yield_now().await;
+                rt::yield_now().await;
             }
 
             Ok(())
@@ -923,7 +977,7 @@ impl SessionInner {
                     return Err(EngineError::Connection("closed".to_string()));
                 }
 
-                tokio::task::yield_now().await;
+                rt::yield_now().await;
             }
 
             Ok(())
@@ -947,3 +1001,140 @@ impl SessionInner {
         }
     }
 }
+
+/// Order the codec capabilities so the video codec comes first, None if it isn't supported
+fn sort_codecs(
+    codecs: Vec<RtpCodecCapability>,
+    video_codec: VideoCodec,
+) -> Option<Vec<RtpCodecCapability>> {
+    let mut matched = Vec::new();
+    let mut partial_matched = Vec::new();
+    let mut unmatched = Vec::new();
+
+    for codec in codecs {
+        let mime_type = codec.mime_type.to_lowercase();
+        if mime_type == format!("video/{}", video_codec.as_str()) {
+            if let Some(sdp_fmtp_line) = codec.sdp_fmtp_line.as_ref() {
+                // for h264 codecs that have sdpFmtpLine available, use only if the
+                // profile-level-id is 42e01f for cross-browser compatibility
+                if sdp_fmtp_line.contains("profile-level-id=42e01f") {
+                    matched.push(codec);
+                    continue;
+                }
+            }
+            partial_matched.push(codec);
+        } else {
+            unmatched.push(codec);
+        }
+    }
+
+    if matched.is_empty() && partial_matched.is_empty() {
+        return None;
+    }
+
+    matched.append(&mut partial_matched);
+    matched.append(&mut unmatched);
+    Some(matched)
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use livekit_webrtc::video_source::native::NativeVideoSource;
+
+    fn codec(mime_type: &str, sdp_fmtp_line: Option<&str>) -> RtpCodecCapability {
+        RtpCodecCapability {
+            channels: None,
+            clock_rate: Some(90000),
+            mime_type: mime_type.to_owned(),
+            sdp_fmtp_line: sdp_fmtp_line.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn sort_video_codecs() {
+        let codecs = vec![
+            codec("video/VP8", None),
+            codec("video/rtx", None),
+            codec(
+                "video/H264",
+                Some("packetization-mode=1;profile-level-id=640c1f"),
+            ),
+            codec(
+                "video/H264",
+                Some("packetization-mode=1;profile-level-id=42e01f"),
+            ),
+            codec("video/VP9", Some("profile-id=0")),
+        ];
+
+        let mime_types = |codecs: Vec<RtpCodecCapability>| -> Vec<String> {
+            codecs.into_iter().map(|c| c.mime_type).collect()
+        };
+
+        let h264 = sort_codecs(codecs.clone(), VideoCodec::H264).unwrap();
+        assert_eq!(
+            h264[0].sdp_fmtp_line.as_deref(),
+            Some("packetization-mode=1;profile-level-id=42e01f")
+        );
+        assert_eq!(
+            mime_types(h264),
+            [
+                "video/H264",
+                "video/H264",
+                "video/VP8",
+                "video/rtx",
+                "video/VP9"
+            ]
+        );
+
+        let vp9 = sort_codecs(codecs.clone(), VideoCodec::VP9).unwrap();
+        assert_eq!(
+            mime_types(vp9),
+            [
+                "video/VP9",
+                "video/VP8",
+                "video/rtx",
+                "video/H264",
+                "video/H264"
+            ]
+        );
+
+        // Not in the capabilities, VP8 would be negotiated instead
+        assert!(sort_codecs(codecs, VideoCodec::AV1).is_none());
+    }
+
+    #[tokio::test]
+    async fn preferred_codec_is_offered_first() {
+        let runtime = LkRuntime::instance();
+        let factory = runtime.pc_factory();
+        let capabilities = factory.get_rtp_sender_capabilities(MediaType::Video);
+
+        for video_codec in [VideoCodec::VP8, VideoCodec::VP9] {
+            let pc = factory
+                .create_peer_connection(RtcConfiguration::default())
+                .unwrap();
+            let track = factory.create_video_track("video", NativeVideoSource::default());
+            let transceiver = pc
+                .add_transceiver(
+                    track.into(),
+                    RtpTransceiverInit {
+                        direction: RtpTransceiverDirection::SendOnly,
+                        stream_ids: Default::default(),
+                        send_encodings: Default::default(),
+                    },
+                )
+                .unwrap();
+
+            let codecs = sort_codecs(capabilities.codecs.clone(), video_codec).unwrap();
+            transceiver.set_codec_preferences(codecs).unwrap();
+
+            // The first payload type of the m-line is the one used by the sender
+            let offer = pc.create_offer(OfferOptions::default()).await.unwrap();
+            let sdp = offer.sdp().unwrap();
+            let video = sdp.media.iter().find(|m| m.media == "video").unwrap();
+            assert!(video.codecs[0]
+                .name
+                .eq_ignore_ascii_case(video_codec.as_str()));
+        }
+    }
+}
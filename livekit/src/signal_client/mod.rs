@@ -1,4 +1,4 @@
-use crate::signal_client::signal_stream::SignalStream;
+use crate::signal_client::signal_stream::{SignalStream, WsError};
 use livekit_protocol as proto;
 
 use parking_lot::RwLock;
@@ -6,9 +6,9 @@ use std::fmt::Debug;
 use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::mpsc;
-use tokio_tungstenite::tungstenite::Error as WsError;
 use tracing::{instrument, Level};
 
+#[cfg_attr(target_arch = "wasm32", path = "web_signal_stream.rs")]
 mod signal_stream;
 
 pub(crate) type SignalEmitter = mpsc::Sender<SignalEvent>;
@@ -16,6 +16,7 @@ pub(crate) type SignalEvents = mpsc::Receiver<SignalEvent>;
 pub(crate) type SignalResult<T> = Result<T, SignalError>;
 
 pub const JOIN_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+pub const PROTOCOL_VERSION: u32 = 8;
 
 #[derive(Error, Debug)]
 pub enum SignalError {
@@ -116,14 +117,43 @@ impl SignalClient {
 }
 
 pub mod utils {
-    use crate::signal_client::{SignalError, SignalEvent, SignalResult, JOIN_RESPONSE_TIMEOUT};
+    use crate::rt::timeout;
+    use crate::signal_client::WsError;
+    use crate::signal_client::{
+        SignalError, SignalEvent, SignalOptions, SignalResult, JOIN_RESPONSE_TIMEOUT,
+        PROTOCOL_VERSION,
+    };
     use livekit_protocol as proto;
-    use tokio::time::timeout;
-    use tokio_tungstenite::tungstenite::Error as WsError;
     use tracing::{event, instrument, Level};
 
     use super::SignalEvents;
 
+    /// Url of the websocket, the token and the options are passed as query parameters
+    pub(crate) fn get_livekit_url(
+        url: &str,
+        token: &str,
+        options: &SignalOptions,
+    ) -> SignalResult<url::Url> {
+        let mut lk_url = url::Url::parse(url)?;
+        lk_url.set_path("/rtc");
+        lk_url
+            .query_pairs_mut()
+            .append_pair("access_token", token)
+            .append_pair("protocol", PROTOCOL_VERSION.to_string().as_str())
+            .append_pair("reconnect", if options.reconnect { "1" } else { "0" })
+            .append_pair("sid", &options.sid)
+            .append_pair(
+                "auto_subscribe",
+                if options.auto_subscribe { "1" } else { "0" },
+            )
+            .append_pair(
+                "adaptive_stream",
+                if options.adaptive_stream { "1" } else { "0" },
+            );
+
+        Ok(lk_url)
+    }
+
     #[instrument(level = Level::DEBUG, skip(receiver))]
     pub(crate) async fn next_join_response(
         receiver: &mut SignalEvents,
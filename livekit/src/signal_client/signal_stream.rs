@@ -1,3 +1,4 @@
+use crate::signal_client::utils::get_livekit_url;
 use crate::signal_client::{SignalEmitter, SignalEvent, SignalOptions, SignalResult};
 use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
@@ -12,7 +13,7 @@ use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 use tracing::{event, Level};
 
-pub const PROTOCOL_VERSION: u32 = 8;
+pub use tokio_tungstenite::tungstenite::Error as WsError;
 
 type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
@@ -52,23 +53,7 @@ impl SignalStream {
         options: SignalOptions,
         emitter: SignalEmitter,
     ) -> SignalResult<Self> {
-        let mut lk_url = url::Url::parse(url)?;
-        lk_url.set_path("/rtc");
-        lk_url
-            .query_pairs_mut()
-            .append_pair("access_token", token)
-            .append_pair("protocol", PROTOCOL_VERSION.to_string().as_str())
-            .append_pair("reconnect", if options.reconnect { "1" } else { "0" })
-            .append_pair("sid", &options.sid)
-            .append_pair(
-                "auto_subscribe",
-                if options.auto_subscribe { "1" } else { "0" },
-            )
-            .append_pair(
-                "adaptive_stream",
-                if options.adaptive_stream { "1" } else { "0" },
-            );
-
+        let lk_url = get_livekit_url(url, token, &options)?;
         event!(Level::INFO, "connecting to SignalClient: {}", lk_url);
         let (ws_stream, _) = connect_async(lk_url).await?;
         let _ = emitter.send(SignalEvent::Open).await;
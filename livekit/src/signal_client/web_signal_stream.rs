@@ -0,0 +1,194 @@
+use crate::rt::{self, JoinHandle};
+use crate::signal_client::utils::get_livekit_url;
+use crate::signal_client::{SignalEmitter, SignalEvent, SignalOptions, SignalResult};
+use js_sys::{ArrayBuffer, Uint8Array};
+use livekit_protocol as proto;
+use prost::Message as ProstMessage;
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tracing::{event, Level};
+use wasm_bindgen::prelude::*;
+use web_sys::{BinaryType, CloseEvent, MessageEvent, WebSocket};
+
+#[derive(Error, Debug)]
+pub enum WsError {
+    #[error("connection closed")]
+    ConnectionClosed,
+    #[error("failed to connect: {code} - {reason}")]
+    ConnectionFailed { code: u16, reason: String },
+    #[error("{0}")]
+    Js(String),
+}
+
+impl From<JsValue> for WsError {
+    fn from(value: JsValue) -> Self {
+        Self::Js(format!("{:?}", value))
+    }
+}
+
+/// Events of the browser WebSocket, forwarded in order to the read task
+#[derive(Debug)]
+enum WsMessage {
+    Open,
+    Binary(Vec<u8>),
+    Error,
+    Close { code: u16, reason: String },
+}
+
+/// SignalStream hold the WebSocket connection
+///
+/// It is replaced by [SignalClient] at each reconnection.
+#[derive(Debug)]
+pub(super) struct SignalStream {
+    ws: WebSocket,
+    read_handle: JoinHandle<()>,
+}
+
+// The WebSocket is only used from the browser thread (wasm32 is single-threaded)
+unsafe impl Send for SignalStream {}
+unsafe impl Sync for SignalStream {}
+
+impl SignalStream {
+    /// Connect to livekit websocket.
+    /// Return SignalError if the connections failed
+    ///
+    /// SignalStream will never try to reconnect if the connection has been
+    /// closed.
+    pub async fn connect(
+        url: &str,
+        token: &str,
+        options: SignalOptions,
+        emitter: SignalEmitter,
+    ) -> SignalResult<Self> {
+        let lk_url = get_livekit_url(url, token, &options)?;
+        event!(Level::INFO, "connecting to SignalClient: {}", lk_url);
+
+        let ws = WebSocket::new(lk_url.as_str()).map_err(WsError::from)?;
+        ws.set_binary_type(BinaryType::Arraybuffer);
+
+        let (ws_tx, mut ws_rx) = mpsc::unbounded_channel();
+        Self::set_handlers(&ws, ws_tx);
+
+        match ws_rx.recv().await {
+            Some(WsMessage::Open) => {}
+            Some(WsMessage::Close { code, reason }) => {
+                return Err(WsError::ConnectionFailed { code, reason }.into());
+            }
+            _ => {
+                // An error event is always followed by a close event
+                let (code, reason) = match ws_rx.recv().await {
+                    Some(WsMessage::Close { code, reason }) => (code, reason),
+                    _ => (0, String::default()),
+                };
+                return Err(WsError::ConnectionFailed { code, reason }.into());
+            }
+        }
+
+        let _ = emitter.send(SignalEvent::Open).await;
+        let read_handle = rt::spawn(Self::read_task(ws.clone(), ws_rx, emitter));
+
+        Ok(Self { ws, read_handle })
+    }
+
+    /// Close the websocket
+    /// The task ends once the server acknowledged the close
+    pub async fn close(self) {
+        let _ = self
+            .ws
+            .close_with_code_and_reason(1000, "disconnected by client");
+        let _ = self.read_handle.await;
+    }
+
+    /// Send a SignalRequest to the websocket
+    /// The browser buffers the message, it only fails if the connection is closed
+    pub async fn send(&self, signal: proto::signal_request::Message) -> SignalResult<()> {
+        event!(Level::TRACE, "sending SignalRequest: {:?}", signal);
+
+        let data = proto::SignalRequest {
+            message: Some(signal),
+        }
+        .encode_to_vec();
+
+        self.ws.send_with_u8_array(&data).map_err(|err| {
+            event!(Level::ERROR, "failed to send signal: {:?}", err);
+            WsError::from(err).into()
+        })
+    }
+
+    fn set_handlers(ws: &WebSocket, ws_tx: mpsc::UnboundedSender<WsMessage>) {
+        let onopen = Closure::<dyn FnMut()>::new({
+            let ws_tx = ws_tx.clone();
+            move || {
+                let _ = ws_tx.send(WsMessage::Open);
+            }
+        });
+
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new({
+            let ws_tx = ws_tx.clone();
+            move |event: MessageEvent| {
+                if let Some(buffer) = event.data().dyn_ref::<ArrayBuffer>() {
+                    let _ = ws_tx.send(WsMessage::Binary(Uint8Array::new(buffer).to_vec()));
+                }
+            }
+        });
+
+        let onerror = Closure::<dyn FnMut()>::new({
+            let ws_tx = ws_tx.clone();
+            move || {
+                let _ = ws_tx.send(WsMessage::Error);
+            }
+        });
+
+        let onclose = Closure::<dyn FnMut(CloseEvent)>::new(move |event: CloseEvent| {
+            let _ = ws_tx.send(WsMessage::Close {
+                code: event.code(),
+                reason: event.reason(),
+            });
+        });
+
+        ws.set_onopen(Some(onopen.into_js_value().unchecked_ref()));
+        ws.set_onmessage(Some(onmessage.into_js_value().unchecked_ref()));
+        ws.set_onerror(Some(onerror.into_js_value().unchecked_ref()));
+        ws.set_onclose(Some(onclose.into_js_value().unchecked_ref()));
+    }
+
+    /// This task is used to read incoming messages from the websocket
+    /// and dispatch them through the EventEmitter.
+    async fn read_task(
+        ws: WebSocket,
+        mut ws_rx: mpsc::UnboundedReceiver<WsMessage>,
+        emitter: SignalEmitter,
+    ) {
+        while let Some(msg) = ws_rx.recv().await {
+            match msg {
+                WsMessage::Binary(data) => {
+                    let res = proto::SignalResponse::decode(data.as_slice())
+                        .expect("failed to decode SignalResponse");
+
+                    let msg = res.message.unwrap();
+                    event!(Level::TRACE, "received SignalResponse: {:?}", msg);
+                    let _ = emitter.send(SignalEvent::Signal(msg)).await;
+                }
+                WsMessage::Close { code, reason } => {
+                    event!(
+                        Level::DEBUG,
+                        "server closed the connection: {} - {}",
+                        code,
+                        reason
+                    );
+                    break;
+                }
+                WsMessage::Error => {
+                    event!(Level::ERROR, "websocket error");
+                }
+                WsMessage::Open => {}
+            }
+        }
+
+        ws.set_onopen(None);
+        ws.set_onmessage(None);
+        ws.set_onerror(None);
+        ws.set_onclose(None);
+        let _ = emitter.send(SignalEvent::Close).await;
+    }
+}
@@ -152,7 +152,10 @@ fn main() {
         "src/video_frame_buffer.rs",
         "src/yuv_helper.rs",
         "src/helper.rs",
+        "src/audio_device.rs",
         "src/audio_resampler.rs",
+        "src/video_codec.rs",
+        "src/encoded_stream.rs",
     ]);
 
     builder.files(&[
@@ -177,6 +180,9 @@ fn main() {
         "src/video_decoder_factory.cpp",
         "src/audio_device.cpp",
         "src/audio_resampler.cpp",
+        "src/video_codec.cpp",
+        "src/passthrough_codec.cpp",
+        "src/encoded_stream.cpp",
     ]);
 
     for include in includes {
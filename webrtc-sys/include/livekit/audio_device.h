@@ -17,7 +17,9 @@
 #pragma once
 
 #include <atomic>
+#include <memory>
 
+#include "api/scoped_refptr.h"
 #include "api/task_queue/task_queue_factory.h"
 #include "modules/audio_device/include/audio_device.h"
 #include "rtc_base/synchronization/mutex.h"
@@ -126,4 +128,21 @@ class AudioDevice : public webrtc::AudioDeviceModule {
   std::atomic<bool> playing_{false};
   std::atomic<bool> initialized_{false};
 };
+
+// ADM created by the user and injected in the PeerConnectionFactory
+class AudioDeviceModule {
+ public:
+  AudioDeviceModule(
+      std::unique_ptr<webrtc::TaskQueueFactory> task_queue_factory,
+      rtc::scoped_refptr<webrtc::AudioDeviceModule> adm);
+
+  rtc::scoped_refptr<webrtc::AudioDeviceModule> get() const { return adm_; }
+
+ private:
+  // Used by the ADM, so it must outlive it
+  std::unique_ptr<webrtc::TaskQueueFactory> task_queue_factory_;
+  rtc::scoped_refptr<webrtc::AudioDeviceModule> adm_;
+};
+
+std::shared_ptr<AudioDeviceModule> create_platform_audio_device_module();
 }  // namespace livekit
@@ -17,9 +17,8 @@
 #pragma once
 
 #include <memory>
+#include <vector>
 
-#include "api/audio/audio_frame.h"
-#include "api/data_channel_interface.h"
 #include "common_audio/resampler/include/push_resampler.h"
 #include "livekit/webrtc.h"
 #include "rust/cxx.h"
@@ -28,18 +27,23 @@ namespace livekit {
 
 class AudioResampler {
  public:
-  size_t remix_and_resample(const int16_t* src,
-                            size_t samples_per_channel,
-                            size_t num_channels,
-                            int sample_rate_hz,
-                            size_t dest_num_channels,
-                            int dest_sample_rate_hz);
-
-  const int16_t* data() const;
+  // src must contain 10ms of interleaved audio, the sizes are validated on
+  // the Rust side. The returned slice is valid until the next call
+  rust::Slice<const int16_t> resample(rust::Slice<const int16_t> src,
+                                      size_t num_channels,
+                                      int sample_rate,
+                                      int dest_sample_rate);
+
+  rust::Slice<const float> resample_f32(rust::Slice<const float> src,
+                                        size_t num_channels,
+                                        int sample_rate,
+                                        int dest_sample_rate);
 
  private:
-  webrtc::AudioFrame frame_;
   webrtc::PushResampler<int16_t> resampler_;
+  webrtc::PushResampler<float> resampler_f32_;
+  std::vector<int16_t> dst_;
+  std::vector<float> dst_f32_;
 };
 
 std::unique_ptr<AudioResampler> create_audio_resampler();
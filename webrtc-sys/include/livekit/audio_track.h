@@ -18,9 +18,11 @@
 
 #include <memory>
 
+#include "absl/types/optional.h"
 #include "livekit/helper.h"
 #include "livekit/media_stream_track.h"
 #include "livekit/webrtc.h"
+#include "modules/audio_processing/include/audio_processing.h"
 #include "pc/local_audio_source.h"
 #include "rtc_base/synchronization/mutex.h"
 #include "rust/cxx.h"
@@ -67,6 +69,12 @@ class NativeAudioSink : public webrtc::AudioTrackSinkInterface {
               int sample_rate,
               size_t number_of_channels,
               size_t number_of_frames) override;
+  void OnData(const void* audio_data,
+              int bits_per_sample,
+              int sample_rate,
+              size_t number_of_channels,
+              size_t number_of_frames,
+              absl::optional<int64_t> absolute_capture_timestamp_ms) override;
 
  private:
   rust::Box<AudioSinkWrapper> observer_;
@@ -102,20 +110,47 @@ class AudioTrackSource {
   };
 
  public:
-  AudioTrackSource();
+  explicit AudioTrackSource(AudioSourceOptions options);
+
+  AudioSourceOptions audio_options() const;
+  void set_audio_options(const AudioSourceOptions& options) const;
 
+  // The captured frames go through the AudioProcessing module when one of the
+  // options is enabled
   void on_captured_frame(rust::Slice<const int16_t> audio_data,
                          int sample_rate,
                          size_t number_of_channels,
                          size_t number_of_frames) const;
 
+  // Audio played out locally, used as the reference for the echo canceller
+  void on_reverse_frame(rust::Slice<const int16_t> audio_data,
+                        int sample_rate,
+                        size_t number_of_channels,
+                        size_t number_of_frames) const;
+
+  // Only Opus is supported (see passthrough_codec.h), the packets must be at
+  // least 10ms long
+  bool on_encoded_frame(rust::Slice<const uint8_t> data,
+                        EncodedAudioFrameInfo info) const;
+
   rtc::scoped_refptr<InternalSource> get() const;
 
  private:
+  void apply_options() const;
+
   rtc::scoped_refptr<InternalSource> source_;
+
+  mutable webrtc::Mutex mutex_;
+  mutable AudioSourceOptions options_;
+  mutable rtc::scoped_refptr<webrtc::AudioProcessing> apm_;
+  mutable std::vector<int16_t> capture_buf_;
+  mutable std::vector<int16_t> reverse_buf_;
+  // Duration already sent ahead of the encoded packets
+  mutable int64_t encoded_advance_us_ = 0;
 };
 
-std::shared_ptr<AudioTrackSource> new_audio_track_source();
+std::shared_ptr<AudioTrackSource> new_audio_track_source(
+    AudioSourceOptions options);
 
 static std::shared_ptr<MediaStreamTrack> audio_to_media(
     std::shared_ptr<AudioTrack> track) {
@@ -19,6 +19,7 @@
 #include <memory>
 
 #include "api/candidate.h"
+#include "rust/cxx.h"
 
 namespace livekit {
 class Candidate;
@@ -32,6 +33,8 @@ class Candidate {
  public:
   explicit Candidate(const cricket::Candidate& candidate);
 
+  CandidateInfo info() const;
+
  private:
   cricket::Candidate candidate_;
 };
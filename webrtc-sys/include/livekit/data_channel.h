@@ -47,6 +47,7 @@ class DataChannel {
   bool send(const DataBuffer& buffer) const;
   rust::String label() const;
   DataState state() const;
+  uint64_t buffered_amount() const;
   void close() const;
 
  private:
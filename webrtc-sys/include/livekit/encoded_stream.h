@@ -0,0 +1,83 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <map>
+#include <memory>
+
+#include "api/frame_transformer_interface.h"
+#include "api/scoped_refptr.h"
+#include "livekit/rtp_receiver.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rust/cxx.h"
+
+namespace livekit {
+class NativeEncodedSink;
+}  // namespace livekit
+#include "webrtc-sys/src/encoded_stream.rs.h"
+
+namespace livekit {
+
+// Observe the depacketized frames of a RtpReceiver before they reach the
+// decoder. The sink receives a copy of the frames until it is closed, the
+// frames are always forwarded to the decoder
+class EncodedFrameTransformer : public webrtc::FrameTransformerInterface {
+ public:
+  EncodedFrameTransformer(webrtc::MediaType media_type,
+                          rust::Box<EncodedSinkWrapper> observer);
+
+  void Transform(
+      std::unique_ptr<webrtc::TransformableFrameInterface> frame) override;
+
+  void RegisterTransformedFrameCallback(
+      rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) override;
+  void RegisterTransformedFrameSinkCallback(
+      rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+      uint32_t ssrc) override;
+  void UnregisterTransformedFrameCallback() override;
+  void UnregisterTransformedFrameSinkCallback(uint32_t ssrc) override;
+
+  void close();
+
+ private:
+  webrtc::MediaType media_type_;
+  webrtc::Mutex mutex_;
+  bool closed_ = false;
+  rust::Box<EncodedSinkWrapper> observer_;
+  rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback_;
+  std::map<uint32_t, rtc::scoped_refptr<webrtc::TransformedFrameCallback>>
+      sink_callbacks_;
+};
+
+class NativeEncodedSink {
+ public:
+  NativeEncodedSink(const RtpReceiver& receiver,
+                    rust::Box<EncodedSinkWrapper> observer);
+  ~NativeEncodedSink();
+
+  // Stop copying the frames to the observer
+  void close() const;
+
+ private:
+  rtc::scoped_refptr<EncodedFrameTransformer> transformer_;
+};
+
+std::shared_ptr<NativeEncodedSink> new_native_encoded_sink(
+    const RtpReceiver& receiver,
+    rust::Box<EncodedSinkWrapper> observer);
+
+}  // namespace livekit
@@ -22,6 +22,7 @@
 #include "api/ref_counted_base.h"
 #include "api/set_local_description_observer_interface.h"
 #include "api/set_remote_description_observer_interface.h"
+#include "livekit/candidate.h"
 #include "livekit/rtc_error.h"
 #include "rust/cxx.h"
 
@@ -42,8 +43,7 @@ class IceCandidate {
 
   rust::String sdp_mid() const;
   int sdp_mline_index() const;
-  rust::String candidate() const;  // TODO(theomonnom) Return livekit::Candidate
-                                   // instead of rust::String
+  std::shared_ptr<Candidate> candidate() const;
 
   rust::String stringify() const;
   std::unique_ptr<webrtc::IceCandidateInterface> release();
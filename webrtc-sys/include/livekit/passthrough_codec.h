@@ -0,0 +1,214 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <memory>
+#include <vector>
+
+#include "absl/types/optional.h"
+#include "api/audio_codecs/audio_encoder.h"
+#include "api/audio_codecs/audio_encoder_factory.h"
+#include "api/media_stream_interface.h"
+#include "api/video/encoded_image.h"
+#include "api/video/video_frame_buffer.h"
+#include "api/video_codecs/video_codec.h"
+#include "api/video_codecs/video_encoder.h"
+#include "rtc_base/buffer.h"
+
+// Allow to publish already encoded media: the encoded data is carried through
+// the media pipeline of its own track and forwarded as is by the passthrough
+// encoders. The passthrough encoders are transparent for the other tracks,
+// they only act on the frames coming from an encoded source.
+
+namespace livekit {
+
+// Native buffer holding an encoded video frame
+class EncodedVideoFrameBuffer : public webrtc::VideoFrameBuffer {
+ public:
+  EncodedVideoFrameBuffer(
+      rtc::scoped_refptr<webrtc::VideoTrackSourceInterface> source,
+      uint64_t sequence,
+      webrtc::VideoCodecType codec_type,
+      const uint8_t* data,
+      size_t size,
+      int width,
+      int height,
+      bool keyframe);
+  ~EncodedVideoFrameBuffer() override;
+
+  Type type() const override;
+  int width() const override;
+  int height() const override;
+  rtc::scoped_refptr<webrtc::I420BufferInterface> ToI420() override;
+
+  // An encoded frame can't be scaled, it is sent with its original resolution
+  rtc::scoped_refptr<webrtc::VideoFrameBuffer> CropAndScale(
+      int offset_x,
+      int offset_y,
+      int crop_width,
+      int crop_height,
+      int scaled_width,
+      int scaled_height) override;
+
+  // Keyframe requests are forwarded to the source (see GenerateKeyFrame)
+  void request_keyframe() const;
+
+  uint64_t sequence() const { return sequence_; }
+  webrtc::VideoCodecType codec_type() const { return codec_type_; }
+  rtc::ArrayView<const uint8_t> data() const { return data_; }
+  bool keyframe() const { return keyframe_; }
+
+  // libwebrtc is built without RTTI, keep track of the live instances instead
+  // of using dynamic_cast
+  static const EncodedVideoFrameBuffer* from(
+      const webrtc::VideoFrameBuffer* buffer);
+
+ private:
+  rtc::scoped_refptr<webrtc::VideoTrackSourceInterface> source_;
+  uint64_t sequence_;
+  webrtc::VideoCodecType codec_type_;
+  rtc::Buffer data_;
+  int width_;
+  int height_;
+  bool keyframe_;
+};
+
+class PassthroughVideoEncoder : public webrtc::VideoEncoder {
+ public:
+  PassthroughVideoEncoder(webrtc::VideoCodecType codec_type,
+                          std::unique_ptr<webrtc::VideoEncoder> encoder);
+
+  void SetFecControllerOverride(
+      webrtc::FecControllerOverride* fec_controller_override) override;
+  int32_t InitEncode(const webrtc::VideoCodec* codec_settings,
+                     const webrtc::VideoEncoder::Settings& settings) override;
+  int32_t RegisterEncodeCompleteCallback(
+      webrtc::EncodedImageCallback* callback) override;
+  int32_t Release() override;
+  int32_t Encode(
+      const webrtc::VideoFrame& frame,
+      const std::vector<webrtc::VideoFrameType>* frame_types) override;
+  void SetRates(const RateControlParameters& parameters) override;
+  void OnPacketLossRateUpdate(float packet_loss_rate) override;
+  void OnRttUpdate(int64_t rtt_ms) override;
+  void OnLossNotification(const LossNotification& loss_notification) override;
+  EncoderInfo GetEncoderInfo() const override;
+
+ private:
+  int32_t encode_passthrough(
+      const webrtc::VideoFrame& frame,
+      const EncodedVideoFrameBuffer* encoded,
+      const std::vector<webrtc::VideoFrameType>* frame_types);
+
+  webrtc::VideoCodecType codec_type_;
+  std::unique_ptr<webrtc::VideoEncoder> encoder_;
+  webrtc::EncodedImageCallback* callback_ = nullptr;
+  // The stream is fed with native buffers, e.g by an encoded source
+  bool native_input_ = false;
+  bool passthrough_ = false;
+  absl::optional<uint64_t> last_sequence_;
+};
+
+// Encoded audio packets are carried in-band by "carrier" frames: 10ms of mono
+// PCM starting with a tag, followed by a chunk of the packet. The carriers
+// survive the pipeline as long as no resampling is needed (48kHz, Opus)
+class EncodedAudioCarrier {
+ public:
+  static constexpr int kSampleRate = 48000;
+  static constexpr size_t kFrames = kSampleRate / 100;
+  static constexpr size_t kHeaderSize = 5;
+  static constexpr size_t kMaxChunkSize = (kFrames - kHeaderSize) * 2;
+
+  // Split the packet into `count` carriers, empty if the packet doesn't fit
+  static std::vector<std::vector<int16_t>> split(
+      rtc::ArrayView<const uint8_t> packet,
+      size_t count);
+
+  // Returns false if the audio isn't a carrier. The chunk is appended to
+  // `packet`
+  static bool parse(rtc::ArrayView<const int16_t> audio,
+                    size_t num_channels,
+                    size_t* index,
+                    size_t* count,
+                    rtc::Buffer* packet);
+};
+
+class PassthroughAudioEncoder : public webrtc::AudioEncoder {
+ public:
+  PassthroughAudioEncoder(int payload_type,
+                          std::unique_ptr<webrtc::AudioEncoder> encoder);
+
+  int SampleRateHz() const override;
+  size_t NumChannels() const override;
+  int RtpTimestampRateHz() const override;
+  size_t Num10MsFramesInNextPacket() const override;
+  size_t Max10MsFramesInAPacket() const override;
+  int GetTargetBitrate() const override;
+  void Reset() override;
+  bool SetFec(bool enable) override;
+  bool SetDtx(bool enable) override;
+  bool GetDtx() const override;
+  bool SetApplication(Application application) override;
+  void SetMaxPlaybackRate(int frequency_hz) override;
+  void OnReceivedUplinkPacketLossFraction(
+      float uplink_packet_loss_fraction) override;
+  void OnReceivedTargetAudioBitrate(int target_bps) override;
+  void OnReceivedUplinkBandwidth(
+      int target_audio_bitrate_bps,
+      absl::optional<int64_t> bwe_period_ms) override;
+  void OnReceivedUplinkAllocation(
+      webrtc::BitrateAllocationUpdate update) override;
+  void OnReceivedRtt(int rtt_ms) override;
+  void OnReceivedOverhead(size_t overhead_bytes_per_packet) override;
+  void SetReceiverFrameLengthRange(int min_frame_length_ms,
+                                   int max_frame_length_ms) override;
+  absl::optional<std::pair<webrtc::TimeDelta, webrtc::TimeDelta>>
+  GetFrameLengthRange() const override;
+
+ protected:
+  EncodedInfo EncodeImpl(uint32_t rtp_timestamp,
+                         rtc::ArrayView<const int16_t> audio,
+                         rtc::Buffer* encoded) override;
+
+ private:
+  int payload_type_;
+  std::unique_ptr<webrtc::AudioEncoder> encoder_;
+  // Packet being reassembled from its carriers
+  rtc::Buffer packet_;
+  size_t next_index_ = 0;
+  uint32_t first_timestamp_ = 0;
+};
+
+class AudioEncoderFactory : public webrtc::AudioEncoderFactory {
+ public:
+  AudioEncoderFactory();
+
+  std::vector<webrtc::AudioCodecSpec> GetSupportedEncoders() override;
+
+  absl::optional<webrtc::AudioCodecInfo> QueryAudioEncoder(
+      const webrtc::SdpAudioFormat& format) override;
+
+  std::unique_ptr<webrtc::AudioEncoder> MakeAudioEncoder(
+      int payload_type,
+      const webrtc::SdpAudioFormat& format,
+      absl::optional<webrtc::AudioCodecPairId> codec_pair_id) override;
+
+ private:
+  rtc::scoped_refptr<webrtc::AudioEncoderFactory> builtin_factory_;
+};
+
+}  // namespace livekit
@@ -19,6 +19,8 @@
 #include "api/peer_connection_interface.h"
 #include "api/scoped_refptr.h"
 #include "livekit/audio_device.h"
+#include "livekit/video_decoder_factory.h"
+#include "livekit/video_encoder_factory.h"
 #include "media_stream.h"
 #include "peer_connection.h"
 #include "rtp_parameters.h"
@@ -38,7 +40,9 @@ webrtc::PeerConnectionInterface::RTCConfiguration to_native_rtc_configuration(
 
 class PeerConnectionFactory {
  public:
-  explicit PeerConnectionFactory(std::shared_ptr<RtcRuntime> rtc_runtime);
+  PeerConnectionFactory(std::shared_ptr<RtcRuntime> rtc_runtime,
+                        PeerConnectionFactoryOptions options,
+                        std::shared_ptr<AudioDeviceModule> audio_device);
   ~PeerConnectionFactory();
 
   std::shared_ptr<PeerConnection> create_peer_connection(
@@ -57,11 +61,27 @@ class PeerConnectionFactory {
 
   RtpCapabilities get_rtp_receiver_capabilities(MediaType type) const;
 
+  // Owned by the media engine, valid as long as this factory is alive
+  VideoEncoderFactory* video_encoder_factory() const {
+    return video_encoder_factory_;
+  }
+
+  VideoDecoderFactory* video_decoder_factory() const {
+    return video_decoder_factory_;
+  }
+
  private:
   std::shared_ptr<RtcRuntime> rtc_runtime_;
-  rtc::scoped_refptr<AudioDevice> audio_device_;
+  std::shared_ptr<AudioDeviceModule> injected_audio_device_;
+  rtc::scoped_refptr<webrtc::AudioDeviceModule> audio_device_;
+  VideoEncoderFactory* video_encoder_factory_;
+  VideoDecoderFactory* video_decoder_factory_;
   rtc::scoped_refptr<webrtc::PeerConnectionFactoryInterface> peer_factory_;
 };
 
 std::shared_ptr<PeerConnectionFactory> create_peer_connection_factory();
+std::shared_ptr<PeerConnectionFactory>
+create_peer_connection_factory_with_options(
+    PeerConnectionFactoryOptions options,
+    std::shared_ptr<AudioDeviceModule> audio_device);
 }  // namespace livekit
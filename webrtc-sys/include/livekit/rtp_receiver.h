@@ -32,7 +32,6 @@ class RtpReceiver;
 namespace livekit {
 
 // TODO(theomonnom): Implement RtpReceiverObserverInterface?
-// TODO(theomonnom): RtpSource
 // TODO(theomonnom): FrameTransformer & FrameDecryptor interface
 class RtpReceiver {
  public:
@@ -54,6 +53,9 @@ class RtpReceiver {
   void set_jitter_buffer_minimum_delay(bool is_some,
                                        double delay_seconds) const;
 
+  // Sources of the packets received in the last 10 seconds
+  rust::Vec<RtpSource> get_sources() const;
+
   rtc::scoped_refptr<webrtc::RtpReceiverInterface> rtc_receiver() const {
     return receiver_;
   }
@@ -0,0 +1,155 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <memory>
+
+#include "api/video/encoded_image.h"
+#include "api/video_codecs/sdp_video_format.h"
+#include "api/video_codecs/video_decoder.h"
+#include "api/video_codecs/video_decoder_factory.h"
+#include "api/video_codecs/video_encoder.h"
+#include "api/video_codecs/video_encoder_factory.h"
+#include "livekit/peer_connection_factory.h"
+#include "livekit/video_frame.h"
+#include "rust/cxx.h"
+
+namespace livekit {
+class EncodedImageSink;
+class DecodedImageSink;
+}  // namespace livekit
+#include "webrtc-sys/src/video_codec.rs.h"
+
+namespace livekit {
+
+webrtc::SdpVideoFormat to_native_sdp_video_format(const SdpVideoFormat& format);
+SdpVideoFormat to_rust_sdp_video_format(const webrtc::SdpVideoFormat& format);
+
+// Forward the images produced by a Rust encoder to libwebrtc
+class EncodedImageSink {
+ public:
+  EncodedImageSink(webrtc::EncodedImageCallback* callback,
+                   webrtc::VideoCodecType codec_type);
+
+  bool on_encoded_image(rust::Slice<const uint8_t> data,
+                        EncodedImageInfo info) const;
+
+ private:
+  webrtc::EncodedImageCallback* callback_;
+  webrtc::VideoCodecType codec_type_;
+};
+
+// Forward the frames produced by a Rust decoder to libwebrtc
+class DecodedImageSink {
+ public:
+  explicit DecodedImageSink(webrtc::DecodedImageCallback* callback);
+
+  void on_decoded_frame(std::unique_ptr<VideoFrame> frame,
+                        uint32_t rtp_timestamp) const;
+
+ private:
+  webrtc::DecodedImageCallback* callback_;
+};
+
+class RustVideoEncoder : public webrtc::VideoEncoder {
+ public:
+  RustVideoEncoder(const webrtc::SdpVideoFormat& format,
+                   rust::Box<VideoEncoderWrapper> encoder);
+
+  int32_t InitEncode(const webrtc::VideoCodec* codec_settings,
+                     const webrtc::VideoEncoder::Settings& settings) override;
+
+  int32_t RegisterEncodeCompleteCallback(
+      webrtc::EncodedImageCallback* callback) override;
+
+  int32_t Release() override;
+
+  int32_t Encode(
+      const webrtc::VideoFrame& frame,
+      const std::vector<webrtc::VideoFrameType>* frame_types) override;
+
+  void SetRates(const RateControlParameters& parameters) override;
+
+  EncoderInfo GetEncoderInfo() const override;
+
+ private:
+  webrtc::VideoCodecType codec_type_;
+  rust::Box<VideoEncoderWrapper> encoder_;
+  std::unique_ptr<EncodedImageSink> sink_;
+};
+
+class RustVideoDecoder : public webrtc::VideoDecoder {
+ public:
+  explicit RustVideoDecoder(rust::Box<VideoDecoderWrapper> decoder);
+
+  bool Configure(const Settings& settings) override;
+
+  int32_t Decode(const webrtc::EncodedImage& input_image,
+                 bool missing_frames,
+                 int64_t render_time_ms) override;
+
+  int32_t RegisterDecodeCompleteCallback(
+      webrtc::DecodedImageCallback* callback) override;
+
+  int32_t Release() override;
+
+  DecoderInfo GetDecoderInfo() const override;
+
+  const char* ImplementationName() const override;
+
+ private:
+  rust::Box<VideoDecoderWrapper> decoder_;
+  std::unique_ptr<DecodedImageSink> sink_;
+};
+
+class RustVideoEncoderFactory : public webrtc::VideoEncoderFactory {
+ public:
+  explicit RustVideoEncoderFactory(
+      rust::Box<VideoEncoderFactoryWrapper> factory);
+
+  std::vector<webrtc::SdpVideoFormat> GetSupportedFormats() const override;
+
+  std::unique_ptr<webrtc::VideoEncoder> CreateVideoEncoder(
+      const webrtc::SdpVideoFormat& format) override;
+
+ private:
+  rust::Box<VideoEncoderFactoryWrapper> factory_;
+};
+
+class RustVideoDecoderFactory : public webrtc::VideoDecoderFactory {
+ public:
+  explicit RustVideoDecoderFactory(
+      rust::Box<VideoDecoderFactoryWrapper> factory);
+
+  std::vector<webrtc::SdpVideoFormat> GetSupportedFormats() const override;
+
+  std::unique_ptr<webrtc::VideoDecoder> CreateVideoDecoder(
+      const webrtc::SdpVideoFormat& format) override;
+
+ private:
+  rust::Box<VideoDecoderFactoryWrapper> factory_;
+};
+
+void register_video_encoder_factory(
+    const PeerConnectionFactory& factory,
+    rust::Box<VideoEncoderFactoryWrapper> encoder_factory);
+
+void register_video_decoder_factory(
+    const PeerConnectionFactory& factory,
+    rust::Box<VideoDecoderFactoryWrapper> decoder_factory);
+
+}  // namespace livekit
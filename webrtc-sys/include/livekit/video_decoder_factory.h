@@ -18,6 +18,7 @@
 
 #include "api/video_codecs/video_decoder.h"
 #include "api/video_codecs/video_decoder_factory.h"
+#include "rtc_base/synchronization/mutex.h"
 
 namespace livekit {
 class VideoDecoderFactory : public webrtc::VideoDecoderFactory {
@@ -29,7 +30,11 @@ class VideoDecoderFactory : public webrtc::VideoDecoderFactory {
   std::unique_ptr<webrtc::VideoDecoder> CreateVideoDecoder(
       const webrtc::SdpVideoFormat& format) override;
 
+  // Factories added here take precedence over the builtin ones
+  void add_factory(std::unique_ptr<webrtc::VideoDecoderFactory> factory);
+
  private:
+  mutable webrtc::Mutex mutex_;
   std::vector<std::unique_ptr<webrtc::VideoDecoderFactory>> factories_;
 };
 }  // namespace livekit
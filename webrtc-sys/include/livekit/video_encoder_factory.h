@@ -18,6 +18,7 @@
 
 #include "api/video_codecs/video_encoder.h"
 #include "api/video_codecs/video_encoder_factory.h"
+#include "rtc_base/synchronization/mutex.h"
 
 namespace livekit {
 class VideoEncoderFactory : public webrtc::VideoEncoderFactory {
@@ -29,7 +30,11 @@ class VideoEncoderFactory : public webrtc::VideoEncoderFactory {
   std::unique_ptr<webrtc::VideoEncoder> CreateVideoEncoder(
       const webrtc::SdpVideoFormat& format) override;
 
+  // Factories added here take precedence over the builtin ones
+  void add_factory(std::unique_ptr<webrtc::VideoEncoderFactory> factory);
+
  private:
+  mutable webrtc::Mutex mutex_;
   std::vector<std::unique_ptr<webrtc::VideoEncoderFactory>> factories_;
 };
 }  // namespace livekit
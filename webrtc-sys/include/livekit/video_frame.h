@@ -41,6 +41,10 @@ class VideoFrame {
   uint32_t transport_frame_id() const;
   uint32_t timestamp() const;
 
+  // Latest receive time of the packets of this frame, -1 if the frame
+  // wasn't received from the network
+  int64_t receive_time_us() const;
+
   VideoRotation rotation() const;
   std::unique_ptr<VideoFrameBuffer> video_frame_buffer() const;
 
@@ -59,6 +63,8 @@ class VideoFrameBuilder {
   // TODO(theomonnom): other setters?
   void set_video_frame_buffer(const VideoFrameBuffer& buffer);
   void set_timestamp_us(int64_t timestamp_us);
+  void set_rtp_timestamp(uint32_t rtp_timestamp);
+  void set_ntp_time_ms(int64_t ntp_time_ms);
   void set_rotation(VideoRotation rotation);
   void set_id(uint16_t id);
   std::unique_ptr<VideoFrame> build();
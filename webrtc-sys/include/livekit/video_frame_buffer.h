@@ -16,10 +16,17 @@
 
 #pragma once
 
+#include <list>
 #include <memory>
 
+#include "api/video/i010_buffer.h"
 #include "api/video/i420_buffer.h"
+#include "api/video/i422_buffer.h"
+#include "api/video/i444_buffer.h"
+#include "api/video/nv12_buffer.h"
 #include "api/video/video_frame_buffer.h"
+#include "rtc_base/ref_counted_object.h"
+#include "rtc_base/synchronization/mutex.h"
 
 namespace livekit {
 class VideoFrameBuffer;
@@ -34,6 +41,13 @@ class I422Buffer;
 class I444Buffer;
 class I010Buffer;
 class NV12Buffer;
+template <class Buffer, class Wrapper>
+class VideoFrameBufferPool;
+using I420BufferPool = VideoFrameBufferPool<webrtc::I420Buffer, I420Buffer>;
+using I422BufferPool = VideoFrameBufferPool<webrtc::I422Buffer, I422Buffer>;
+using I444BufferPool = VideoFrameBufferPool<webrtc::I444Buffer, I444Buffer>;
+using I010BufferPool = VideoFrameBufferPool<webrtc::I010Buffer, I010Buffer>;
+using NV12BufferPool = VideoFrameBufferPool<webrtc::NV12Buffer, NV12Buffer>;
 }  // namespace livekit
 #include "webrtc-sys/src/video_frame_buffer.rs.h"
 
@@ -133,12 +147,29 @@ class BiplanarYuv8Buffer : public BiplanarYuvBuffer {
 };
 
 std::unique_ptr<I420Buffer> new_i420_buffer(int width, int height);
+std::unique_ptr<I420ABuffer> new_i420a_buffer(int width, int height);
+std::unique_ptr<I422Buffer> new_i422_buffer(int width, int height);
+std::unique_ptr<I444Buffer> new_i444_buffer(int width, int height);
+std::unique_ptr<I010Buffer> new_i010_buffer(int width, int height);
+std::unique_ptr<NV12Buffer> new_nv12_buffer(int width, int height);
 std::unique_ptr<I420Buffer> copy_i420_buffer(
     const std::unique_ptr<I420Buffer>& i420);
 
 class I420Buffer : public PlanarYuv8Buffer {
  public:
   explicit I420Buffer(rtc::scoped_refptr<webrtc::I420BufferInterface> buffer);
+
+  std::unique_ptr<I420Buffer> scale(int scaled_width,
+                                    int scaled_height,
+                                    ScaleFilter filter) const;
+  // x and y are rounded down to even values (chroma is subsampled)
+  std::unique_ptr<I420Buffer> crop(int x, int y, int width, int height) const;
+  // rotation is in degrees (0, 90, 180 or 270)
+  std::unique_ptr<I420Buffer> rotate(int rotation) const;
+  std::unique_ptr<I420Buffer> mirror() const;
+
+ private:
+  const webrtc::I420BufferInterface* buffer() const;
 };
 
 class I420ABuffer : public I420Buffer {
@@ -172,6 +203,99 @@ class NV12Buffer : public BiplanarYuv8Buffer {
   explicit NV12Buffer(rtc::scoped_refptr<webrtc::NV12BufferInterface> buffer);
 };
 
+// Same as webrtc::VideoFrameBufferPool, but it can be used from any thread.
+// A buffer is reused once the pool holds the only reference to it (e.g the
+// encoder released the frame). Reused buffers aren't cleared.
+template <class Buffer, class Wrapper>
+class VideoFrameBufferPool {
+ public:
+  explicit VideoFrameBufferPool(size_t max_number_of_buffers)
+      : max_number_of_buffers_(max_number_of_buffers) {}
+
+  // Returns nullptr if all the buffers are in use
+  std::unique_ptr<Wrapper> create(int width, int height) const {
+    webrtc::MutexLock lock(&mutex_);
+    rtc::scoped_refptr<rtc::RefCountedObject<Buffer>> free_buffer;
+    for (auto it = buffers_.begin(); it != buffers_.end();) {
+      if ((*it)->HasOneRef()) {
+        if ((*it)->width() == width && (*it)->height() == height) {
+          free_buffer = *it;
+          break;
+        }
+
+        // The resolution changed, drop the unused buffer
+        it = buffers_.erase(it);
+        continue;
+      }
+      ++it;
+    }
+
+    if (!free_buffer) {
+      if (buffers_.size() >= max_number_of_buffers_)
+        return nullptr;
+
+      free_buffer = rtc::scoped_refptr<rtc::RefCountedObject<Buffer>>(
+          new rtc::RefCountedObject<Buffer>(width, height));
+      buffers_.push_back(free_buffer);
+    }
+
+    return std::make_unique<Wrapper>(free_buffer);
+  }
+
+  // Drop the buffers that aren't in use
+  void release() const {
+    webrtc::MutexLock lock(&mutex_);
+    buffers_.remove_if([](const auto& buffer) { return buffer->HasOneRef(); });
+  }
+
+  // Returns false if more buffers than the new maximum are in use
+  bool resize(size_t max_number_of_buffers) const {
+    webrtc::MutexLock lock(&mutex_);
+    max_number_of_buffers_ = max_number_of_buffers;
+    size_t used = 0;
+    for (auto it = buffers_.begin(); it != buffers_.end();) {
+      if ((*it)->HasOneRef() && buffers_.size() > max_number_of_buffers_) {
+        it = buffers_.erase(it);
+        continue;
+      }
+      used += (*it)->HasOneRef() ? 0 : 1;
+      ++it;
+    }
+    return used <= max_number_of_buffers_;
+  }
+
+ private:
+  mutable webrtc::Mutex mutex_;
+  mutable size_t max_number_of_buffers_;
+  mutable std::list<rtc::scoped_refptr<rtc::RefCountedObject<Buffer>>>
+      buffers_;
+};
+
+static std::shared_ptr<I420BufferPool> new_i420_buffer_pool(
+    size_t max_number_of_buffers) {
+  return std::make_shared<I420BufferPool>(max_number_of_buffers);
+}
+
+static std::shared_ptr<I422BufferPool> new_i422_buffer_pool(
+    size_t max_number_of_buffers) {
+  return std::make_shared<I422BufferPool>(max_number_of_buffers);
+}
+
+static std::shared_ptr<I444BufferPool> new_i444_buffer_pool(
+    size_t max_number_of_buffers) {
+  return std::make_shared<I444BufferPool>(max_number_of_buffers);
+}
+
+static std::shared_ptr<I010BufferPool> new_i010_buffer_pool(
+    size_t max_number_of_buffers) {
+  return std::make_shared<I010BufferPool>(max_number_of_buffers);
+}
+
+static std::shared_ptr<NV12BufferPool> new_nv12_buffer_pool(
+    size_t max_number_of_buffers) {
+  return std::make_shared<NV12BufferPool>(max_number_of_buffers);
+}
+
 static const VideoFrameBuffer* yuv_to_vfb(const PlanarYuvBuffer* yuv) {
   return yuv;
 }
@@ -16,15 +16,18 @@
 
 #pragma once
 
+#include <atomic>
 #include <memory>
 
 #include "api/media_stream_interface.h"
+#include "api/notifier.h"
 #include "api/video/video_frame.h"
 #include "livekit/helper.h"
 #include "livekit/media_stream_track.h"
 #include "livekit/video_frame.h"
 #include "livekit/webrtc.h"
-#include "media/base/adapted_video_track_source.h"
+#include "media/base/video_adapter.h"
+#include "media/base/video_broadcaster.h"
 #include "rtc_base/synchronization/mutex.h"
 #include "rtc_base/timestamp_aligner.h"
 #include "rust/cxx.h"
@@ -85,36 +88,67 @@ std::shared_ptr<NativeVideoSink> new_native_video_sink(
     rust::Box<VideoSinkWrapper> observer);
 
 class VideoTrackSource {
-  class InternalSource : public rtc::AdaptedVideoTrackSource {
+  // Same behavior as rtc::AdaptedVideoTrackSource, which doesn't let us
+  // observe the aggregated VideoSinkWants
+  class InternalSource
+      : public webrtc::Notifier<webrtc::VideoTrackSourceInterface> {
    public:
-    InternalSource();
+    explicit InternalSource(
+        rust::Box<VideoTrackSourceObserverWrapper> observer);
     ~InternalSource() override;
 
     bool is_screencast() const override;
     absl::optional<bool> needs_denoising() const override;
     SourceState state() const override;
     bool remote() const override;
+    bool GetStats(Stats* stats) override;
+
+    void AddOrUpdateSink(rtc::VideoSinkInterface<webrtc::VideoFrame>* sink,
+                         const rtc::VideoSinkWants& wants) override;
+    void RemoveSink(rtc::VideoSinkInterface<webrtc::VideoFrame>* sink) override;
+
+    bool SupportsEncodedOutput() const override;
+    void GenerateKeyFrame() override;
+    void AddEncodedSink(
+        rtc::VideoSinkInterface<webrtc::RecordableEncodedFrame>* sink) override;
+    void RemoveEncodedSink(
+        rtc::VideoSinkInterface<webrtc::RecordableEncodedFrame>* sink) override;
 
     bool on_captured_frame(const webrtc::VideoFrame& frame);
+    void on_encoded_frame(const webrtc::VideoFrame& frame);
 
    private:
+    void on_sink_wants_changed(const rtc::VideoSinkWants& wants);
+    void broadcast_frame(const webrtc::VideoFrame& frame);
+
     webrtc::Mutex mutex_;
     rtc::TimestampAligner timestamp_aligner_;
+    rtc::VideoBroadcaster broadcaster_;
+    cricket::VideoAdapter video_adapter_;
+    webrtc::Mutex stats_mutex_;
+    absl::optional<Stats> stats_ RTC_GUARDED_BY(stats_mutex_);
+    rust::Box<VideoTrackSourceObserverWrapper> observer_;
   };
 
  public:
-  VideoTrackSource();
+  explicit VideoTrackSource(
+      rust::Box<VideoTrackSourceObserverWrapper> observer);
 
   bool on_captured_frame(const std::unique_ptr<VideoFrame>& frame)
       const;  // frames pushed from Rust (+interior mutability)
 
+  bool on_encoded_frame(rust::Slice<const uint8_t> data,
+                        EncodedVideoFrameInfo info) const;
+
   rtc::scoped_refptr<InternalSource> get() const;
 
  private:
   rtc::scoped_refptr<InternalSource> source_;
+  mutable std::atomic<uint64_t> encoded_sequence_{0};
 };
 
-std::shared_ptr<VideoTrackSource> new_video_track_source();
+std::shared_ptr<VideoTrackSource> new_video_track_source(
+    rust::Box<VideoTrackSourceObserverWrapper> observer);
 
 static std::shared_ptr<MediaStreamTrack> video_to_media(
     std::shared_ptr<VideoTrack> track) {
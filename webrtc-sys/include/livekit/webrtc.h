@@ -47,8 +47,13 @@ class RtpSender;
 // on resources that depend on it. (e.g: AudioTrack, VideoTrack).
 class RtcRuntime : public std::enable_shared_from_this<RtcRuntime> {
  public:
-  [[nodiscard]] static std::shared_ptr<RtcRuntime> create() {
-    return std::shared_ptr<RtcRuntime>(new RtcRuntime());
+  // network_threads and worker_threads are the sizes of the thread pools
+  // shared with the other runtimes, 0 starts threads dedicated to this runtime
+  [[nodiscard]] static std::shared_ptr<RtcRuntime> create(
+      uint32_t network_threads = 0,
+      uint32_t worker_threads = 0) {
+    return std::shared_ptr<RtcRuntime>(
+        new RtcRuntime(network_threads, worker_threads));
   }
 
   RtcRuntime(const RtcRuntime&) = delete;
@@ -69,10 +74,11 @@ class RtcRuntime : public std::enable_shared_from_this<RtcRuntime> {
       rtc::scoped_refptr<webrtc::VideoTrackInterface> track);
 
  private:
-  RtcRuntime();
+  RtcRuntime(uint32_t network_threads, uint32_t worker_threads);
 
-  std::unique_ptr<rtc::Thread> network_thread_;
-  std::unique_ptr<rtc::Thread> worker_thread_;
+  // Stopped once the last runtime using them is dropped
+  std::shared_ptr<rtc::Thread> network_thread_;
+  std::shared_ptr<rtc::Thread> worker_thread_;
   std::unique_ptr<rtc::Thread> signaling_thread_;
 
   // Lists used to make sure we don't create multiple wrappers for one
@@ -16,11 +16,18 @@
 
 #pragma once
 
+#include <cstdlib>
 #include <memory>
 #include <stdexcept>
 #include <string>
+#include <vector>
 
 #include "api/video/yuv_helper.h"
+#include "libyuv/convert.h"
+#include "libyuv/convert_argb.h"
+#include "libyuv/convert_from.h"
+#include "libyuv/convert_from_argb.h"
+#include "libyuv/planar_functions.h"
 #include "webrtc-sys/src/yuv_helper.rs.h"
 
 namespace livekit {
@@ -130,4 +137,350 @@ static void argb_to_rgb24(const uint8_t* src_argb,
                                      dst_stride_rgb24, width, height));
 }
 
+static void i420_to_rgb24(const uint8_t* src_y,
+                          int src_stride_y,
+                          const uint8_t* src_u,
+                          int src_stride_u,
+                          const uint8_t* src_v,
+                          int src_stride_v,
+                          uint8_t* dst_rgb24,
+                          int dst_stride_rgb24,
+                          int width,
+                          int height) {
+  THROW_ON_ERROR(libyuv::I420ToRGB24(src_y, src_stride_y, src_u, src_stride_u,
+                                     src_v, src_stride_v, dst_rgb24,
+                                     dst_stride_rgb24, width, height));
+}
+
+static void i422_to_argb(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_argb,
+                         int dst_stride_argb,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I422ToARGB(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_argb,
+                                    dst_stride_argb, width, height));
+}
+
+static void i422_to_abgr(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_abgr,
+                         int dst_stride_abgr,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I422ToABGR(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_abgr,
+                                    dst_stride_abgr, width, height));
+}
+
+static void i444_to_argb(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_argb,
+                         int dst_stride_argb,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I444ToARGB(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_argb,
+                                    dst_stride_argb, width, height));
+}
+
+static void i444_to_abgr(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_abgr,
+                         int dst_stride_abgr,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I444ToABGR(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_abgr,
+                                    dst_stride_abgr, width, height));
+}
+
+static void i420_to_i010(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint16_t* dst_y,
+                         int dst_stride_y,
+                         uint16_t* dst_u,
+                         int dst_stride_u,
+                         uint16_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToI010(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void i420_to_nv12(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_uv,
+                         int dst_stride_uv,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToNV12(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_uv, dst_stride_uv, width, height));
+}
+
+static void i420_to_i422(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToI422(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void i420_to_i444(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I420ToI444(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void rgba_to_i420(const uint8_t* src_rgba,
+                         int src_stride_rgba,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::RGBAToI420(src_rgba, src_stride_rgba, dst_y,
+                                    dst_stride_y, dst_u, dst_stride_u, dst_v,
+                                    dst_stride_v, width, height));
+}
+
+static void bgra_to_i420(const uint8_t* src_bgra,
+                         int src_stride_bgra,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::BGRAToI420(src_bgra, src_stride_bgra, dst_y,
+                                    dst_stride_y, dst_u, dst_stride_u, dst_v,
+                                    dst_stride_v, width, height));
+}
+
+static void rgb24_to_i420(const uint8_t* src_rgb24,
+                          int src_stride_rgb24,
+                          uint8_t* dst_y,
+                          int dst_stride_y,
+                          uint8_t* dst_u,
+                          int dst_stride_u,
+                          uint8_t* dst_v,
+                          int dst_stride_v,
+                          int width,
+                          int height) {
+  THROW_ON_ERROR(libyuv::RGB24ToI420(src_rgb24, src_stride_rgb24, dst_y,
+                                     dst_stride_y, dst_u, dst_stride_u, dst_v,
+                                     dst_stride_v, width, height));
+}
+
+static void nv12_to_i420(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_uv,
+                         int src_stride_uv,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::NV12ToI420(src_y, src_stride_y, src_uv, src_stride_uv,
+                                    dst_y, dst_stride_y, dst_u, dst_stride_u,
+                                    dst_v, dst_stride_v, width, height));
+}
+
+static void i422_to_i420(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I422ToI420(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void i444_to_i420(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_u,
+                         int src_stride_u,
+                         const uint8_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I444ToI420(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void i010_to_i420(const uint16_t* src_y,
+                         int src_stride_y,
+                         const uint16_t* src_u,
+                         int src_stride_u,
+                         const uint16_t* src_v,
+                         int src_stride_v,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_u,
+                         int dst_stride_u,
+                         uint8_t* dst_v,
+                         int dst_stride_v,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::I010ToI420(src_y, src_stride_y, src_u, src_stride_u,
+                                    src_v, src_stride_v, dst_y, dst_stride_y,
+                                    dst_u, dst_stride_u, dst_v, dst_stride_v,
+                                    width, height));
+}
+
+static void nv12_to_argb(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_uv,
+                         int src_stride_uv,
+                         uint8_t* dst_argb,
+                         int dst_stride_argb,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::NV12ToARGB(src_y, src_stride_y, src_uv, src_stride_uv,
+                                    dst_argb, dst_stride_argb, width, height));
+}
+
+static void nv12_to_abgr(const uint8_t* src_y,
+                         int src_stride_y,
+                         const uint8_t* src_uv,
+                         int src_stride_uv,
+                         uint8_t* dst_abgr,
+                         int dst_stride_abgr,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::NV12ToABGR(src_y, src_stride_y, src_uv, src_stride_uv,
+                                    dst_abgr, dst_stride_abgr, width, height));
+}
+
+static void argb_to_nv12(const uint8_t* src_argb,
+                         int src_stride_argb,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_uv,
+                         int dst_stride_uv,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::ARGBToNV12(src_argb, src_stride_argb, dst_y,
+                                    dst_stride_y, dst_uv, dst_stride_uv, width,
+                                    height));
+}
+
+static void abgr_to_nv12(const uint8_t* src_abgr,
+                         int src_stride_abgr,
+                         uint8_t* dst_y,
+                         int dst_stride_y,
+                         uint8_t* dst_uv,
+                         int dst_stride_uv,
+                         int width,
+                         int height) {
+  THROW_ON_ERROR(libyuv::ABGRToNV12(src_abgr, src_stride_abgr, dst_y,
+                                    dst_stride_y, dst_uv, dst_stride_uv, width,
+                                    height));
+}
+
+static void rgb24_to_nv12(const uint8_t* src_rgb24,
+                          int src_stride_rgb24,
+                          uint8_t* dst_y,
+                          int dst_stride_y,
+                          uint8_t* dst_uv,
+                          int dst_stride_uv,
+                          int width,
+                          int height) {
+  // libyuv has no direct RGB24 to NV12 conversion, go through I420
+  int abs_height = std::abs(height);
+  int chroma_width = (width + 1) / 2;
+  int chroma_height = (abs_height + 1) / 2;
+  std::vector<uint8_t> u(static_cast<size_t>(chroma_width) * chroma_height);
+  std::vector<uint8_t> v(static_cast<size_t>(chroma_width) * chroma_height);
+  THROW_ON_ERROR(libyuv::RGB24ToI420(src_rgb24, src_stride_rgb24, dst_y,
+                                     dst_stride_y, u.data(), chroma_width,
+                                     v.data(), chroma_width, width, height));
+  THROW_ON_ERROR(libyuv::MergeUVPlane(u.data(), chroma_width, v.data(),
+                                      chroma_width, dst_uv, dst_stride_uv,
+                                      chroma_width, chroma_height));
+}
+
 }  // namespace livekit
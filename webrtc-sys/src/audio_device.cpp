@@ -16,6 +16,11 @@
 
 #include "livekit/audio_device.h"
 
+#include <utility>
+
+#include "api/task_queue/default_task_queue_factory.h"
+#include "rtc_base/logging.h"
+
 const int kBytesPerSample = 2;
 const int kSampleRate = 48000;
 const int kChannels = 2;
@@ -321,4 +326,27 @@ int32_t AudioDevice::SetAudioDeviceSink(webrtc::AudioDeviceSink* sink) const {
   return 0;
 }
 
+AudioDeviceModule::AudioDeviceModule(
+    std::unique_ptr<webrtc::TaskQueueFactory> task_queue_factory,
+    rtc::scoped_refptr<webrtc::AudioDeviceModule> adm)
+    : task_queue_factory_(std::move(task_queue_factory)),
+      adm_(std::move(adm)) {}
+
+std::shared_ptr<AudioDeviceModule> create_platform_audio_device_module() {
+  std::unique_ptr<webrtc::TaskQueueFactory> task_queue_factory =
+      webrtc::CreateDefaultTaskQueueFactory();
+  rtc::scoped_refptr<webrtc::AudioDeviceModule> adm =
+      webrtc::AudioDeviceModule::Create(
+          webrtc::AudioDeviceModule::kPlatformDefaultAudio,
+          task_queue_factory.get());
+
+  if (!adm) {
+    RTC_LOG(LS_WARNING) << "Failed to create the platform ADM";
+    return nullptr;
+  }
+
+  return std::make_shared<AudioDeviceModule>(std::move(task_queue_factory),
+                                             std::move(adm));
+}
+
 }  // namespace livekit
@@ -0,0 +1,15 @@
+use crate::impl_thread_safety;
+
+#[cxx::bridge(namespace = "livekit")]
+pub mod ffi {
+    unsafe extern "C++" {
+        include!("livekit/audio_device.h");
+
+        type AudioDeviceModule;
+
+        /// Null if the platform doesn't have an audio device implementation
+        fn create_platform_audio_device_module() -> SharedPtr<AudioDeviceModule>;
+    }
+}
+
+impl_thread_safety!(ffi::AudioDeviceModule, Send + Sync);
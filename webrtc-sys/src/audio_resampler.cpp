@@ -18,26 +18,45 @@
 
 #include <memory>
 
-#include "audio/remix_resample.h"
-
 namespace livekit {
 
-size_t AudioResampler::remix_and_resample(const int16_t* src,
-                                          size_t samples_per_channel,
-                                          size_t num_channels,
-                                          int sample_rate,
-                                          size_t dest_num_channels,
-                                          int dest_sample_rate) {
-  frame_.num_channels_ = dest_num_channels;
-  frame_.sample_rate_hz_ = dest_sample_rate;
-  webrtc::voe::RemixAndResample(src, samples_per_channel, num_channels,
-                                sample_rate, &resampler_, &frame_);
-
-  return frame_.num_channels() * frame_.samples_per_channel() * sizeof(int16_t);
+namespace {
+
+template <typename T>
+rust::Slice<const T> resample_10ms(webrtc::PushResampler<T>& resampler,
+                                   std::vector<T>& dst,
+                                   rust::Slice<const T> src,
+                                   size_t num_channels,
+                                   int sample_rate,
+                                   int dest_sample_rate) {
+  dst.resize(dest_sample_rate / 100 * num_channels);
+  resampler.InitializeIfNeeded(sample_rate, dest_sample_rate, num_channels);
+  int size = resampler.Resample(src.data(), src.size(), dst.data(),
+                                dst.size());
+  if (size < 0) {
+    return rust::Slice<const T>();
+  }
+  return rust::Slice<const T>(dst.data(), size);
+}
+
+}  // namespace
+
+rust::Slice<const int16_t> AudioResampler::resample(
+    rust::Slice<const int16_t> src,
+    size_t num_channels,
+    int sample_rate,
+    int dest_sample_rate) {
+  return resample_10ms(resampler_, dst_, src, num_channels, sample_rate,
+                       dest_sample_rate);
 }
 
-const int16_t* AudioResampler::data() const {
-  return frame_.data();
+rust::Slice<const float> AudioResampler::resample_f32(
+    rust::Slice<const float> src,
+    size_t num_channels,
+    int sample_rate,
+    int dest_sample_rate) {
+  return resample_10ms(resampler_f32_, dst_f32_, src, num_channels,
+                       sample_rate, dest_sample_rate);
 }
 
 std::unique_ptr<AudioResampler> create_audio_resampler() {
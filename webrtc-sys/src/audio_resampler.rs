@@ -7,17 +7,21 @@ pub mod ffi {
 
         type AudioResampler;
 
-        unsafe fn remix_and_resample(
-            self: Pin<&mut AudioResampler>,
-            src: *const i16,
-            samples_per_channel: usize,
+        fn resample<'a>(
+            self: Pin<&'a mut AudioResampler>,
+            src: &[i16],
             num_channels: usize,
             sample_rate: i32,
-            dst_num_channels: usize,
             dst_sample_rate: i32,
-        ) -> usize;
+        ) -> &'a [i16];
 
-        unsafe fn data(self: &AudioResampler) -> *const i16;
+        fn resample_f32<'a>(
+            self: Pin<&'a mut AudioResampler>,
+            src: &[f32],
+            num_channels: usize,
+            sample_rate: i32,
+            dst_sample_rate: i32,
+        ) -> &'a [f32];
 
         fn create_audio_resampler() -> UniquePtr<AudioResampler>;
     }
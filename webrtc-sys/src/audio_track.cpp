@@ -23,6 +23,7 @@
 #include "api/media_stream_interface.h"
 #include "audio/remix_resample.h"
 #include "common_audio/include/audio_util.h"
+#include "livekit/passthrough_codec.h"
 #include "rtc_base/logging.h"
 #include "rtc_base/ref_counted_object.h"
 #include "rtc_base/synchronization/mutex.h"
@@ -63,10 +64,27 @@ void NativeAudioSink::OnData(const void* audio_data,
                              int sample_rate,
                              size_t number_of_channels,
                              size_t number_of_frames) {
+  OnData(audio_data, bits_per_sample, sample_rate, number_of_channels,
+         number_of_frames, absl::nullopt);
+}
+
+void NativeAudioSink::OnData(
+    const void* audio_data,
+    int bits_per_sample,
+    int sample_rate,
+    size_t number_of_channels,
+    size_t number_of_frames,
+    absl::optional<int64_t> absolute_capture_timestamp_ms) {
   RTC_CHECK_EQ(16, bits_per_sample);
   rust::Slice<const int16_t> data(static_cast<const int16_t*>(audio_data),
                                   number_of_channels * number_of_frames);
-  observer_->on_data(data, sample_rate, number_of_channels, number_of_frames);
+
+  AudioDataTiming timing{};
+  timing.absolute_capture_timestamp_ms =
+      absolute_capture_timestamp_ms.value_or(-1);
+
+  observer_->on_data(data, sample_rate, number_of_channels, number_of_frames,
+                     timing);
 }
 
 std::shared_ptr<NativeAudioSink> new_native_audio_sink(
@@ -117,25 +135,130 @@ void AudioTrackSource::InternalSource::on_captured_frame(
   }
 }
 
-AudioTrackSource::AudioTrackSource() {
+AudioTrackSource::AudioTrackSource(AudioSourceOptions options)
+    : options_(options) {
   source_ = rtc::make_ref_counted<InternalSource>();
+  apply_options();
+}
+
+AudioSourceOptions AudioTrackSource::audio_options() const {
+  webrtc::MutexLock lock(&mutex_);
+  return options_;
+}
+
+void AudioTrackSource::set_audio_options(
+    const AudioSourceOptions& options) const {
+  webrtc::MutexLock lock(&mutex_);
+  options_ = options;
+  apply_options();
+}
+
+void AudioTrackSource::apply_options() const {
+  if (!options_.echo_cancellation && !options_.noise_suppression &&
+      !options_.auto_gain_control) {
+    apm_ = nullptr;
+    return;
+  }
+
+  if (!apm_)
+    apm_ = webrtc::AudioProcessingBuilder().Create();
+
+  webrtc::AudioProcessing::Config config;
+  config.echo_canceller.enabled = options_.echo_cancellation;
+  config.noise_suppression.enabled = options_.noise_suppression;
+  config.gain_controller2.enabled = options_.auto_gain_control;
+  config.gain_controller2.adaptive_digital.enabled =
+      options_.auto_gain_control;
+  config.high_pass_filter.enabled =
+      options_.echo_cancellation || options_.noise_suppression;
+  apm_->ApplyConfig(config);
 }
 
 void AudioTrackSource::on_captured_frame(rust::Slice<const int16_t> audio_data,
                                          int sample_rate,
                                          size_t number_of_channels,
                                          size_t number_of_frames) const {
+  webrtc::MutexLock lock(&mutex_);
+  if (apm_) {
+    webrtc::StreamConfig config(sample_rate, number_of_channels);
+    capture_buf_.resize(audio_data.size());
+    if (apm_->ProcessStream(audio_data.data(), config, config,
+                            capture_buf_.data()) ==
+        webrtc::AudioProcessing::kNoError) {
+      source_->on_captured_frame(
+          rust::Slice<const int16_t>(capture_buf_.data(), capture_buf_.size()),
+          sample_rate, number_of_channels, number_of_frames);
+      return;
+    }
+
+    RTC_LOG(LS_WARNING) << "Failed to process the captured audio, "
+                           "forwarding it unprocessed";
+  }
+
   source_->on_captured_frame(audio_data, sample_rate, number_of_channels,
                              number_of_frames);
 }
 
+void AudioTrackSource::on_reverse_frame(rust::Slice<const int16_t> audio_data,
+                                        int sample_rate,
+                                        size_t number_of_channels,
+                                        size_t number_of_frames) const {
+  webrtc::MutexLock lock(&mutex_);
+  if (!apm_ || !options_.echo_cancellation)
+    return;
+
+  webrtc::StreamConfig config(sample_rate, number_of_channels);
+  reverse_buf_.resize(audio_data.size());
+  if (apm_->ProcessReverseStream(audio_data.data(), config, config,
+                                 reverse_buf_.data()) !=
+      webrtc::AudioProcessing::kNoError) {
+    RTC_LOG(LS_WARNING) << "Failed to process the reverse audio stream";
+  }
+}
+
+bool AudioTrackSource::on_encoded_frame(rust::Slice<const uint8_t> data,
+                                        EncodedAudioFrameInfo info) const {
+  if (info.sample_rate == 0)
+    return false;
+
+  webrtc::MutexLock lock(&mutex_);
+  // A carrier lasts 10ms, the packets that aren't a multiple of 10ms are
+  // completed by the next carrier and the difference is deducted from the
+  // next packet, so the stream doesn't drift
+  int64_t duration_us = static_cast<int64_t>(info.samples_per_channel) *
+                        rtc::kNumMicrosecsPerSec / info.sample_rate;
+  if (duration_us < 10000) {
+    RTC_LOG(LS_ERROR) << "Encoded audio frames must be at least 10ms long";
+    return false;
+  }
+
+  int64_t pending_us = duration_us - encoded_advance_us_;
+  size_t count = (pending_us + 9999) / 10000;
+  auto carriers = EncodedAudioCarrier::split(
+      rtc::ArrayView<const uint8_t>(data.data(), data.size()), count);
+  if (carriers.empty()) {
+    RTC_LOG(LS_ERROR) << "Encoded audio frame too large (" << data.size()
+                      << " bytes)";
+    return false;
+  }
+
+  encoded_advance_us_ = count * 10000 - pending_us;
+  for (const auto& carrier : carriers) {
+    source_->on_captured_frame(
+        rust::Slice<const int16_t>(carrier.data(), carrier.size()),
+        EncodedAudioCarrier::kSampleRate, 1, carrier.size());
+  }
+  return true;
+}
+
 rtc::scoped_refptr<AudioTrackSource::InternalSource> AudioTrackSource::get()
     const {
   return source_;
 }
 
-std::shared_ptr<AudioTrackSource> new_audio_track_source() {
-  return std::make_shared<AudioTrackSource>();
+std::shared_ptr<AudioTrackSource> new_audio_track_source(
+    AudioSourceOptions options) {
+  return std::make_shared<AudioTrackSource>(options);
 }
 
 }  // namespace livekit
@@ -3,6 +3,24 @@ use std::sync::Arc;
 
 #[cxx::bridge(namespace = "livekit")]
 pub mod ffi {
+    #[derive(Debug, Clone)]
+    pub struct AudioSourceOptions {
+        pub echo_cancellation: bool,
+        pub noise_suppression: bool,
+        pub auto_gain_control: bool,
+    }
+
+    #[derive(Debug)]
+    pub struct AudioDataTiming {
+        pub absolute_capture_timestamp_ms: i64, // -1 if unknown
+    }
+
+    #[derive(Debug)]
+    pub struct EncodedAudioFrameInfo {
+        pub sample_rate: u32,
+        pub samples_per_channel: u32,
+    }
+
     extern "C++" {
         include!("livekit/media_stream_track.h");
 
@@ -27,7 +45,21 @@ pub mod ffi {
             nb_channels: usize,
             nb_frames: usize,
         );
-        fn new_audio_track_source() -> SharedPtr<AudioTrackSource>;
+        fn on_reverse_frame(
+            self: &AudioTrackSource,
+            data: &[i16],
+            sample_rate: i32,
+            nb_channels: usize,
+            nb_frames: usize,
+        );
+        fn audio_options(self: &AudioTrackSource) -> AudioSourceOptions;
+        fn set_audio_options(self: &AudioTrackSource, options: &AudioSourceOptions);
+        fn on_encoded_frame(
+            self: &AudioTrackSource,
+            data: &[u8],
+            info: EncodedAudioFrameInfo,
+        ) -> bool;
+        fn new_audio_track_source(options: AudioSourceOptions) -> SharedPtr<AudioTrackSource>;
 
         fn audio_to_media(track: SharedPtr<AudioTrack>) -> SharedPtr<MediaStreamTrack>;
         unsafe fn media_to_audio(track: SharedPtr<MediaStreamTrack>) -> SharedPtr<AudioTrack>;
@@ -43,6 +75,7 @@ pub mod ffi {
             sample_rate: i32,
             nb_channels: usize,
             nb_frames: usize,
+            timing: AudioDataTiming,
         );
     }
 }
@@ -52,7 +85,14 @@ impl_thread_safety!(ffi::NativeAudioSink, Send + Sync);
 impl_thread_safety!(ffi::AudioTrackSource, Send + Sync);
 
 pub trait AudioSink: Send {
-    fn on_data(&self, data: &[i16], sample_rate: i32, nb_channels: usize, nb_frames: usize);
+    fn on_data(
+        &self,
+        data: &[i16],
+        sample_rate: i32,
+        nb_channels: usize,
+        nb_frames: usize,
+        timing: ffi::AudioDataTiming,
+    );
 }
 
 pub struct AudioSinkWrapper {
@@ -64,8 +104,15 @@ impl AudioSinkWrapper {
         Self { observer }
     }
 
-    fn on_data(&self, data: &[i16], sample_rate: i32, nb_channels: usize, nb_frames: usize) {
+    fn on_data(
+        &self,
+        data: &[i16],
+        sample_rate: i32,
+        nb_channels: usize,
+        nb_frames: usize,
+        timing: ffi::AudioDataTiming,
+    ) {
         self.observer
-            .on_data(data, sample_rate, nb_channels, nb_frames);
+            .on_data(data, sample_rate, nb_channels, nb_frames, timing);
     }
 }
@@ -16,7 +16,61 @@
 
 #include "livekit/candidate.h"
 
+#include "p2p/base/p2p_constants.h"
+#include "p2p/base/port.h"
+
 namespace livekit {
+
+// mDNS candidates only have a hostname
+static rust::String candidate_address(const rtc::SocketAddress& address) {
+  if (address.IsUnresolvedIP())
+    return address.hostname();
+
+  return address.ipaddr().ToString();
+}
+
+static CandidateType candidate_type(const std::string& type) {
+  if (type == cricket::LOCAL_PORT_TYPE)
+    return CandidateType::Host;
+  if (type == cricket::STUN_PORT_TYPE)
+    return CandidateType::Srflx;
+  if (type == cricket::PRFLX_PORT_TYPE)
+    return CandidateType::Prflx;
+  if (type == cricket::RELAY_PORT_TYPE)
+    return CandidateType::Relay;
+
+  return CandidateType::Unknown;
+}
+
+static TcpType tcp_type(const std::string& tcptype) {
+  if (tcptype == cricket::TCPTYPE_ACTIVE_STR)
+    return TcpType::Active;
+  if (tcptype == cricket::TCPTYPE_PASSIVE_STR)
+    return TcpType::Passive;
+  if (tcptype == cricket::TCPTYPE_SIMOPEN_STR)
+    return TcpType::SimultaneousOpen;
+
+  return TcpType::None;
+}
+
 Candidate::Candidate(const cricket::Candidate& candidate)
     : candidate_(candidate) {}
+
+CandidateInfo Candidate::info() const {
+  CandidateInfo info{};
+  info.foundation = candidate_.foundation();
+  info.component = candidate_.component();
+  info.protocol = candidate_.protocol();
+  info.priority = candidate_.priority();
+  info.address = candidate_address(candidate_.address());
+  info.port = candidate_.address().port();
+  info.candidate_type = candidate_type(candidate_.type());
+  info.has_related_address = !candidate_.related_address().IsNil();
+  if (info.has_related_address) {
+    info.related_address = candidate_address(candidate_.related_address());
+    info.related_port = candidate_.related_address().port();
+  }
+  info.tcp_type = tcp_type(candidate_.tcptype());
+  return info;
+}
 }  // namespace livekit
@@ -1,10 +1,50 @@
+use crate::impl_thread_safety;
+
 #[cxx::bridge(namespace = "livekit")]
 pub mod ffi {
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum CandidateType {
+        Host,
+        Srflx,
+        Prflx,
+        Relay,
+        Unknown,
+    }
+
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum TcpType {
+        None,
+        Active,
+        Passive,
+        SimultaneousOpen,
+    }
+
+    #[derive(Debug)]
+    pub struct CandidateInfo {
+        pub foundation: String,
+        pub component: i32,
+        pub protocol: String,
+        pub priority: u32,
+        pub address: String,
+        pub port: u16,
+        pub candidate_type: CandidateType,
+        pub has_related_address: bool,
+        pub related_address: String,
+        pub related_port: u16,
+        pub tcp_type: TcpType,
+    }
+
     unsafe extern "C++" {
         include!("livekit/candidate.h");
 
         type Candidate; // cricket::Candidate
 
+        fn info(self: &Candidate) -> CandidateInfo;
+
         fn _shared_candidate() -> SharedPtr<Candidate>;
     }
 }
+
+impl_thread_safety!(ffi::Candidate, Send + Sync);
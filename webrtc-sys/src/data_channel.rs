@@ -53,6 +53,7 @@ pub mod ffi {
         fn send(self: &DataChannel, data: &DataBuffer) -> bool;
         fn label(self: &DataChannel) -> String;
         fn state(self: &DataChannel) -> DataState;
+        fn buffered_amount(self: &DataChannel) -> u64;
         fn close(self: &DataChannel);
 
         fn _shared_data_channel() -> SharedPtr<DataChannel>; // Ignore
@@ -0,0 +1,128 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/encoded_stream.h"
+
+#include <memory>
+#include <utility>
+
+#include "api/frame_transformer_interface.h"
+#include "rtc_base/logging.h"
+
+namespace livekit {
+
+EncodedFrameTransformer::EncodedFrameTransformer(
+    webrtc::MediaType media_type,
+    rust::Box<EncodedSinkWrapper> observer)
+    : media_type_(media_type), observer_(std::move(observer)) {}
+
+void EncodedFrameTransformer::Transform(
+    std::unique_ptr<webrtc::TransformableFrameInterface> frame) {
+  rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback;
+  bool closed;
+  {
+    webrtc::MutexLock lock(&mutex_);
+    closed = closed_;
+    callback = callback_;
+    auto it = sink_callbacks_.find(frame->GetSsrc());
+    if (it != sink_callbacks_.end())
+      callback = it->second;
+  }
+
+  if (!closed) {
+    EncodedFrameInfo info{};
+    info.payload_type = frame->GetPayloadType();
+    info.rtp_timestamp = frame->GetTimestamp();
+    info.ssrc = frame->GetSsrc();
+
+    if (media_type_ == webrtc::MediaType::VIDEO) {
+      auto video_frame =
+          static_cast<webrtc::TransformableVideoFrameInterface*>(frame.get());
+      auto metadata = video_frame->GetMetadata();
+      info.keyframe = video_frame->IsKeyFrame();
+      info.width = metadata.GetWidth();
+      info.height = metadata.GetHeight();
+    }
+
+    // The sink receives a copy, the frame still goes to the decoder
+    auto data = frame->GetData();
+    observer_->on_encoded_frame(
+        rust::Slice<const uint8_t>(data.data(), data.size()), info);
+  }
+
+  if (callback)
+    callback->OnTransformedFrame(std::move(frame));
+}
+
+void EncodedFrameTransformer::RegisterTransformedFrameCallback(
+    rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = callback;
+}
+
+void EncodedFrameTransformer::RegisterTransformedFrameSinkCallback(
+    rtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_[ssrc] = callback;
+}
+
+void EncodedFrameTransformer::UnregisterTransformedFrameCallback() {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = nullptr;
+}
+
+void EncodedFrameTransformer::UnregisterTransformedFrameSinkCallback(
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_.erase(ssrc);
+}
+
+void EncodedFrameTransformer::close() {
+  webrtc::MutexLock lock(&mutex_);
+  closed_ = true;
+}
+
+NativeEncodedSink::NativeEncodedSink(const RtpReceiver& receiver,
+                                     rust::Box<EncodedSinkWrapper> observer) {
+  auto rtc_receiver = receiver.rtc_receiver();
+  auto media_type = rtc_receiver->media_type() == cricket::MEDIA_TYPE_VIDEO
+                        ? webrtc::MediaType::VIDEO
+                        : webrtc::MediaType::AUDIO;
+
+  transformer_ = rtc::make_ref_counted<EncodedFrameTransformer>(
+      media_type, std::move(observer));
+
+  // There is no way to remove a transformer from a receiver, it stays
+  // installed and stops copying the frames once closed
+  rtc_receiver->SetDepacketizerToDecoderFrameTransformer(transformer_);
+}
+
+NativeEncodedSink::~NativeEncodedSink() {
+  close();
+}
+
+void NativeEncodedSink::close() const {
+  transformer_->close();
+}
+
+std::shared_ptr<NativeEncodedSink> new_native_encoded_sink(
+    const RtpReceiver& receiver,
+    rust::Box<EncodedSinkWrapper> observer) {
+  return std::make_shared<NativeEncodedSink>(receiver, std::move(observer));
+}
+
+}  // namespace livekit
@@ -0,0 +1,60 @@
+use crate::impl_thread_safety;
+use std::sync::Arc;
+
+#[cxx::bridge(namespace = "livekit")]
+pub mod ffi {
+    #[derive(Debug)]
+    pub struct EncodedFrameInfo {
+        pub payload_type: u8,
+        pub rtp_timestamp: u32,
+        pub ssrc: u32,
+        pub keyframe: bool, // Always false for audio
+        pub width: u32,
+        pub height: u32,
+    }
+
+    extern "C++" {
+        include!("livekit/rtp_receiver.h");
+
+        type RtpReceiver = crate::rtp_receiver::ffi::RtpReceiver;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/encoded_stream.h");
+
+        type NativeEncodedSink;
+
+        fn close(self: &NativeEncodedSink);
+
+        fn new_native_encoded_sink(
+            receiver: &RtpReceiver,
+            observer: Box<EncodedSinkWrapper>,
+        ) -> SharedPtr<NativeEncodedSink>;
+    }
+
+    extern "Rust" {
+        type EncodedSinkWrapper;
+
+        fn on_encoded_frame(self: &EncodedSinkWrapper, data: &[u8], info: EncodedFrameInfo);
+    }
+}
+
+impl_thread_safety!(ffi::NativeEncodedSink, Send + Sync);
+
+pub trait EncodedSink: Send + Sync {
+    fn on_encoded_frame(&self, data: &[u8], info: ffi::EncodedFrameInfo);
+}
+
+pub struct EncodedSinkWrapper {
+    observer: Arc<dyn EncodedSink>,
+}
+
+impl EncodedSinkWrapper {
+    pub fn new(observer: Arc<dyn EncodedSink>) -> Self {
+        Self { observer }
+    }
+
+    fn on_encoded_frame(&self, data: &[u8], info: ffi::EncodedFrameInfo) {
+        self.observer.on_encoded_frame(data, info);
+    }
+}
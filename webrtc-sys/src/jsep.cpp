@@ -46,8 +46,8 @@ int IceCandidate::sdp_mline_index() const {
   return ice_candidate_->sdp_mline_index();
 }
 
-rust::String IceCandidate::candidate() const {
-  return stringify();
+std::shared_ptr<Candidate> IceCandidate::candidate() const {
+  return std::make_shared<Candidate>(ice_candidate_->candidate());
 }
 
 rust::String IceCandidate::stringify() const {
@@ -21,8 +21,10 @@ pub mod ffi {
 
     extern "C++" {
         include!("livekit/rtc_error.h");
+        include!("livekit/candidate.h");
 
         type RtcError = crate::rtc_error::ffi::RtcError;
+        type Candidate = crate::candidate::ffi::Candidate;
     }
 
     unsafe extern "C++" {
@@ -33,7 +35,7 @@ pub mod ffi {
 
         fn sdp_mid(self: &IceCandidate) -> String;
         fn sdp_mline_index(self: &IceCandidate) -> i32;
-        fn candidate(self: &IceCandidate) -> String;
+        fn candidate(self: &IceCandidate) -> SharedPtr<Candidate>;
         fn stringify(self: &IceCandidate) -> String;
 
         fn sdp_type(self: &SessionDescription) -> SdpType;
@@ -1,7 +1,9 @@
+pub mod audio_device;
 pub mod audio_resampler;
 pub mod audio_track;
 pub mod candidate;
 pub mod data_channel;
+pub mod encoded_stream;
 pub mod helper;
 pub mod jsep;
 pub mod media_stream;
@@ -13,6 +15,7 @@ pub mod rtp_parameters;
 pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
+pub mod video_codec;
 pub mod video_frame;
 pub mod video_frame_buffer;
 pub mod video_track;
@@ -0,0 +1,467 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/passthrough_codec.h"
+
+#include <algorithm>
+#include <memory>
+#include <set>
+#include <utility>
+
+#include "api/audio_codecs/builtin_audio_encoder_factory.h"
+#include "api/video/i420_buffer.h"
+#include "livekit/video_codec.h"
+#include "modules/video_coding/include/video_error_codes.h"
+#include "rtc_base/logging.h"
+#include "rtc_base/synchronization/mutex.h"
+
+namespace livekit {
+
+namespace {
+webrtc::Mutex g_buffers_mutex;
+std::set<const webrtc::VideoFrameBuffer*> g_encoded_buffers;
+
+// Tag written at the start of each carrier frame
+constexpr int16_t kCarrierMagic0 = 0x4c4b;  // "LK"
+constexpr int16_t kCarrierMagic1 = 0x5054;  // "PT"
+}  // namespace
+
+EncodedVideoFrameBuffer::EncodedVideoFrameBuffer(
+    rtc::scoped_refptr<webrtc::VideoTrackSourceInterface> source,
+    uint64_t sequence,
+    webrtc::VideoCodecType codec_type,
+    const uint8_t* data,
+    size_t size,
+    int width,
+    int height,
+    bool keyframe)
+    : source_(std::move(source)),
+      sequence_(sequence),
+      codec_type_(codec_type),
+      data_(data, size),
+      width_(width),
+      height_(height),
+      keyframe_(keyframe) {
+  webrtc::MutexLock lock(&g_buffers_mutex);
+  g_encoded_buffers.insert(this);
+}
+
+EncodedVideoFrameBuffer::~EncodedVideoFrameBuffer() {
+  webrtc::MutexLock lock(&g_buffers_mutex);
+  g_encoded_buffers.erase(this);
+}
+
+webrtc::VideoFrameBuffer::Type EncodedVideoFrameBuffer::type() const {
+  return Type::kNative;
+}
+
+int EncodedVideoFrameBuffer::width() const {
+  return width_;
+}
+
+int EncodedVideoFrameBuffer::height() const {
+  return height_;
+}
+
+rtc::scoped_refptr<webrtc::I420BufferInterface>
+EncodedVideoFrameBuffer::ToI420() {
+  // Only reached when the frame isn't consumed by a passthrough encoder (e.g
+  // the negotiated codec doesn't match), it can't be decoded here
+  RTC_LOG(LS_ERROR) << "Encoded frame converted to I420, sending a black "
+                       "frame instead";
+  auto buffer = webrtc::I420Buffer::Create(width_, height_);
+  webrtc::I420Buffer::SetBlack(buffer.get());
+  return buffer;
+}
+
+rtc::scoped_refptr<webrtc::VideoFrameBuffer>
+EncodedVideoFrameBuffer::CropAndScale(int offset_x,
+                                      int offset_y,
+                                      int crop_width,
+                                      int crop_height,
+                                      int scaled_width,
+                                      int scaled_height) {
+  return rtc::scoped_refptr<webrtc::VideoFrameBuffer>(this);
+}
+
+void EncodedVideoFrameBuffer::request_keyframe() const {
+  source_->GenerateKeyFrame();
+}
+
+const EncodedVideoFrameBuffer* EncodedVideoFrameBuffer::from(
+    const webrtc::VideoFrameBuffer* buffer) {
+  if (buffer->type() != Type::kNative)
+    return nullptr;
+
+  webrtc::MutexLock lock(&g_buffers_mutex);
+  if (g_encoded_buffers.find(buffer) == g_encoded_buffers.end())
+    return nullptr;
+
+  return static_cast<const EncodedVideoFrameBuffer*>(buffer);
+}
+
+PassthroughVideoEncoder::PassthroughVideoEncoder(
+    webrtc::VideoCodecType codec_type,
+    std::unique_ptr<webrtc::VideoEncoder> encoder)
+    : codec_type_(codec_type), encoder_(std::move(encoder)) {}
+
+void PassthroughVideoEncoder::SetFecControllerOverride(
+    webrtc::FecControllerOverride* fec_controller_override) {
+  encoder_->SetFecControllerOverride(fec_controller_override);
+}
+
+int32_t PassthroughVideoEncoder::InitEncode(
+    const webrtc::VideoCodec* codec_settings,
+    const webrtc::VideoEncoder::Settings& settings) {
+  native_input_ = codec_settings->expect_encode_from_texture;
+  return encoder_->InitEncode(codec_settings, settings);
+}
+
+int32_t PassthroughVideoEncoder::RegisterEncodeCompleteCallback(
+    webrtc::EncodedImageCallback* callback) {
+  callback_ = callback;
+  return encoder_->RegisterEncodeCompleteCallback(callback);
+}
+
+int32_t PassthroughVideoEncoder::Release() {
+  last_sequence_ = absl::nullopt;
+  return encoder_->Release();
+}
+
+int32_t PassthroughVideoEncoder::Encode(
+    const webrtc::VideoFrame& frame,
+    const std::vector<webrtc::VideoFrameType>* frame_types) {
+  auto buffer = frame.video_frame_buffer();
+  if (!native_input_)
+    return encoder_->Encode(frame, frame_types);
+
+  auto encoded = EncodedVideoFrameBuffer::from(buffer.get());
+  if (encoded)
+    return encode_passthrough(frame, encoded, frame_types);
+
+  if (buffer->type() == webrtc::VideoFrameBuffer::Type::kNative &&
+      !encoder_->GetEncoderInfo().supports_native_handle) {
+    // We advertise the support of native handles for the native streams, do
+    // the conversion ourselves for the encoders that don't
+    webrtc::VideoFrame i420_frame(frame);
+    i420_frame.set_video_frame_buffer(buffer->ToI420());
+    return encoder_->Encode(i420_frame, frame_types);
+  }
+
+  return encoder_->Encode(frame, frame_types);
+}
+
+int32_t PassthroughVideoEncoder::encode_passthrough(
+    const webrtc::VideoFrame& frame,
+    const EncodedVideoFrameBuffer* encoded,
+    const std::vector<webrtc::VideoFrameType>* frame_types) {
+  if (!callback_)
+    return WEBRTC_VIDEO_CODEC_UNINITIALIZED;
+
+  passthrough_ = true;
+  if (encoded->codec_type() != codec_type_) {
+    RTC_LOG(LS_ERROR) << "Encoded frame codec doesn't match the negotiated "
+                         "codec, dropping the frame";
+    return WEBRTC_VIDEO_CODEC_ERROR;
+  }
+
+  bool keyframe_requested =
+      frame_types &&
+      std::find(frame_types->begin(), frame_types->end(),
+                webrtc::VideoFrameType::kVideoFrameKey) != frame_types->end();
+
+  // The frames skipped by the pipeline (e.g dropped by the encoder queue)
+  // break the decoding of the next delta frames
+  bool frames_skipped =
+      last_sequence_ && encoded->sequence() != *last_sequence_ + 1;
+  last_sequence_ = encoded->sequence();
+
+  if (!encoded->keyframe() && (keyframe_requested || frames_skipped))
+    encoded->request_keyframe();
+
+  EncodedImageInfo info{};
+  info.rtp_timestamp = frame.timestamp();
+  info.capture_time_ms = frame.render_time_ms();
+  info.keyframe = encoded->keyframe();
+  info.width = encoded->width();
+  info.height = encoded->height();
+
+  auto data = encoded->data();
+  EncodedImageSink sink(callback_, codec_type_);
+  if (!sink.on_encoded_image(
+          rust::Slice<const uint8_t>(data.data(), data.size()), info))
+    return WEBRTC_VIDEO_CODEC_ERROR;
+
+  return WEBRTC_VIDEO_CODEC_OK;
+}
+
+void PassthroughVideoEncoder::SetRates(
+    const RateControlParameters& parameters) {
+  encoder_->SetRates(parameters);
+}
+
+void PassthroughVideoEncoder::OnPacketLossRateUpdate(float packet_loss_rate) {
+  encoder_->OnPacketLossRateUpdate(packet_loss_rate);
+}
+
+void PassthroughVideoEncoder::OnRttUpdate(int64_t rtt_ms) {
+  encoder_->OnRttUpdate(rtt_ms);
+}
+
+void PassthroughVideoEncoder::OnLossNotification(
+    const LossNotification& loss_notification) {
+  encoder_->OnLossNotification(loss_notification);
+}
+
+webrtc::VideoEncoder::EncoderInfo PassthroughVideoEncoder::GetEncoderInfo()
+    const {
+  EncoderInfo info = encoder_->GetEncoderInfo();
+  if (native_input_) {
+    // Needed to receive the EncodedVideoFrameBuffer untouched
+    info.supports_native_handle = true;
+  }
+
+  if (passthrough_) {
+    // The bitrate of the encoded frames isn't ours to control, don't let the
+    // frame dropper skip them
+    info.has_trusted_rate_controller = true;
+  }
+  return info;
+}
+
+std::vector<std::vector<int16_t>> EncodedAudioCarrier::split(
+    rtc::ArrayView<const uint8_t> packet,
+    size_t count) {
+  if (count == 0 || packet.size() > count * kMaxChunkSize)
+    return {};
+
+  size_t chunk_size = (packet.size() + count - 1) / count;
+  std::vector<std::vector<int16_t>> carriers;
+  for (size_t i = 0; i < count; i++) {
+    size_t offset = std::min(i * chunk_size, packet.size());
+    size_t size = std::min(chunk_size, packet.size() - offset);
+
+    std::vector<int16_t> carrier(kFrames, 0);
+    carrier[0] = kCarrierMagic0;
+    carrier[1] = kCarrierMagic1;
+    carrier[2] = static_cast<int16_t>(i);
+    carrier[3] = static_cast<int16_t>(count);
+    carrier[4] = static_cast<int16_t>(size);
+    for (size_t j = 0; j < size; j++) {
+      // Two bytes per sample
+      uint16_t sample = static_cast<uint16_t>(carrier[kHeaderSize + j / 2]);
+      uint16_t byte = packet[offset + j];
+      sample |= static_cast<uint16_t>(j % 2 == 0 ? byte << 8 : byte);
+      carrier[kHeaderSize + j / 2] = static_cast<int16_t>(sample);
+    }
+    carriers.push_back(std::move(carrier));
+  }
+  return carriers;
+}
+
+bool EncodedAudioCarrier::parse(rtc::ArrayView<const int16_t> audio,
+                                size_t num_channels,
+                                size_t* index,
+                                size_t* count,
+                                rtc::Buffer* packet) {
+  // Interleaved, the mono carriers are upmixed by copying the samples
+  if (num_channels == 0 || audio.size() != kFrames * num_channels)
+    return false;
+
+  auto sample = [&](size_t i) {
+    return static_cast<uint16_t>(audio[i * num_channels]);
+  };
+  if (sample(0) != static_cast<uint16_t>(kCarrierMagic0) ||
+      sample(1) != static_cast<uint16_t>(kCarrierMagic1))
+    return false;
+
+  *index = sample(2);
+  *count = sample(3);
+  size_t size = sample(4);
+  if (size > kMaxChunkSize || *index >= *count)
+    return false;
+
+  for (size_t j = 0; j < size; j++) {
+    uint16_t value = sample(kHeaderSize + j / 2);
+    uint8_t byte = j % 2 == 0 ? value >> 8 : value & 0xff;
+    packet->AppendData(&byte, 1);
+  }
+  return true;
+}
+
+PassthroughAudioEncoder::PassthroughAudioEncoder(
+    int payload_type,
+    std::unique_ptr<webrtc::AudioEncoder> encoder)
+    : payload_type_(payload_type), encoder_(std::move(encoder)) {}
+
+int PassthroughAudioEncoder::SampleRateHz() const {
+  return encoder_->SampleRateHz();
+}
+
+size_t PassthroughAudioEncoder::NumChannels() const {
+  return encoder_->NumChannels();
+}
+
+int PassthroughAudioEncoder::RtpTimestampRateHz() const {
+  return encoder_->RtpTimestampRateHz();
+}
+
+size_t PassthroughAudioEncoder::Num10MsFramesInNextPacket() const {
+  return encoder_->Num10MsFramesInNextPacket();
+}
+
+size_t PassthroughAudioEncoder::Max10MsFramesInAPacket() const {
+  return encoder_->Max10MsFramesInAPacket();
+}
+
+int PassthroughAudioEncoder::GetTargetBitrate() const {
+  return encoder_->GetTargetBitrate();
+}
+
+void PassthroughAudioEncoder::Reset() {
+  encoder_->Reset();
+}
+
+bool PassthroughAudioEncoder::SetFec(bool enable) {
+  return encoder_->SetFec(enable);
+}
+
+bool PassthroughAudioEncoder::SetDtx(bool enable) {
+  return encoder_->SetDtx(enable);
+}
+
+bool PassthroughAudioEncoder::GetDtx() const {
+  return encoder_->GetDtx();
+}
+
+bool PassthroughAudioEncoder::SetApplication(Application application) {
+  return encoder_->SetApplication(application);
+}
+
+void PassthroughAudioEncoder::SetMaxPlaybackRate(int frequency_hz) {
+  encoder_->SetMaxPlaybackRate(frequency_hz);
+}
+
+void PassthroughAudioEncoder::OnReceivedUplinkPacketLossFraction(
+    float uplink_packet_loss_fraction) {
+  encoder_->OnReceivedUplinkPacketLossFraction(uplink_packet_loss_fraction);
+}
+
+void PassthroughAudioEncoder::OnReceivedTargetAudioBitrate(int target_bps) {
+  encoder_->OnReceivedTargetAudioBitrate(target_bps);
+}
+
+void PassthroughAudioEncoder::OnReceivedUplinkBandwidth(
+    int target_audio_bitrate_bps,
+    absl::optional<int64_t> bwe_period_ms) {
+  encoder_->OnReceivedUplinkBandwidth(target_audio_bitrate_bps, bwe_period_ms);
+}
+
+void PassthroughAudioEncoder::OnReceivedUplinkAllocation(
+    webrtc::BitrateAllocationUpdate update) {
+  encoder_->OnReceivedUplinkAllocation(update);
+}
+
+void PassthroughAudioEncoder::OnReceivedRtt(int rtt_ms) {
+  encoder_->OnReceivedRtt(rtt_ms);
+}
+
+void PassthroughAudioEncoder::OnReceivedOverhead(
+    size_t overhead_bytes_per_packet) {
+  encoder_->OnReceivedOverhead(overhead_bytes_per_packet);
+}
+
+void PassthroughAudioEncoder::SetReceiverFrameLengthRange(
+    int min_frame_length_ms,
+    int max_frame_length_ms) {
+  encoder_->SetReceiverFrameLengthRange(min_frame_length_ms,
+                                        max_frame_length_ms);
+}
+
+absl::optional<std::pair<webrtc::TimeDelta, webrtc::TimeDelta>>
+PassthroughAudioEncoder::GetFrameLengthRange() const {
+  return encoder_->GetFrameLengthRange();
+}
+
+webrtc::AudioEncoder::EncodedInfo PassthroughAudioEncoder::EncodeImpl(
+    uint32_t rtp_timestamp,
+    rtc::ArrayView<const int16_t> audio,
+    rtc::Buffer* encoded) {
+  size_t index, count;
+  rtc::Buffer chunk;
+  if (!EncodedAudioCarrier::parse(audio, NumChannels(), &index, &count,
+                                  &chunk))
+    return encoder_->Encode(rtp_timestamp, audio, encoded);
+
+  EncodedInfo info;
+  if (index == 0) {
+    packet_.Clear();
+    next_index_ = 0;
+    first_timestamp_ = rtp_timestamp;
+  }
+
+  if (index != next_index_) {
+    RTC_LOG(LS_WARNING) << "Missing encoded audio carrier, dropping the packet";
+    next_index_ = 0;
+    return info;
+  }
+
+  packet_.AppendData(chunk);
+  next_index_++;
+  if (next_index_ != count)
+    return info;  // Wait for the last carrier of the packet
+
+  encoded->AppendData(packet_);
+  info.encoded_bytes = packet_.size();
+  packet_.Clear();
+  next_index_ = 0;
+
+  info.encoded_timestamp = first_timestamp_;
+  info.payload_type = payload_type_;
+  info.send_even_if_empty = false;
+  info.speech = true;
+  info.encoder_type = webrtc::CodecType::kOther;
+  return info;
+}
+
+AudioEncoderFactory::AudioEncoderFactory()
+    : builtin_factory_(webrtc::CreateBuiltinAudioEncoderFactory()) {}
+
+std::vector<webrtc::AudioCodecSpec>
+AudioEncoderFactory::GetSupportedEncoders() {
+  return builtin_factory_->GetSupportedEncoders();
+}
+
+absl::optional<webrtc::AudioCodecInfo> AudioEncoderFactory::QueryAudioEncoder(
+    const webrtc::SdpAudioFormat& format) {
+  return builtin_factory_->QueryAudioEncoder(format);
+}
+
+std::unique_ptr<webrtc::AudioEncoder> AudioEncoderFactory::MakeAudioEncoder(
+    int payload_type,
+    const webrtc::SdpAudioFormat& format,
+    absl::optional<webrtc::AudioCodecPairId> codec_pair_id) {
+  auto encoder =
+      builtin_factory_->MakeAudioEncoder(payload_type, format, codec_pair_id);
+  if (!encoder)
+    return nullptr;
+
+  // Transparent unless the track comes from an encoded source
+  return std::make_unique<PassthroughAudioEncoder>(payload_type,
+                                                   std::move(encoder));
+}
+
+}  // namespace livekit
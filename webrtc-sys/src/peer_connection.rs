@@ -12,15 +12,15 @@ use std::sync::Arc;
 #[cxx::bridge(namespace = "livekit")]
 pub mod ffi {
     pub struct CandidatePair {
-        local: SharedPtr<Candidate>,
-        remote: SharedPtr<Candidate>,
+        pub local: SharedPtr<Candidate>,
+        pub remote: SharedPtr<Candidate>,
     }
 
     pub struct CandidatePairChangeEvent {
-        selected_candidate_pair: CandidatePair,
-        last_data_received_ms: i64,
-        reason: String,
-        estimated_disconnected_time_ms: i64,
+        pub selected_candidate_pair: CandidatePair,
+        pub last_data_received_ms: i64,
+        pub reason: String,
+        pub estimated_disconnected_time_ms: i64,
     }
 
     #[repr(i32)]
@@ -16,17 +16,20 @@
 
 #include "livekit/peer_connection_factory.h"
 
+#include <map>
 #include <memory>
+#include <string>
 #include <utility>
 
 #include "api/audio_codecs/builtin_audio_decoder_factory.h"
-#include "api/audio_codecs/builtin_audio_encoder_factory.h"
 #include "api/peer_connection_interface.h"
 #include "api/rtc_event_log/rtc_event_log_factory.h"
 #include "api/task_queue/default_task_queue_factory.h"
 #include "api/video_codecs/builtin_video_decoder_factory.h"
 #include "api/video_codecs/builtin_video_encoder_factory.h"
+#include "api/webrtc_key_value_config.h"
 #include "livekit/audio_device.h"
+#include "livekit/passthrough_codec.h"
 #include "livekit/peer_connection.h"
 #include "livekit/rtc_error.h"
 #include "livekit/rtp_parameters.h"
@@ -36,11 +39,47 @@
 #include "media/engine/webrtc_media_engine.h"
 #include "rtc_base/location.h"
 #include "rtc_base/thread.h"
+#include "system_wrappers/include/field_trial.h"
 #include "webrtc-sys/src/peer_connection.rs.h"
 #include "webrtc-sys/src/peer_connection_factory.rs.h"
 
 namespace livekit {
 
+// Field trials scoped to a factory, the keys not found fallback to the global
+// field trials string. Implements the interface directly since Lookup can't be
+// overridden from FieldTrialBasedConfig in newer libwebrtc versions
+class FieldTrials : public webrtc::WebRtcKeyValueConfig {
+ public:
+  explicit FieldTrials(const std::string& trials) {
+    // Format: "Name/Value/Name2/Value2/"
+    size_t pos = 0;
+    while (pos < trials.size()) {
+      size_t name_end = trials.find('/', pos);
+      if (name_end == std::string::npos)
+        break;
+
+      size_t value_end = trials.find('/', name_end + 1);
+      if (value_end == std::string::npos)
+        break;
+
+      trials_[trials.substr(pos, name_end - pos)] =
+          trials.substr(name_end + 1, value_end - name_end - 1);
+      pos = value_end + 1;
+    }
+  }
+
+  std::string Lookup(absl::string_view key) const override {
+    auto it = trials_.find(std::string(key));
+    if (it != trials_.end())
+      return it->second;
+
+    return webrtc::field_trial::FindFullName(std::string(key));
+  }
+
+ private:
+  std::map<std::string, std::string> trials_;
+};
+
 webrtc::PeerConnectionInterface::RTCConfiguration to_native_rtc_configuration(
     RtcConfiguration config) {
   webrtc::PeerConnectionInterface::RTCConfiguration rtc_config{};
@@ -64,12 +103,26 @@ webrtc::PeerConnectionInterface::RTCConfiguration to_native_rtc_configuration(
       static_cast<webrtc::PeerConnectionInterface::IceTransportsType>(
           config.ice_transport_type);
 
+  if (config.has_port_range) {
+    rtc_config.port_allocator_config.min_port = config.min_port;
+    rtc_config.port_allocator_config.max_port = config.max_port;
+  }
+
+  rtc_config.disable_ipv6 = config.disable_ipv6;
+  rtc_config.disable_link_local_networks = config.disable_link_local_networks;
+
+  if (config.disable_tcp)
+    rtc_config.tcp_candidate_policy =
+        webrtc::PeerConnectionInterface::kTcpCandidatePolicyDisabled;
+
   return rtc_config;
 }
 
 PeerConnectionFactory::PeerConnectionFactory(
-    std::shared_ptr<RtcRuntime> rtc_runtime)
-    : rtc_runtime_(rtc_runtime) {
+    std::shared_ptr<RtcRuntime> rtc_runtime,
+    PeerConnectionFactoryOptions options,
+    std::shared_ptr<AudioDeviceModule> audio_device)
+    : rtc_runtime_(rtc_runtime), injected_audio_device_(audio_device) {
   RTC_LOG(LS_VERBOSE) << "PeerConnectionFactory::PeerConnectionFactory()";
 
   webrtc::PeerConnectionFactoryDependencies dependencies;
@@ -81,25 +134,35 @@ PeerConnectionFactory::PeerConnectionFactory(
   dependencies.event_log_factory = std::make_unique<webrtc::RtcEventLogFactory>(
       dependencies.task_queue_factory.get());
   dependencies.call_factory = webrtc::CreateCallFactory();
-  dependencies.trials = std::make_unique<webrtc::FieldTrialBasedConfig>();
+  dependencies.trials =
+      std::make_unique<FieldTrials>(std::string(options.field_trials));
 
   cricket::MediaEngineDependencies media_deps;
   media_deps.task_queue_factory = dependencies.task_queue_factory.get();
 
-  audio_device_ = rtc_runtime_->worker_thread()
-                      ->Invoke<rtc::scoped_refptr<livekit::AudioDevice>>(
-                          RTC_FROM_HERE, [&] {
-                            return rtc::make_ref_counted<livekit::AudioDevice>(
-                                media_deps.task_queue_factory);
-                          });
+  if (injected_audio_device_) {
+    audio_device_ = injected_audio_device_->get();
+  } else {
+    audio_device_ =
+        rtc_runtime_->worker_thread()
+            ->Invoke<rtc::scoped_refptr<webrtc::AudioDeviceModule>>(
+                RTC_FROM_HERE, [&] {
+                  return rtc::make_ref_counted<livekit::AudioDevice>(
+                      media_deps.task_queue_factory);
+                });
+  }
 
   media_deps.adm = audio_device_;
 
-  media_deps.video_encoder_factory =
-      std::move(std::make_unique<livekit::VideoEncoderFactory>());
-  media_deps.video_decoder_factory =
-      std::move(std::make_unique<livekit::VideoDecoderFactory>());
-  media_deps.audio_encoder_factory = webrtc::CreateBuiltinAudioEncoderFactory();
+  auto video_encoder_factory = std::make_unique<livekit::VideoEncoderFactory>();
+  auto video_decoder_factory = std::make_unique<livekit::VideoDecoderFactory>();
+  video_encoder_factory_ = video_encoder_factory.get();
+  video_decoder_factory_ = video_decoder_factory.get();
+
+  media_deps.video_encoder_factory = std::move(video_encoder_factory);
+  media_deps.video_decoder_factory = std::move(video_decoder_factory);
+  media_deps.audio_encoder_factory =
+      rtc::make_ref_counted<livekit::AudioEncoderFactory>();
   media_deps.audio_decoder_factory = webrtc::CreateBuiltinAudioDecoderFactory();
   media_deps.audio_processing = webrtc::AudioProcessingBuilder().Create();
   media_deps.trials = dependencies.trials.get();
@@ -113,6 +176,11 @@ PeerConnectionFactory::PeerConnectionFactory(
     RTC_LOG_ERR(LS_ERROR) << "Failed to create PeerConnectionFactory";
     return;
   }
+
+  // Applied by the factory to the port allocator of every PeerConnection
+  webrtc::PeerConnectionFactoryInterface::Options factory_options;
+  factory_options.network_ignore_mask = options.network_ignore_mask;
+  peer_factory_->SetOptions(factory_options);
 }
 
 PeerConnectionFactory::~PeerConnectionFactory() {
@@ -128,6 +196,7 @@ std::shared_ptr<PeerConnection> PeerConnectionFactory::create_peer_connection(
     std::unique_ptr<NativePeerConnectionObserver> observer) const {
   observer->rtc_runtime_ = rtc_runtime_;  // See peer_connection.h
   webrtc::PeerConnectionDependencies deps{observer.get()};
+
   auto result = peer_factory_->CreatePeerConnectionOrError(
       to_native_rtc_configuration(config), std::move(deps));
 
@@ -168,7 +237,18 @@ RtpCapabilities PeerConnectionFactory::get_rtp_receiver_capabilities(
 }
 
 std::shared_ptr<PeerConnectionFactory> create_peer_connection_factory() {
-  return std::make_shared<PeerConnectionFactory>(RtcRuntime::create());
+  return create_peer_connection_factory_with_options(
+      PeerConnectionFactoryOptions{}, nullptr);
+}
+
+std::shared_ptr<PeerConnectionFactory>
+create_peer_connection_factory_with_options(
+    PeerConnectionFactoryOptions options,
+    std::shared_ptr<AudioDeviceModule> audio_device) {
+  std::shared_ptr<RtcRuntime> rtc_runtime =
+      RtcRuntime::create(options.network_threads, options.worker_threads);
+  return std::make_shared<PeerConnectionFactory>(
+      rtc_runtime, std::move(options), std::move(audio_device));
 }
 
 }  // namespace livekit
@@ -26,12 +26,31 @@ pub mod ffi {
         pub ice_servers: Vec<IceServer>,
         pub continual_gathering_policy: ContinualGatheringPolicy,
         pub ice_transport_type: IceTransportsType,
+        pub has_port_range: bool,
+        pub min_port: u16,
+        pub max_port: u16,
+        pub disable_ipv6: bool,
+        pub disable_link_local_networks: bool,
+        pub disable_tcp: bool,
+    }
+
+    pub struct PeerConnectionFactoryOptions {
+        /// e.g "WebRTC-Foo/Enabled/WebRTC-Bar/Disabled/"
+        pub field_trials: String,
+        /// 0 spawns dedicated threads, otherwise the threads are picked
+        /// round-robin from a pool of this size shared by all the factories
+        pub network_threads: u32,
+        pub worker_threads: u32,
+        /// rtc::AdapterType bits of the interfaces ignored by all the
+        /// PeerConnections of the factory
+        pub network_ignore_mask: i32,
     }
 
     extern "C++" {
         include!("livekit/media_stream.h");
         include!("livekit/webrtc.h");
         include!("livekit/rtp_parameters.h");
+        include!("livekit/audio_device.h");
 
         type AudioTrackSource = crate::audio_track::ffi::AudioTrackSource;
         type VideoTrackSource = crate::video_track::ffi::VideoTrackSource;
@@ -39,6 +58,7 @@ pub mod ffi {
         type VideoTrack = crate::video_track::ffi::VideoTrack;
         type RtpCapabilities = crate::rtp_parameters::ffi::RtpCapabilities;
         type MediaType = crate::webrtc::ffi::MediaType;
+        type AudioDeviceModule = crate::audio_device::ffi::AudioDeviceModule;
         type NativePeerConnectionObserver =
             crate::peer_connection::ffi::NativePeerConnectionObserver;
     }
@@ -50,6 +70,11 @@ pub mod ffi {
         type PeerConnectionFactory;
 
         fn create_peer_connection_factory() -> SharedPtr<PeerConnectionFactory>;
+        /// Uses a virtual audio device when audio_device is null
+        fn create_peer_connection_factory_with_options(
+            options: PeerConnectionFactoryOptions,
+            audio_device: SharedPtr<AudioDeviceModule>,
+        ) -> SharedPtr<PeerConnectionFactory>;
 
         fn create_peer_connection(
             self: &PeerConnectionFactory,
@@ -64,4 +64,22 @@ void RtpReceiver::set_jitter_buffer_minimum_delay(bool is_some,
       is_some ? absl::make_optional(delay_seconds) : absl::nullopt);
 }
 
+rust::Vec<RtpSource> RtpReceiver::get_sources() const {
+  rust::Vec<RtpSource> rust;
+  for (const webrtc::RtpSource& source : receiver_->GetSources()) {
+    RtpSource rtp_source{};
+    rtp_source.timestamp_ms = source.timestamp().ms();
+    rtp_source.source_id = source.source_id();
+    rtp_source.source_type =
+        source.source_type() == webrtc::RtpSourceType::SSRC
+            ? RtpSourceType::Ssrc
+            : RtpSourceType::Csrc;
+    rtp_source.rtp_timestamp = source.rtp_timestamp();
+    rtp_source.has_audio_level = source.audio_level().has_value();
+    rtp_source.audio_level = source.audio_level().value_or(0);
+    rust.push_back(rtp_source);
+  }
+  return rust;
+}
+
 }  // namespace livekit
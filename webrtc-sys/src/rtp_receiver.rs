@@ -2,6 +2,22 @@ use crate::impl_thread_safety;
 
 #[cxx::bridge(namespace = "livekit")]
 pub mod ffi {
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum RtpSourceType {
+        Ssrc,
+        Csrc,
+    }
+
+    #[derive(Debug)]
+    pub struct RtpSource {
+        pub timestamp_ms: i64,
+        pub source_id: u32,
+        pub source_type: RtpSourceType,
+        pub rtp_timestamp: u32,
+        pub has_audio_level: bool,
+        pub audio_level: u8,
+    }
 
     extern "C++" {
         include!("livekit/webrtc.h");
@@ -28,6 +44,7 @@ pub mod ffi {
         fn id(self: &RtpReceiver) -> String;
         fn get_parameters(self: &RtpReceiver) -> RtpParameters;
         fn set_jitter_buffer_minimum_delay(self: &RtpReceiver, is_some: bool, delay_seconds: f64);
+        fn get_sources(self: &RtpReceiver) -> Vec<RtpSource>;
 
         fn _shared_rtp_receiver() -> SharedPtr<RtpReceiver>;
     }
@@ -0,0 +1,299 @@
+/*
+ * Copyright 2023 LiveKit
+ *
+ * Licensed under the Apache License, Version 2.0 (the “License”);
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an “AS IS” BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/video_codec.h"
+
+#include <memory>
+#include <utility>
+
+#include "api/video/encoded_image.h"
+#include "api/video_codecs/sdp_video_format.h"
+#include "media/base/media_constants.h"
+#include "modules/video_coding/codecs/interface/common_constants.h"
+#include "modules/video_coding/include/video_codec_interface.h"
+#include "modules/video_coding/include/video_error_codes.h"
+#include "rtc_base/logging.h"
+
+namespace livekit {
+
+webrtc::SdpVideoFormat to_native_sdp_video_format(
+    const SdpVideoFormat& format) {
+  webrtc::SdpVideoFormat::Parameters parameters;
+  for (const auto& param : format.parameters)
+    parameters[std::string(param.key)] = std::string(param.value);
+
+  return webrtc::SdpVideoFormat(std::string(format.name), parameters);
+}
+
+SdpVideoFormat to_rust_sdp_video_format(const webrtc::SdpVideoFormat& format) {
+  SdpVideoFormat rust_format{};
+  rust_format.name = format.name;
+  for (const auto& [key, value] : format.parameters)
+    rust_format.parameters.push_back(SdpVideoFormatParameter{key, value});
+
+  return rust_format;
+}
+
+EncodedImageSink::EncodedImageSink(webrtc::EncodedImageCallback* callback,
+                                   webrtc::VideoCodecType codec_type)
+    : callback_(callback), codec_type_(codec_type) {}
+
+bool EncodedImageSink::on_encoded_image(rust::Slice<const uint8_t> data,
+                                        EncodedImageInfo info) const {
+  webrtc::EncodedImage image;
+  image.SetEncodedData(
+      webrtc::EncodedImageBuffer::Create(data.data(), data.size()));
+  image.SetTimestamp(info.rtp_timestamp);
+  image.capture_time_ms_ = info.capture_time_ms;
+  image._encodedWidth = info.width;
+  image._encodedHeight = info.height;
+  image._frameType = info.keyframe ? webrtc::VideoFrameType::kVideoFrameKey
+                                   : webrtc::VideoFrameType::kVideoFrameDelta;
+
+  // The Rust encoders produce a single spatial and temporal layer
+  webrtc::CodecSpecificInfo codec_info;
+  codec_info.codecType = codec_type_;
+  codec_info.end_of_picture = true;
+  switch (codec_type_) {
+    case webrtc::kVideoCodecH264:
+      codec_info.codecSpecific.H264.packetization_mode =
+          webrtc::H264PacketizationMode::NonInterleaved;
+      codec_info.codecSpecific.H264.temporal_idx = webrtc::kNoTemporalIdx;
+      codec_info.codecSpecific.H264.base_layer_sync = false;
+      codec_info.codecSpecific.H264.idr_frame = info.keyframe;
+      break;
+    case webrtc::kVideoCodecVP8:
+      codec_info.codecSpecific.VP8.nonReference = false;
+      codec_info.codecSpecific.VP8.temporalIdx = webrtc::kNoTemporalIdx;
+      codec_info.codecSpecific.VP8.layerSync = false;
+      codec_info.codecSpecific.VP8.keyIdx = webrtc::kNoKeyIdx;
+      break;
+    case webrtc::kVideoCodecVP9: {
+      auto& vp9 = codec_info.codecSpecific.VP9;
+      vp9.first_frame_in_picture = true;
+      vp9.inter_pic_predicted = !info.keyframe;
+      vp9.flexible_mode = false;
+      vp9.non_ref_for_inter_layer_pred = true;
+      vp9.temporal_idx = webrtc::kNoTemporalIdx;
+      vp9.temporal_up_switch = true;
+      vp9.inter_layer_predicted = false;
+      vp9.gof_idx = 0;
+      vp9.num_spatial_layers = 1;
+      vp9.first_active_layer = 0;
+      // The scalability structure is sent with each keyframe
+      vp9.ss_data_available = info.keyframe;
+      if (info.keyframe) {
+        vp9.spatial_layer_resolution_present = true;
+        vp9.width[0] = info.width;
+        vp9.height[0] = info.height;
+        vp9.gof.SetGofInfoVP9(webrtc::kTemporalStructureMode1);
+      }
+      break;
+    }
+    default:
+      // AV1 has no codec specific info, the dependency descriptor extension is
+      // omitted and the receiver relies on the OBUs
+      break;
+  }
+
+  auto result = callback_->OnEncodedImage(image, &codec_info);
+  return result.error == webrtc::EncodedImageCallback::Result::OK;
+}
+
+DecodedImageSink::DecodedImageSink(webrtc::DecodedImageCallback* callback)
+    : callback_(callback) {}
+
+void DecodedImageSink::on_decoded_frame(std::unique_ptr<VideoFrame> frame,
+                                        uint32_t rtp_timestamp) const {
+  webrtc::VideoFrame decoded = frame->get();
+  decoded.set_timestamp(rtp_timestamp);  // Used to match the encoded image
+  callback_->Decoded(decoded);
+}
+
+RustVideoEncoder::RustVideoEncoder(const webrtc::SdpVideoFormat& format,
+                                   rust::Box<VideoEncoderWrapper> encoder)
+    : codec_type_(webrtc::PayloadStringToCodecType(format.name)),
+      encoder_(std::move(encoder)) {}
+
+int32_t RustVideoEncoder::InitEncode(
+    const webrtc::VideoCodec* codec_settings,
+    const webrtc::VideoEncoder::Settings& settings) {
+  if (!codec_settings)
+    return WEBRTC_VIDEO_CODEC_ERR_PARAMETER;
+
+  VideoEncoderSettings rust_settings{};
+  rust_settings.width = codec_settings->width;
+  rust_settings.height = codec_settings->height;
+  rust_settings.start_bitrate = codec_settings->startBitrate;
+  rust_settings.max_bitrate = codec_settings->maxBitrate;
+  rust_settings.min_bitrate = codec_settings->minBitrate;
+  rust_settings.max_framerate = codec_settings->maxFramerate;
+  rust_settings.number_of_cores = settings.number_of_cores;
+  rust_settings.max_payload_size = settings.max_payload_size;
+  return encoder_->init_encode(rust_settings);
+}
+
+int32_t RustVideoEncoder::RegisterEncodeCompleteCallback(
+    webrtc::EncodedImageCallback* callback) {
+  sink_ = std::make_unique<EncodedImageSink>(callback, codec_type_);
+  return WEBRTC_VIDEO_CODEC_OK;
+}
+
+int32_t RustVideoEncoder::Release() {
+  sink_ = nullptr;
+  return encoder_->release();
+}
+
+int32_t RustVideoEncoder::Encode(
+    const webrtc::VideoFrame& frame,
+    const std::vector<webrtc::VideoFrameType>* frame_types) {
+  if (!sink_)
+    return WEBRTC_VIDEO_CODEC_UNINITIALIZED;
+
+  bool keyframe = false;
+  if (frame_types) {
+    for (auto frame_type : *frame_types) {
+      if (frame_type == webrtc::VideoFrameType::kVideoFrameKey) {
+        keyframe = true;
+        break;
+      }
+    }
+  }
+
+  VideoFrame rust_frame(frame);
+  return encoder_->encode(rust_frame, keyframe, *sink_);
+}
+
+void RustVideoEncoder::SetRates(const RateControlParameters& parameters) {
+  encoder_->set_rates(parameters.bitrate.get_sum_bps(),
+                      parameters.framerate_fps);
+}
+
+webrtc::VideoEncoder::EncoderInfo RustVideoEncoder::GetEncoderInfo() const {
+  EncoderInfo info;
+  info.implementation_name = "RustVideoEncoder";
+  info.supports_native_handle = false;
+  info.is_hardware_accelerated = false;
+  return info;
+}
+
+RustVideoDecoder::RustVideoDecoder(rust::Box<VideoDecoderWrapper> decoder)
+    : decoder_(std::move(decoder)) {}
+
+bool RustVideoDecoder::Configure(const Settings& settings) {
+  VideoDecoderSettings rust_settings{};
+  rust_settings.max_width = settings.max_render_resolution().Width();
+  rust_settings.max_height = settings.max_render_resolution().Height();
+  rust_settings.number_of_cores = settings.number_of_cores();
+  return decoder_->configure(rust_settings);
+}
+
+int32_t RustVideoDecoder::Decode(const webrtc::EncodedImage& input_image,
+                                 bool missing_frames,
+                                 int64_t render_time_ms) {
+  if (!sink_)
+    return WEBRTC_VIDEO_CODEC_UNINITIALIZED;
+
+  EncodedImageInfo info{};
+  info.rtp_timestamp = input_image.Timestamp();
+  info.capture_time_ms = input_image.capture_time_ms_;
+  info.keyframe =
+      input_image._frameType == webrtc::VideoFrameType::kVideoFrameKey;
+  info.width = input_image._encodedWidth;
+  info.height = input_image._encodedHeight;
+
+  return decoder_->decode(
+      rust::Slice<const uint8_t>(input_image.data(), input_image.size()), info,
+      *sink_);
+}
+
+int32_t RustVideoDecoder::RegisterDecodeCompleteCallback(
+    webrtc::DecodedImageCallback* callback) {
+  sink_ = std::make_unique<DecodedImageSink>(callback);
+  return WEBRTC_VIDEO_CODEC_OK;
+}
+
+int32_t RustVideoDecoder::Release() {
+  sink_ = nullptr;
+  return decoder_->release();
+}
+
+webrtc::VideoDecoder::DecoderInfo RustVideoDecoder::GetDecoderInfo() const {
+  DecoderInfo info;
+  info.implementation_name = ImplementationName();
+  info.is_hardware_accelerated = false;
+  return info;
+}
+
+const char* RustVideoDecoder::ImplementationName() const {
+  return "RustVideoDecoder";
+}
+
+RustVideoEncoderFactory::RustVideoEncoderFactory(
+    rust::Box<VideoEncoderFactoryWrapper> factory)
+    : factory_(std::move(factory)) {}
+
+std::vector<webrtc::SdpVideoFormat>
+RustVideoEncoderFactory::GetSupportedFormats() const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : factory_->supported_formats())
+    formats.push_back(to_native_sdp_video_format(format));
+
+  return formats;
+}
+
+std::unique_ptr<webrtc::VideoEncoder>
+RustVideoEncoderFactory::CreateVideoEncoder(
+    const webrtc::SdpVideoFormat& format) {
+  return std::make_unique<RustVideoEncoder>(
+      format, factory_->create_encoder(to_rust_sdp_video_format(format)));
+}
+
+RustVideoDecoderFactory::RustVideoDecoderFactory(
+    rust::Box<VideoDecoderFactoryWrapper> factory)
+    : factory_(std::move(factory)) {}
+
+std::vector<webrtc::SdpVideoFormat>
+RustVideoDecoderFactory::GetSupportedFormats() const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : factory_->supported_formats())
+    formats.push_back(to_native_sdp_video_format(format));
+
+  return formats;
+}
+
+std::unique_ptr<webrtc::VideoDecoder>
+RustVideoDecoderFactory::CreateVideoDecoder(
+    const webrtc::SdpVideoFormat& format) {
+  return std::make_unique<RustVideoDecoder>(
+      factory_->create_decoder(to_rust_sdp_video_format(format)));
+}
+
+void register_video_encoder_factory(
+    const PeerConnectionFactory& factory,
+    rust::Box<VideoEncoderFactoryWrapper> encoder_factory) {
+  factory.video_encoder_factory()->add_factory(
+      std::make_unique<RustVideoEncoderFactory>(std::move(encoder_factory)));
+}
+
+void register_video_decoder_factory(
+    const PeerConnectionFactory& factory,
+    rust::Box<VideoDecoderFactoryWrapper> decoder_factory) {
+  factory.video_decoder_factory()->add_factory(
+      std::make_unique<RustVideoDecoderFactory>(std::move(decoder_factory)));
+}
+
+}  // namespace livekit
@@ -0,0 +1,242 @@
+use crate::impl_thread_safety;
+use crate::video_frame::ffi::VideoFrame;
+
+// Return codes expected by libwebrtc (see video_error_codes.h)
+pub const VIDEO_CODEC_OK: i32 = 0;
+pub const VIDEO_CODEC_ERROR: i32 = -1;
+pub const VIDEO_CODEC_MEMORY: i32 = -3;
+pub const VIDEO_CODEC_ERR_PARAMETER: i32 = -4;
+pub const VIDEO_CODEC_UNINITIALIZED: i32 = -7;
+pub const VIDEO_CODEC_FALLBACK_SOFTWARE: i32 = -13;
+
+#[cxx::bridge(namespace = "livekit")]
+pub mod ffi {
+    #[derive(Debug, Clone)]
+    pub struct SdpVideoFormatParameter {
+        pub key: String,
+        pub value: String,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct SdpVideoFormat {
+        pub name: String,
+        pub parameters: Vec<SdpVideoFormatParameter>,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct VideoEncoderSettings {
+        pub width: u16,
+        pub height: u16,
+        pub start_bitrate: u32, // kbps
+        pub max_bitrate: u32,   // kbps
+        pub min_bitrate: u32,   // kbps
+        pub max_framerate: u32,
+        pub number_of_cores: i32,
+        pub max_payload_size: usize,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct VideoDecoderSettings {
+        pub max_width: i32,
+        pub max_height: i32,
+        pub number_of_cores: i32,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct EncodedImageInfo {
+        pub rtp_timestamp: u32,
+        pub capture_time_ms: i64,
+        pub keyframe: bool,
+        pub width: u32,
+        pub height: u32,
+    }
+
+    extern "C++" {
+        include!("livekit/video_frame.h");
+        include!("livekit/peer_connection_factory.h");
+
+        type VideoFrame = crate::video_frame::ffi::VideoFrame;
+        type PeerConnectionFactory = crate::peer_connection_factory::ffi::PeerConnectionFactory;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/video_codec.h");
+
+        type EncodedImageSink;
+        type DecodedImageSink;
+
+        fn on_encoded_image(self: &EncodedImageSink, data: &[u8], info: EncodedImageInfo) -> bool;
+
+        fn on_decoded_frame(
+            self: &DecodedImageSink,
+            frame: UniquePtr<VideoFrame>,
+            rtp_timestamp: u32,
+        );
+
+        fn register_video_encoder_factory(
+            factory: &PeerConnectionFactory,
+            encoder_factory: Box<VideoEncoderFactoryWrapper>,
+        );
+
+        fn register_video_decoder_factory(
+            factory: &PeerConnectionFactory,
+            decoder_factory: Box<VideoDecoderFactoryWrapper>,
+        );
+    }
+
+    extern "Rust" {
+        type VideoEncoderFactoryWrapper;
+        type VideoEncoderWrapper;
+        type VideoDecoderFactoryWrapper;
+        type VideoDecoderWrapper;
+
+        fn supported_formats(self: &VideoEncoderFactoryWrapper) -> Vec<SdpVideoFormat>;
+        fn create_encoder(
+            self: &VideoEncoderFactoryWrapper,
+            format: &SdpVideoFormat,
+        ) -> Box<VideoEncoderWrapper>;
+
+        fn init_encode(self: &mut VideoEncoderWrapper, settings: &VideoEncoderSettings) -> i32;
+        fn encode(
+            self: &mut VideoEncoderWrapper,
+            frame: &VideoFrame,
+            keyframe: bool,
+            sink: &EncodedImageSink,
+        ) -> i32;
+        fn set_rates(self: &mut VideoEncoderWrapper, bitrate_bps: u32, framerate: f64);
+        fn release(self: &mut VideoEncoderWrapper) -> i32;
+
+        fn supported_formats(self: &VideoDecoderFactoryWrapper) -> Vec<SdpVideoFormat>;
+        fn create_decoder(
+            self: &VideoDecoderFactoryWrapper,
+            format: &SdpVideoFormat,
+        ) -> Box<VideoDecoderWrapper>;
+
+        fn configure(self: &mut VideoDecoderWrapper, settings: &VideoDecoderSettings) -> bool;
+        fn decode(
+            self: &mut VideoDecoderWrapper,
+            data: &[u8],
+            info: EncodedImageInfo,
+            sink: &DecodedImageSink,
+        ) -> i32;
+        fn release(self: &mut VideoDecoderWrapper) -> i32;
+    }
+}
+
+// The sinks are only borrowed by Rust during an encode/decode call, but an implementation may
+// hand them over to another thread (e.g a scoped thread pool) before returning.
+impl_thread_safety!(ffi::EncodedImageSink, Send + Sync);
+impl_thread_safety!(ffi::DecodedImageSink, Send + Sync);
+
+pub trait VideoEncoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat>;
+    fn create_encoder(&self, format: &ffi::SdpVideoFormat) -> Box<dyn VideoEncoder>;
+}
+
+pub trait VideoEncoder: Send {
+    fn init_encode(&mut self, settings: &ffi::VideoEncoderSettings) -> i32;
+    fn encode(&mut self, frame: &VideoFrame, keyframe: bool, sink: &ffi::EncodedImageSink) -> i32;
+    fn set_rates(&mut self, bitrate_bps: u32, framerate: f64);
+    fn release(&mut self) -> i32;
+}
+
+pub trait VideoDecoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat>;
+    fn create_decoder(&self, format: &ffi::SdpVideoFormat) -> Box<dyn VideoDecoder>;
+}
+
+pub trait VideoDecoder: Send {
+    fn configure(&mut self, settings: &ffi::VideoDecoderSettings) -> bool;
+    fn decode(
+        &mut self,
+        data: &[u8],
+        info: ffi::EncodedImageInfo,
+        sink: &ffi::DecodedImageSink,
+    ) -> i32;
+    fn release(&mut self) -> i32;
+}
+
+pub struct VideoEncoderFactoryWrapper {
+    factory: Box<dyn VideoEncoderFactory>,
+}
+
+impl VideoEncoderFactoryWrapper {
+    pub fn new(factory: Box<dyn VideoEncoderFactory>) -> Self {
+        Self { factory }
+    }
+
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat> {
+        self.factory.supported_formats()
+    }
+
+    fn create_encoder(&self, format: &ffi::SdpVideoFormat) -> Box<VideoEncoderWrapper> {
+        Box::new(VideoEncoderWrapper {
+            encoder: self.factory.create_encoder(format),
+        })
+    }
+}
+
+pub struct VideoEncoderWrapper {
+    encoder: Box<dyn VideoEncoder>,
+}
+
+impl VideoEncoderWrapper {
+    fn init_encode(&mut self, settings: &ffi::VideoEncoderSettings) -> i32 {
+        self.encoder.init_encode(settings)
+    }
+
+    fn encode(&mut self, frame: &VideoFrame, keyframe: bool, sink: &ffi::EncodedImageSink) -> i32 {
+        self.encoder.encode(frame, keyframe, sink)
+    }
+
+    fn set_rates(&mut self, bitrate_bps: u32, framerate: f64) {
+        self.encoder.set_rates(bitrate_bps, framerate)
+    }
+
+    fn release(&mut self) -> i32 {
+        self.encoder.release()
+    }
+}
+
+pub struct VideoDecoderFactoryWrapper {
+    factory: Box<dyn VideoDecoderFactory>,
+}
+
+impl VideoDecoderFactoryWrapper {
+    pub fn new(factory: Box<dyn VideoDecoderFactory>) -> Self {
+        Self { factory }
+    }
+
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat> {
+        self.factory.supported_formats()
+    }
+
+    fn create_decoder(&self, format: &ffi::SdpVideoFormat) -> Box<VideoDecoderWrapper> {
+        Box::new(VideoDecoderWrapper {
+            decoder: self.factory.create_decoder(format),
+        })
+    }
+}
+
+pub struct VideoDecoderWrapper {
+    decoder: Box<dyn VideoDecoder>,
+}
+
+impl VideoDecoderWrapper {
+    fn configure(&mut self, settings: &ffi::VideoDecoderSettings) -> bool {
+        self.decoder.configure(settings)
+    }
+
+    fn decode(
+        &mut self,
+        data: &[u8],
+        info: ffi::EncodedImageInfo,
+        sink: &ffi::DecodedImageSink,
+    ) -> i32 {
+        self.decoder.decode(data, info, sink)
+    }
+
+    fn release(&mut self) -> i32 {
+        self.decoder.release()
+    }
+}
@@ -16,6 +16,8 @@
 
 #include "livekit/video_decoder_factory.h"
 
+#include <algorithm>
+
 #include "api/video_codecs/builtin_video_decoder_factory.h"
 #include "api/video_codecs/builtin_video_encoder_factory.h"
 #include "api/video_codecs/sdp_video_format.h"
@@ -31,23 +33,38 @@ VideoDecoderFactory::VideoDecoderFactory() {
 #ifdef __APPLE__
   factories_.push_back(livekit::CreateObjCVideoDecoderFactory());
 #endif
+}
 
-  // TODO(theomonnom): Add other HW decoders here
+void VideoDecoderFactory::add_factory(
+    std::unique_ptr<webrtc::VideoDecoderFactory> factory) {
+  webrtc::MutexLock lock(&mutex_);
+  factories_.insert(factories_.begin(), std::move(factory));
 }
 
 std::vector<webrtc::SdpVideoFormat> VideoDecoderFactory::GetSupportedFormats()
     const {
+  webrtc::MutexLock lock(&mutex_);
+  // A codec supported by several factories is only advertised once (otherwise
+  // it gets multiple payload types), the first factory is the one used by
+  // CreateVideoDecoder
   std::vector<webrtc::SdpVideoFormat> formats;
   for (const auto& factory : factories_) {
-    auto supported_formats = factory->GetSupportedFormats();
-    formats.insert(formats.end(), supported_formats.begin(),
-                   supported_formats.end());
+    for (const auto& format : factory->GetSupportedFormats()) {
+      bool duplicate = std::any_of(
+          formats.begin(), formats.end(),
+          [&](const webrtc::SdpVideoFormat& other) {
+            return other.IsSameCodec(format);
+          });
+      if (!duplicate)
+        formats.push_back(format);
+    }
   }
   return formats;
 }
 
 std::unique_ptr<webrtc::VideoDecoder> VideoDecoderFactory::CreateVideoDecoder(
     const webrtc::SdpVideoFormat& format) {
+  webrtc::MutexLock lock(&mutex_);
   for (const auto& factory : factories_) {
     for (const auto& supported_format : factory->GetSupportedFormats()) {
       if (supported_format.IsSameCodec(format))
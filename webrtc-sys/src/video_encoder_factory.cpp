@@ -16,11 +16,14 @@
 
 #include "livekit/video_encoder_factory.h"
 
+#include <algorithm>
+
 #include "api/video_codecs/builtin_video_decoder_factory.h"
 #include "api/video_codecs/builtin_video_encoder_factory.h"
 #include "api/video_codecs/sdp_video_format.h"
 #include "api/video_codecs/video_encoder.h"
 #include "livekit/objc_video_factory.h"
+#include "livekit/passthrough_codec.h"
 #include "media/base/media_constants.h"
 #include "rtc_base/logging.h"
 
@@ -32,27 +35,49 @@ VideoEncoderFactory::VideoEncoderFactory() {
 #ifdef __APPLE__
   factories_.push_back(livekit::CreateObjCVideoEncoderFactory());
 #endif
+}
 
-  // TODO(theomonnom): Add other HW encoders here
+void VideoEncoderFactory::add_factory(
+    std::unique_ptr<webrtc::VideoEncoderFactory> factory) {
+  webrtc::MutexLock lock(&mutex_);
+  factories_.insert(factories_.begin(), std::move(factory));
 }
 
 std::vector<webrtc::SdpVideoFormat> VideoEncoderFactory::GetSupportedFormats()
     const {
+  webrtc::MutexLock lock(&mutex_);
+  // A codec supported by several factories is only advertised once (otherwise
+  // it gets multiple payload types), the first factory is the one used by
+  // CreateVideoEncoder
   std::vector<webrtc::SdpVideoFormat> formats;
   for (const auto& factory : factories_) {
-    auto supported_formats = factory->GetSupportedFormats();
-    formats.insert(formats.end(), supported_formats.begin(),
-                   supported_formats.end());
+    for (const auto& format : factory->GetSupportedFormats()) {
+      bool duplicate = std::any_of(
+          formats.begin(), formats.end(),
+          [&](const webrtc::SdpVideoFormat& other) {
+            return other.IsSameCodec(format);
+          });
+      if (!duplicate)
+        formats.push_back(format);
+    }
   }
   return formats;
 }
 
 std::unique_ptr<webrtc::VideoEncoder> VideoEncoderFactory::CreateVideoEncoder(
     const webrtc::SdpVideoFormat& format) {
+  webrtc::MutexLock lock(&mutex_);
   for (const auto& factory : factories_) {
     for (const auto& supported_format : factory->GetSupportedFormats()) {
-      if (supported_format.IsSameCodec(format))
-        return factory->CreateVideoEncoder(format);
+      if (supported_format.IsSameCodec(format)) {
+        auto encoder = factory->CreateVideoEncoder(format);
+        if (!encoder)
+          return nullptr;
+
+        // Transparent unless the track comes from an encoded source
+        return std::make_unique<PassthroughVideoEncoder>(
+            webrtc::PayloadStringToCodecType(format.name), std::move(encoder));
+      }
     }
   }
 
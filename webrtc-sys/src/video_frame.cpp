@@ -16,8 +16,10 @@
 
 #include "livekit/video_frame.h"
 
+#include <algorithm>
 #include <memory>
 
+#include "api/rtp_packet_infos.h"
 #include "api/video/video_frame.h"
 
 namespace livekit {
@@ -49,6 +51,14 @@ uint32_t VideoFrame::timestamp() const {
   return frame_.timestamp();
 }
 
+int64_t VideoFrame::receive_time_us() const {
+  int64_t receive_time_us = -1;
+  for (const webrtc::RtpPacketInfo& info : frame_.packet_infos()) {
+    receive_time_us = std::max(receive_time_us, info.receive_time().us());
+  }
+  return receive_time_us;
+}
+
 VideoRotation VideoFrame::rotation() const {
   return static_cast<VideoRotation>(frame_.rotation());
 }
@@ -70,6 +80,14 @@ void VideoFrameBuilder::set_timestamp_us(int64_t timestamp_us) {
   builder_.set_timestamp_us(timestamp_us);
 }
 
+void VideoFrameBuilder::set_rtp_timestamp(uint32_t rtp_timestamp) {
+  builder_.set_timestamp_rtp(rtp_timestamp);
+}
+
+void VideoFrameBuilder::set_ntp_time_ms(int64_t ntp_time_ms) {
+  builder_.set_ntp_time_ms(ntp_time_ms);
+}
+
 void VideoFrameBuilder::set_rotation(VideoRotation rotation) {
   builder_.set_rotation(static_cast<webrtc::VideoRotation>(rotation));
 }
@@ -30,6 +30,7 @@ pub mod ffi {
         fn ntp_time_ms(self: &VideoFrame) -> i64;
         fn transport_frame_id(self: &VideoFrame) -> u32;
         fn timestamp(self: &VideoFrame) -> u32;
+        fn receive_time_us(self: &VideoFrame) -> i64;
         fn rotation(self: &VideoFrame) -> VideoRotation;
         unsafe fn video_frame_buffer(self: &VideoFrame) -> UniquePtr<VideoFrameBuffer>;
 
@@ -37,6 +38,8 @@ pub mod ffi {
         type VideoFrameBuilder;
         fn new_video_frame_builder() -> UniquePtr<VideoFrameBuilder>;
         fn set_timestamp_us(self: Pin<&mut VideoFrameBuilder>, timestamp_us: i64);
+        fn set_rtp_timestamp(self: Pin<&mut VideoFrameBuilder>, rtp_timestamp: u32);
+        fn set_ntp_time_ms(self: Pin<&mut VideoFrameBuilder>, ntp_time_ms: i64);
         fn set_rotation(self: Pin<&mut VideoFrameBuilder>, rotation: VideoRotation);
         fn set_id(self: Pin<&mut VideoFrameBuilder>, id: u16);
         fn set_video_frame_buffer(self: Pin<&mut VideoFrameBuilder>, buffer: &VideoFrameBuffer);
@@ -16,6 +16,14 @@
 
 #include "livekit/video_frame_buffer.h"
 
+#include <algorithm>
+#include <memory>
+#include <vector>
+
+#include "common_video/include/video_frame_buffer.h"
+#include "libyuv/planar_functions.h"
+#include "libyuv/scale.h"
+
 namespace livekit {
 
 VideoFrameBuffer::VideoFrameBuffer(
@@ -192,6 +200,39 @@ std::unique_ptr<I420Buffer> new_i420_buffer(int width, int height) {
       webrtc::I420Buffer::Create(width, height));
 }
 
+std::unique_ptr<I420ABuffer> new_i420a_buffer(int width, int height) {
+  // libwebrtc has no owning I420A buffer, the planes are kept alive by the
+  // release callback of the wrapper
+  rtc::scoped_refptr<webrtc::I420Buffer> yuv =
+      webrtc::I420Buffer::Create(width, height);
+  auto alpha = std::make_shared<std::vector<uint8_t>>(
+      static_cast<size_t>(width) * height, 255);
+  return std::make_unique<I420ABuffer>(webrtc::WrapI420ABuffer(
+      width, height, yuv->DataY(), yuv->StrideY(), yuv->DataU(),
+      yuv->StrideU(), yuv->DataV(), yuv->StrideV(), alpha->data(), width,
+      [yuv, alpha] {}));
+}
+
+std::unique_ptr<I422Buffer> new_i422_buffer(int width, int height) {
+  return std::make_unique<I422Buffer>(
+      webrtc::I422Buffer::Create(width, height));
+}
+
+std::unique_ptr<I444Buffer> new_i444_buffer(int width, int height) {
+  return std::make_unique<I444Buffer>(
+      webrtc::I444Buffer::Create(width, height));
+}
+
+std::unique_ptr<I010Buffer> new_i010_buffer(int width, int height) {
+  return std::make_unique<I010Buffer>(
+      webrtc::I010Buffer::Create(width, height));
+}
+
+std::unique_ptr<NV12Buffer> new_nv12_buffer(int width, int height) {
+  return std::make_unique<NV12Buffer>(
+      webrtc::NV12Buffer::Create(width, height));
+}
+
 std::unique_ptr<I420Buffer> copy_i420_buffer(
     const std::unique_ptr<I420Buffer>& i420) {
   return std::make_unique<I420Buffer>(webrtc::I420Buffer::Copy(*i420->get()));
@@ -200,6 +241,69 @@ std::unique_ptr<I420Buffer> copy_i420_buffer(
 I420Buffer::I420Buffer(rtc::scoped_refptr<webrtc::I420BufferInterface> buffer)
     : PlanarYuv8Buffer(buffer) {}
 
+const webrtc::I420BufferInterface* I420Buffer::buffer() const {
+  return buffer_->GetI420();
+}
+
+std::unique_ptr<I420Buffer> I420Buffer::scale(int scaled_width,
+                                              int scaled_height,
+                                              ScaleFilter filter) const {
+  const webrtc::I420BufferInterface* src = buffer();
+  rtc::scoped_refptr<webrtc::I420Buffer> dst =
+      webrtc::I420Buffer::Create(scaled_width, scaled_height);
+
+  libyuv::I420Scale(src->DataY(), src->StrideY(), src->DataU(),
+                    src->StrideU(), src->DataV(), src->StrideV(),
+                    src->width(), src->height(), dst->MutableDataY(),
+                    dst->StrideY(), dst->MutableDataU(), dst->StrideU(),
+                    dst->MutableDataV(), dst->StrideV(), scaled_width,
+                    scaled_height, static_cast<libyuv::FilterMode>(filter));
+
+  return std::make_unique<I420Buffer>(dst);
+}
+
+std::unique_ptr<I420Buffer> I420Buffer::crop(int x,
+                                             int y,
+                                             int width,
+                                             int height) const {
+  const webrtc::I420BufferInterface* src = buffer();
+  x &= ~1;
+  y &= ~1;
+  width = std::min(width, src->width() - x);
+  height = std::min(height, src->height() - y);
+
+  rtc::scoped_refptr<webrtc::I420Buffer> dst =
+      webrtc::I420Buffer::Create(width, height);
+
+  libyuv::I420Copy(
+      src->DataY() + y * src->StrideY() + x, src->StrideY(),
+      src->DataU() + (y / 2) * src->StrideU() + x / 2, src->StrideU(),
+      src->DataV() + (y / 2) * src->StrideV() + x / 2, src->StrideV(),
+      dst->MutableDataY(), dst->StrideY(), dst->MutableDataU(), dst->StrideU(),
+      dst->MutableDataV(), dst->StrideV(), width, height);
+
+  return std::make_unique<I420Buffer>(dst);
+}
+
+std::unique_ptr<I420Buffer> I420Buffer::rotate(int rotation) const {
+  return std::make_unique<I420Buffer>(webrtc::I420Buffer::Rotate(
+      *buffer(), static_cast<webrtc::VideoRotation>(rotation)));
+}
+
+std::unique_ptr<I420Buffer> I420Buffer::mirror() const {
+  const webrtc::I420BufferInterface* src = buffer();
+  rtc::scoped_refptr<webrtc::I420Buffer> dst =
+      webrtc::I420Buffer::Create(src->width(), src->height());
+
+  libyuv::I420Mirror(src->DataY(), src->StrideY(), src->DataU(),
+                     src->StrideU(), src->DataV(), src->StrideV(),
+                     dst->MutableDataY(), dst->StrideY(), dst->MutableDataU(),
+                     dst->StrideU(), dst->MutableDataV(), dst->StrideV(),
+                     src->width(), src->height());
+
+  return std::make_unique<I420Buffer>(dst);
+}
+
 I420ABuffer::I420ABuffer(
     rtc::scoped_refptr<webrtc::I420ABufferInterface> buffer)
     : I420Buffer(buffer) {}
@@ -14,6 +14,16 @@ pub mod ffi {
         NV12,
     }
 
+    // Same values as libyuv::FilterMode
+    #[derive(Debug)]
+    #[repr(i32)]
+    pub enum ScaleFilter {
+        None,
+        Linear,
+        Bilinear,
+        Box,
+    }
+
     unsafe extern "C++" {
         include!("livekit/video_frame_buffer.h");
 
@@ -29,6 +39,11 @@ pub mod ffi {
         type I444Buffer;
         type I010Buffer;
         type NV12Buffer;
+        type I420BufferPool;
+        type I422BufferPool;
+        type I444BufferPool;
+        type I010BufferPool;
+        type NV12BufferPool;
 
         fn buffer_type(self: &VideoFrameBuffer) -> VideoFrameBufferType;
         fn width(self: &VideoFrameBuffer) -> u32;
@@ -72,7 +87,28 @@ pub mod ffi {
         fn stride_a(self: &I420ABuffer) -> u32;
         fn data_a(self: &I420ABuffer) -> *const u8;
 
+        fn scale(
+            self: &I420Buffer,
+            scaled_width: i32,
+            scaled_height: i32,
+            filter: ScaleFilter,
+        ) -> UniquePtr<I420Buffer>;
+        fn crop(
+            self: &I420Buffer,
+            x: i32,
+            y: i32,
+            width: i32,
+            height: i32,
+        ) -> UniquePtr<I420Buffer>;
+        fn rotate(self: &I420Buffer, rotation: i32) -> UniquePtr<I420Buffer>;
+        fn mirror(self: &I420Buffer) -> UniquePtr<I420Buffer>;
+
         fn new_i420_buffer(width: i32, height: i32) -> UniquePtr<I420Buffer>;
+        fn new_i420a_buffer(width: i32, height: i32) -> UniquePtr<I420ABuffer>;
+        fn new_i422_buffer(width: i32, height: i32) -> UniquePtr<I422Buffer>;
+        fn new_i444_buffer(width: i32, height: i32) -> UniquePtr<I444Buffer>;
+        fn new_i010_buffer(width: i32, height: i32) -> UniquePtr<I010Buffer>;
+        fn new_nv12_buffer(width: i32, height: i32) -> UniquePtr<NV12Buffer>;
         fn copy_i420_buffer(i420: &UniquePtr<I420Buffer>) -> UniquePtr<I420Buffer>;
 
         unsafe fn yuv_to_vfb(yuv: *const PlanarYuvBuffer) -> *const VideoFrameBuffer;
@@ -87,6 +123,31 @@ pub mod ffi {
         unsafe fn i010_to_yuv16b(i010: *const I010Buffer) -> *const PlanarYuv16BBuffer;
         unsafe fn nv12_to_biyuv8(nv12: *const NV12Buffer) -> *const BiplanarYuv8Buffer;
 
+        fn new_i420_buffer_pool(max_number_of_buffers: usize) -> SharedPtr<I420BufferPool>;
+        fn create(self: &I420BufferPool, width: i32, height: i32) -> UniquePtr<I420Buffer>;
+        fn release(self: &I420BufferPool);
+        fn resize(self: &I420BufferPool, max_number_of_buffers: usize) -> bool;
+
+        fn new_i422_buffer_pool(max_number_of_buffers: usize) -> SharedPtr<I422BufferPool>;
+        fn create(self: &I422BufferPool, width: i32, height: i32) -> UniquePtr<I422Buffer>;
+        fn release(self: &I422BufferPool);
+        fn resize(self: &I422BufferPool, max_number_of_buffers: usize) -> bool;
+
+        fn new_i444_buffer_pool(max_number_of_buffers: usize) -> SharedPtr<I444BufferPool>;
+        fn create(self: &I444BufferPool, width: i32, height: i32) -> UniquePtr<I444Buffer>;
+        fn release(self: &I444BufferPool);
+        fn resize(self: &I444BufferPool, max_number_of_buffers: usize) -> bool;
+
+        fn new_i010_buffer_pool(max_number_of_buffers: usize) -> SharedPtr<I010BufferPool>;
+        fn create(self: &I010BufferPool, width: i32, height: i32) -> UniquePtr<I010Buffer>;
+        fn release(self: &I010BufferPool);
+        fn resize(self: &I010BufferPool, max_number_of_buffers: usize) -> bool;
+
+        fn new_nv12_buffer_pool(max_number_of_buffers: usize) -> SharedPtr<NV12BufferPool>;
+        fn create(self: &NV12BufferPool, width: i32, height: i32) -> UniquePtr<NV12Buffer>;
+        fn release(self: &NV12BufferPool);
+        fn resize(self: &NV12BufferPool, max_number_of_buffers: usize) -> bool;
+
         fn _unique_video_frame_buffer() -> UniquePtr<VideoFrameBuffer>;
     }
 }
@@ -103,3 +164,8 @@ impl_thread_safety!(ffi::I422Buffer, Send + Sync);
 impl_thread_safety!(ffi::I444Buffer, Send + Sync);
 impl_thread_safety!(ffi::I010Buffer, Send + Sync);
 impl_thread_safety!(ffi::NV12Buffer, Send + Sync);
+impl_thread_safety!(ffi::I420BufferPool, Send + Sync);
+impl_thread_safety!(ffi::I422BufferPool, Send + Sync);
+impl_thread_safety!(ffi::I444BufferPool, Send + Sync);
+impl_thread_safety!(ffi::I010BufferPool, Send + Sync);
+impl_thread_safety!(ffi::NV12BufferPool, Send + Sync);
@@ -21,11 +21,13 @@
 #include <memory>
 
 #include "api/media_stream_interface.h"
+#include "api/video/i420_buffer.h"
 #include "api/video/video_frame.h"
 #include "api/video/video_rotation.h"
 #include "audio/remix_resample.h"
 #include "common_audio/include/audio_util.h"
 #include "livekit/media_stream.h"
+#include "livekit/passthrough_codec.h"
 #include "livekit/video_track.h"
 #include "rtc_base/logging.h"
 #include "rtc_base/ref_counted_object.h"
@@ -103,8 +105,9 @@ std::shared_ptr<NativeVideoSink> new_native_video_sink(
   return std::make_shared<NativeVideoSink>(std::move(observer));
 }
 
-VideoTrackSource::InternalSource::InternalSource()
-    : rtc::AdaptedVideoTrackSource(4) {}
+VideoTrackSource::InternalSource::InternalSource(
+    rust::Box<VideoTrackSourceObserverWrapper> observer)
+    : video_adapter_(4), observer_(std::move(observer)) {}
 
 VideoTrackSource::InternalSource::~InternalSource() {}
 
@@ -125,6 +128,77 @@ bool VideoTrackSource::InternalSource::remote() const {
   return false;
 }
 
+bool VideoTrackSource::InternalSource::GetStats(Stats* stats) {
+  webrtc::MutexLock lock(&stats_mutex_);
+  if (!stats_)
+    return false;
+
+  *stats = *stats_;
+  return true;
+}
+
+void VideoTrackSource::InternalSource::AddOrUpdateSink(
+    rtc::VideoSinkInterface<webrtc::VideoFrame>* sink,
+    const rtc::VideoSinkWants& wants) {
+  broadcaster_.AddOrUpdateSink(sink, wants);
+  on_sink_wants_changed(broadcaster_.wants());
+}
+
+void VideoTrackSource::InternalSource::RemoveSink(
+    rtc::VideoSinkInterface<webrtc::VideoFrame>* sink) {
+  broadcaster_.RemoveSink(sink);
+  on_sink_wants_changed(broadcaster_.wants());
+}
+
+bool VideoTrackSource::InternalSource::SupportsEncodedOutput() const {
+  return false;
+}
+
+void VideoTrackSource::InternalSource::GenerateKeyFrame() {
+  // Requested by the passthrough encoders, the encoded source must produce a
+  // keyframe (see passthrough_codec.h)
+  observer_->on_keyframe_request();
+}
+
+void VideoTrackSource::InternalSource::AddEncodedSink(
+    rtc::VideoSinkInterface<webrtc::RecordableEncodedFrame>* sink) {}
+
+void VideoTrackSource::InternalSource::RemoveEncodedSink(
+    rtc::VideoSinkInterface<webrtc::RecordableEncodedFrame>* sink) {}
+
+void VideoTrackSource::InternalSource::on_sink_wants_changed(
+    const rtc::VideoSinkWants& wants) {
+  video_adapter_.OnSinkWants(wants);
+
+  VideoSinkWants rust_wants{};
+  rust_wants.rotation_applied = wants.rotation_applied;
+  rust_wants.black_frames = wants.black_frames;
+  rust_wants.max_pixel_count = wants.max_pixel_count;
+  rust_wants.has_target_pixel_count = wants.target_pixel_count.has_value();
+  rust_wants.target_pixel_count = wants.target_pixel_count.value_or(0);
+  rust_wants.max_framerate_fps = wants.max_framerate_fps;
+  rust_wants.resolution_alignment = wants.resolution_alignment;
+  observer_->on_sink_wants_changed(rust_wants);
+}
+
+void VideoTrackSource::InternalSource::broadcast_frame(
+    const webrtc::VideoFrame& frame) {
+  rtc::scoped_refptr<webrtc::VideoFrameBuffer> buffer =
+      frame.video_frame_buffer();
+
+  if (broadcaster_.wants().rotation_applied &&
+      frame.rotation() != webrtc::kVideoRotation_0 &&
+      buffer->type() == webrtc::VideoFrameBuffer::Type::kI420) {
+    webrtc::VideoFrame rotated_frame(frame);
+    rotated_frame.set_video_frame_buffer(
+        webrtc::I420Buffer::Rotate(*buffer->GetI420(), frame.rotation()));
+    rotated_frame.set_rotation(webrtc::kVideoRotation_0);
+    broadcaster_.OnFrame(rotated_frame);
+  } else {
+    broadcaster_.OnFrame(frame);
+  }
+}
+
 bool VideoTrackSource::InternalSource::on_captured_frame(
     const webrtc::VideoFrame& frame) {
   webrtc::MutexLock lock(&mutex_);
@@ -135,26 +209,39 @@ bool VideoTrackSource::InternalSource::on_captured_frame(
   rtc::scoped_refptr<webrtc::VideoFrameBuffer> buffer =
       frame.video_frame_buffer();
 
-  int adapted_width, adapted_height, crop_width, crop_height, crop_x, crop_y;
-  if (!AdaptFrame(buffer->width(), buffer->height(), aligned_timestamp_us,
-                  &adapted_width, &adapted_height, &crop_width, &crop_height,
-                  &crop_x, &crop_y)) {
+  {
+    webrtc::MutexLock lock(&stats_mutex_);
+    stats_ = Stats{buffer->width(), buffer->height()};
+  }
+
+  if (!broadcaster_.frame_wanted())
+    return false;
+
+  int adapted_width, adapted_height, crop_width, crop_height;
+  if (!video_adapter_.AdaptFrameResolution(
+          buffer->width(), buffer->height(),
+          aligned_timestamp_us * rtc::kNumNanosecsPerMicrosec, &crop_width,
+          &crop_height, &adapted_width, &adapted_height)) {
+    broadcaster_.OnDiscardedFrame();
     return false;
   }
 
+  int crop_x = (buffer->width() - crop_width) / 2;
+  int crop_y = (buffer->height() - crop_height) / 2;
+
   if (adapted_width != frame.width() || adapted_height != frame.height()) {
     buffer = buffer->CropAndScale(crop_x, crop_y, crop_width, crop_height,
                                   adapted_width, adapted_height);
   }
 
   webrtc::VideoRotation rotation = frame.rotation();
-  if (apply_rotation() && rotation != webrtc::kVideoRotation_0) {
-    // If the buffer is I420, rtc::AdaptedVideoTrackSource will handle the
-    // rotation for us.
+  if (broadcaster_.wants().rotation_applied &&
+      rotation != webrtc::kVideoRotation_0) {
+    // If the buffer is I420, broadcast_frame will handle the rotation for us.
     buffer = buffer->ToI420();
   }
 
-  OnFrame(webrtc::VideoFrame::Builder()
+  broadcast_frame(webrtc::VideoFrame::Builder()
               .set_video_frame_buffer(buffer)
               .set_rotation(rotation)
               .set_timestamp_us(aligned_timestamp_us)
@@ -163,26 +250,61 @@ bool VideoTrackSource::InternalSource::on_captured_frame(
   return true;
 }
 
-VideoTrackSource::VideoTrackSource() {
-  source_ = rtc::make_ref_counted<InternalSource>();
+void VideoTrackSource::InternalSource::on_encoded_frame(
+    const webrtc::VideoFrame& frame) {
+  // Encoded frames can't be adapted, only translate the capture timestamp
+  webrtc::MutexLock lock(&mutex_);
+  webrtc::VideoFrame aligned_frame(frame);
+  aligned_frame.set_timestamp_us(timestamp_aligner_.TranslateTimestamp(
+      frame.timestamp_us(), rtc::TimeMicros()));
+  broadcaster_.OnFrame(aligned_frame);
+}
+
+VideoTrackSource::VideoTrackSource(
+    rust::Box<VideoTrackSourceObserverWrapper> observer) {
+  source_ = rtc::make_ref_counted<InternalSource>(std::move(observer));
 }
 
 bool VideoTrackSource::on_captured_frame(
     const std::unique_ptr<VideoFrame>& frame) const {
   auto rtc_frame = frame->get();
-  rtc_frame.set_timestamp_us(
-      rtc::TimeMicros());  // TODO(theomonnom): Expore capture ts to Rust
+  if (rtc_frame.timestamp_us() == 0) {
+    // No capture timestamp provided by the caller
+    rtc_frame.set_timestamp_us(rtc::TimeMicros());
+  }
 
   return source_->on_captured_frame(rtc_frame);
 }
 
+bool VideoTrackSource::on_encoded_frame(rust::Slice<const uint8_t> data,
+                                        EncodedVideoFrameInfo info) const {
+  auto codec_type =
+      webrtc::PayloadStringToCodecType(std::string(info.codec_name));
+  if (codec_type == webrtc::kVideoCodecGeneric) {
+    RTC_LOG(LS_ERROR) << "Unsupported encoded video codec: "
+                      << std::string(info.codec_name);
+    return false;
+  }
+
+  auto buffer = rtc::make_ref_counted<EncodedVideoFrameBuffer>(
+      source_, encoded_sequence_++, codec_type, data.data(), data.size(),
+      info.width, info.height, info.keyframe);
+
+  source_->on_encoded_frame(webrtc::VideoFrame::Builder()
+                                .set_video_frame_buffer(buffer)
+                                .set_timestamp_us(info.timestamp_us)
+                                .build());
+  return true;
+}
+
 rtc::scoped_refptr<VideoTrackSource::InternalSource> VideoTrackSource::get()
     const {
   return source_;
 }
 
-std::shared_ptr<VideoTrackSource> new_video_track_source() {
-  return std::make_shared<VideoTrackSource>();
+std::shared_ptr<VideoTrackSource> new_video_track_source(
+    rust::Box<VideoTrackSourceObserverWrapper> observer) {
+  return std::make_shared<VideoTrackSource>(std::move(observer));
 }
 
 }  // namespace livekit
@@ -21,6 +21,26 @@ pub mod ffi {
         pub max_fps: f64,
     }
 
+    #[derive(Debug)]
+    pub struct VideoSinkWants {
+        pub rotation_applied: bool,
+        pub black_frames: bool,
+        pub max_pixel_count: i32,
+        pub has_target_pixel_count: bool,
+        pub target_pixel_count: i32,
+        pub max_framerate_fps: i32,
+        pub resolution_alignment: i32,
+    }
+
+    #[derive(Debug)]
+    pub struct EncodedVideoFrameInfo {
+        pub codec_name: String,
+        pub width: u32,
+        pub height: u32,
+        pub keyframe: bool,
+        pub timestamp_us: i64,
+    }
+
     extern "C++" {
         include!("livekit/video_frame.h");
         include!("livekit/media_stream_track.h");
@@ -45,7 +65,14 @@ pub mod ffi {
         fn new_native_video_sink(observer: Box<VideoSinkWrapper>) -> SharedPtr<NativeVideoSink>;
 
         fn on_captured_frame(self: &VideoTrackSource, frame: &UniquePtr<VideoFrame>) -> bool;
-        fn new_video_track_source() -> SharedPtr<VideoTrackSource>;
+        fn on_encoded_frame(
+            self: &VideoTrackSource,
+            data: &[u8],
+            info: EncodedVideoFrameInfo,
+        ) -> bool;
+        fn new_video_track_source(
+            observer: Box<VideoTrackSourceObserverWrapper>,
+        ) -> SharedPtr<VideoTrackSource>;
 
         fn video_to_media(track: SharedPtr<VideoTrack>) -> SharedPtr<MediaStreamTrack>;
         unsafe fn media_to_video(track: SharedPtr<MediaStreamTrack>) -> SharedPtr<VideoTrack>;
@@ -62,6 +89,13 @@ pub mod ffi {
             constraints: VideoTrackSourceConstraints,
         );
     }
+
+    extern "Rust" {
+        type VideoTrackSourceObserverWrapper;
+
+        fn on_sink_wants_changed(self: &VideoTrackSourceObserverWrapper, wants: VideoSinkWants);
+        fn on_keyframe_request(self: &VideoTrackSourceObserverWrapper);
+    }
 }
 
 impl_thread_safety!(ffi::VideoTrack, Send + Sync);
@@ -95,3 +129,26 @@ impl VideoSinkWrapper {
         self.observer.on_constraints_changed(constraints);
     }
 }
+
+pub trait VideoTrackSourceObserver: Send + Sync {
+    fn on_sink_wants_changed(&self, wants: ffi::VideoSinkWants);
+    fn on_keyframe_request(&self);
+}
+
+pub struct VideoTrackSourceObserverWrapper {
+    observer: Arc<dyn VideoTrackSourceObserver>,
+}
+
+impl VideoTrackSourceObserverWrapper {
+    pub fn new(observer: Arc<dyn VideoTrackSourceObserver>) -> Self {
+        Self { observer }
+    }
+
+    fn on_sink_wants_changed(&self, wants: ffi::VideoSinkWants) {
+        self.observer.on_sink_wants_changed(wants);
+    }
+
+    fn on_keyframe_request(&self) {
+        self.observer.on_keyframe_request();
+    }
+}
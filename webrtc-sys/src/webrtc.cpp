@@ -20,6 +20,9 @@
 #include <atomic>
 #include <iostream>
 #include <memory>
+#include <string>
+#include <utility>
+#include <vector>
 
 #include "livekit/audio_track.h"
 #include "livekit/media_stream_track.h"
@@ -41,7 +44,62 @@ static webrtc::Mutex g_mutex{};
 // execution of the first init
 static uint32_t g_release_counter(0);
 
-RtcRuntime::RtcRuntime() {
+static std::shared_ptr<rtc::Thread> start_thread(bool with_socket_server,
+                                                 const std::string& name) {
+  std::unique_ptr<rtc::Thread> thread =
+      with_socket_server ? rtc::Thread::CreateWithSocketServer()
+                         : rtc::Thread::Create();
+  thread->SetName(name, nullptr);
+  thread->Start();
+  return std::shared_ptr<rtc::Thread>(thread.release(),
+                                      [](rtc::Thread* thread) {
+                                        thread->Stop();
+                                        delete thread;
+                                      });
+}
+
+// Threads shared by the runtimes, they are assigned round-robin
+class ThreadPool {
+ public:
+  ThreadPool(bool with_socket_server, std::string name)
+      : with_socket_server_(with_socket_server), name_(std::move(name)) {}
+
+  std::shared_ptr<rtc::Thread> get(uint32_t size) {
+    if (size == 0)
+      return start_thread(with_socket_server_, name_);
+
+    webrtc::MutexLock lock(&mutex_);
+    if (threads_.size() < size)
+      threads_.resize(size);
+
+    std::weak_ptr<rtc::Thread>& slot = threads_[next_++ % size];
+    std::shared_ptr<rtc::Thread> thread = slot.lock();
+    if (!thread) {
+      thread = start_thread(with_socket_server_, name_);
+      slot = thread;
+    }
+    return thread;
+  }
+
+ private:
+  const bool with_socket_server_;
+  const std::string name_;
+  webrtc::Mutex mutex_;
+  std::vector<std::weak_ptr<rtc::Thread>> threads_;
+  size_t next_ = 0;
+};
+
+static ThreadPool& network_thread_pool() {
+  static ThreadPool* pool = new ThreadPool(true, "network_thread");
+  return *pool;
+}
+
+static ThreadPool& worker_thread_pool() {
+  static ThreadPool* pool = new ThreadPool(false, "worker_thread");
+  return *pool;
+}
+
+RtcRuntime::RtcRuntime(uint32_t network_threads, uint32_t worker_threads) {
   rtc::LogMessage::LogToDebug(rtc::LS_INFO);
   RTC_LOG(LS_VERBOSE) << "RtcRuntime()";
 
@@ -59,12 +117,8 @@ RtcRuntime::RtcRuntime() {
     g_release_counter++;
   }
 
-  network_thread_ = rtc::Thread::CreateWithSocketServer();
-  network_thread_->SetName("network_thread", &network_thread_);
-  network_thread_->Start();
-  worker_thread_ = rtc::Thread::Create();
-  worker_thread_->SetName("worker_thread", &worker_thread_);
-  worker_thread_->Start();
+  network_thread_ = network_thread_pool().get(network_threads);
+  worker_thread_ = worker_thread_pool().get(worker_threads);
   signaling_thread_ = rtc::Thread::Create();
   signaling_thread_->SetName("signaling_thread", &signaling_thread_);
   signaling_thread_->Start();
@@ -73,9 +127,10 @@ RtcRuntime::RtcRuntime() {
 RtcRuntime::~RtcRuntime() {
   RTC_LOG(LS_VERBOSE) << "~RtcRuntime()";
 
-  worker_thread_->Stop();
+  // The shared threads keep running if another runtime uses them
+  worker_thread_ = nullptr;
   signaling_thread_->Stop();
-  network_thread_->Stop();
+  network_thread_ = nullptr;
 
   {
     webrtc::MutexLock lock(&g_mutex);